@@ -24,6 +24,7 @@ impl NuCompleter {
         let offset = working_set.next_span_start();
         let pos = offset + pos;
         let (output, _err) = parse(&mut working_set, Some("completer"), line.as_bytes(), false);
+        let cwd = working_set.get_cwd();
 
         for stmt in output.stmts.into_iter() {
             if let Statement::Pipeline(pipeline) = stmt {
@@ -106,7 +107,7 @@ impl NuCompleter {
                                     let results = working_set.find_commands_by_prefix(prefix);
 
                                     let prefix = String::from_utf8_lossy(prefix).to_string();
-                                    let results2 = file_path_completion(flat.0, &prefix)
+                                    let results2 = file_path_completion(flat.0, &prefix, &cwd)
                                         .into_iter()
                                         .map(move |x| {
                                             (
@@ -138,7 +139,7 @@ impl NuCompleter {
                                     let prefix = working_set.get_span_contents(flat.0);
                                     let prefix = String::from_utf8_lossy(prefix).to_string();
 
-                                    let results = file_path_completion(flat.0, &prefix);
+                                    let results = file_path_completion(flat.0, &prefix, &cwd);
 
                                     return results
                                         .into_iter()
@@ -212,6 +213,7 @@ impl Completer for NuCompleter {
 fn file_path_completion(
     span: nu_protocol::Span,
     partial: &str,
+    cwd: &std::path::Path,
 ) -> Vec<(nu_protocol::Span, String)> {
     use std::path::{is_separator, Path};
 
@@ -238,7 +240,7 @@ fn file_path_completion(
         (base, rest)
     };
 
-    let base_dir = nu_path::expand_path(&base_dir_name);
+    let base_dir = nu_path::expand_path_with(&base_dir_name, cwd);
     // This check is here as base_dir.read_dir() with base_dir == "" will open the current dir
     // which we don't want in this case (if we did, base_dir would already be ".")
     if base_dir == Path::new("") {
@@ -39,6 +39,11 @@ impl<'src> miette::Diagnostic for CliError<'src> {
         self.0.labels()
     }
 
+    // Related errors are rendered with our source too
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn miette::Diagnostic> + 'a>> {
+        self.0.related()
+    }
+
     // Finally, we redirect the source_code method to our own source.
     fn source_code(&self) -> Option<&dyn SourceCode> {
         Some(&self.1)
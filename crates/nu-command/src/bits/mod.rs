@@ -0,0 +1,5 @@
+mod command;
+mod not;
+
+pub use command::Bits;
+pub use not::SubCommand as BitsNot;
@@ -0,0 +1,86 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "bits not"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("bits not")
+            .input_output_type(Type::Int, Type::Int)
+            .input_output_type(
+                Type::List(Box::new(Type::Int)),
+                Type::List(Box::new(Type::Int)),
+            )
+            .category(Category::Bits)
+    }
+
+    fn usage(&self) -> &str {
+        "Performs logical negation on each bit"
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        let head = call.head;
+        input.map(
+            move |value| operate(value, head),
+            engine_state.ctrlc.clone(),
+        )
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Apply logical negation to a list of numbers",
+                example: "[4 3 2] | bits not",
+                result: Some(Value::List {
+                    vals: vec![
+                        Value::test_int(-5),
+                        Value::test_int(-4),
+                        Value::test_int(-3),
+                    ],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Clear the lowest bits of a number",
+                example: "0xff bit-and (0x0f | bits not)",
+                result: Some(Value::test_int(0xf0)),
+            },
+        ]
+    }
+}
+
+fn operate(value: Value, head: Span) -> Value {
+    match value {
+        Value::Int { val, span } => Value::Int { val: !val, span },
+        other => Value::Error {
+            error: ShellError::UnsupportedInput(
+                format!("Only integers are supported, found {}", other.get_type()),
+                other.span().unwrap_or(head),
+            ),
+        },
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(SubCommand {})
+    }
+}
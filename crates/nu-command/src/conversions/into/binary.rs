@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -20,6 +20,16 @@ impl Command for SubCommand {
                 SyntaxShape::CellPath,
                 "column paths to convert to binary (for table input)",
             )
+            .input_output_type(Type::Binary, Type::Binary)
+            .input_output_type(Type::Number, Type::Binary)
+            .input_output_type(Type::Filesize, Type::Binary)
+            .input_output_type(Type::String, Type::Binary)
+            .input_output_type(Type::Bool, Type::Binary)
+            .input_output_type(Type::Date, Type::Binary)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Binary)),
+            )
             .category(Category::Conversions)
     }
 
@@ -99,6 +109,11 @@ fn into_binary(
 
     match input {
         PipelineData::ByteStream(..) => Ok(input),
+        PipelineData::ExternalStream(stream, span, metadata) => Ok(PipelineData::ByteStream(
+            stream.into_stdout(span),
+            span,
+            metadata,
+        )),
         _ => input.map(
             move |v| {
                 if column_paths.is_empty() {
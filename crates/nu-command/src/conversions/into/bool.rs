@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -20,6 +20,13 @@ impl Command for SubCommand {
                 SyntaxShape::CellPath,
                 "column paths to convert to boolean (for table input)",
             )
+            .input_output_type(Type::Bool, Type::Bool)
+            .input_output_type(Type::Number, Type::Bool)
+            .input_output_type(Type::String, Type::Bool)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Bool)),
+            )
             .category(Category::Conversions)
     }
 
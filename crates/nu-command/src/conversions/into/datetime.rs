@@ -4,7 +4,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::ast::CellPath;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 struct Arguments {
@@ -79,6 +79,8 @@ impl Command for SubCommand {
                 SyntaxShape::CellPath,
                 "optionally convert text into datetime by column paths",
             )
+            .input_output_type(Type::String, Type::Date)
+            .input_output_type(Type::List(Box::new(Type::String)), Type::List(Box::new(Type::Date)))
             .category(Category::Conversions)
     }
 
@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -14,11 +14,18 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("into decimal").rest(
-            "rest",
-            SyntaxShape::CellPath,
-            "optionally convert text into decimal by column paths",
-        )
+        Signature::build("into decimal")
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                "optionally convert text into decimal by column paths",
+            )
+            .input_output_type(Type::String, Type::Float)
+            .input_output_type(Type::Int, Type::Float)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Float)),
+            )
     }
 
     fn usage(&self) -> &str {
@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -20,6 +20,13 @@ impl Command for SubCommand {
                 SyntaxShape::CellPath,
                 "column paths to convert to filesize (for table input)",
             )
+            .input_output_type(Type::Filesize, Type::Filesize)
+            .input_output_type(Type::Number, Type::Filesize)
+            .input_output_type(Type::String, Type::Filesize)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Filesize)),
+            )
             .category(Category::Conversions)
     }
 
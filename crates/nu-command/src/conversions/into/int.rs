@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 struct Arguments {
@@ -26,6 +26,14 @@ impl Command for SubCommand {
                 SyntaxShape::CellPath,
                 "column paths to convert to int (for table input)",
             )
+            .input_output_type(Type::String, Type::Int)
+            .input_output_type(Type::Number, Type::Int)
+            .input_output_type(Type::Bool, Type::Int)
+            .input_output_type(Type::Filesize, Type::Int)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Int)),
+            )
             .category(Category::Conversions)
     }
 
@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Category, Config, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Config, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 // TODO num_format::SystemLocale once platform-specific dependencies are stable (see Cargo.toml)
@@ -29,6 +29,16 @@ impl Command for SubCommand {
                 "decimal digits to which to round",
                 Some('d'),
             )
+            .input_output_type(Type::Number, Type::String)
+            .input_output_type(Type::Bool, Type::String)
+            .input_output_type(Type::Date, Type::String)
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(Type::Filesize, Type::String)
+            .input_output_type(Type::Nothing, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::String)),
+            )
             .category(Category::Conversions)
     }
 
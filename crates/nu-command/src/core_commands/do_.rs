@@ -1,7 +1,7 @@
 use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, Signature, SyntaxShape, Value};
+use nu_protocol::engine::{CaptureBlock, Command, EngineState, Stack};
+use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape, Value};
 
 #[derive(Clone)]
 pub struct Do;
@@ -29,6 +29,7 @@ impl Command for Do {
                 Some('i'),
             )
             .rest("rest", SyntaxShape::Any, "the parameter(s) for the block")
+            .runs_in_place()
             .category(Category::Core)
     }
 
@@ -39,16 +40,15 @@ impl Command for Do {
         call: &Call,
         input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        let block: Value = call.req(engine_state, stack, 0)?;
-        let block_id = block.as_block()?;
+        let capture_block: CaptureBlock = call.req(engine_state, stack, 0)?;
 
         let ignore_errors = call.has_flag("ignore-errors");
 
         let rest: Vec<Value> = call.rest(engine_state, stack, 1)?;
 
-        let block = engine_state.get_block(block_id);
+        let block = engine_state.get_block(capture_block.block_id);
 
-        let mut stack = stack.collect_captures(&block.captures);
+        let mut block_stack = stack.captures_to_stack(&capture_block.captures);
 
         let params: Vec<_> = block
             .signature
@@ -59,7 +59,7 @@ impl Command for Do {
 
         for param in params.iter().zip(&rest) {
             if let Some(var_id) = param.0.var_id {
-                stack.add_var(var_id, param.1.clone())
+                block_stack.add_var(var_id, param.1.clone())
             }
         }
 
@@ -77,7 +77,7 @@ impl Command for Do {
                     call.head
                 };
 
-                stack.add_var(
+                block_stack.add_var(
                     param
                         .var_id
                         .expect("Internal error: rest positional parameter lacks var_id"),
@@ -88,11 +88,17 @@ impl Command for Do {
                 )
             }
         }
-        let result = eval_block(engine_state, &mut stack, block, input);
+        let result = eval_block(engine_state, &mut block_stack, block, input);
+        block_stack.pass_exit_code_to(stack);
+        block_stack.pass_mutable_captures_to(engine_state, stack, &block.captures);
 
         if ignore_errors {
             match result {
                 Ok(x) => Ok(x),
+                // Loop control isn't an error, it belongs to the enclosing loop
+                Err(error @ ShellError::Break(_)) | Err(error @ ShellError::Continue(_)) => {
+                    Err(error)
+                }
                 Err(_) => Ok(PipelineData::new(call.head)),
             }
         } else {
@@ -1,9 +1,11 @@
+use std::sync::atomic::Ordering;
+
 use nu_engine::{eval_block, eval_expression};
-use nu_protocol::ast::Call;
+use nu_protocol::ast::{Block, Call};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoInterruptiblePipelineData, PipelineData, Signature, Span, SyntaxShape,
-    Value,
+    BlockId, Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData,
+    ShellError, Signature, Span, SyntaxShape, Value, VarId,
 };
 
 #[derive(Clone)]
@@ -18,6 +20,12 @@ impl Command for For {
         "Loop over a range"
     }
 
+    fn extra_usage(&self) -> &str {
+        r#"The results are streamed, so the block only runs for the items that are used. A block
+that updates mutable variables runs for every item before the loop gives its results, so that
+the variables are up to date once the loop is done."#
+    }
+
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("for")
             .required(
@@ -41,6 +49,7 @@ impl Command for For {
                 Some('n'),
             )
             .creates_scope()
+            .is_loop()
             .category(Category::Core)
     }
 
@@ -66,77 +75,36 @@ impl Command for For {
             .expect("internal error: expected block");
 
         let numbered = call.has_flag("numbered");
-
-        let ctrlc = engine_state.ctrlc.clone();
-        let engine_state = engine_state.clone();
-        let block = engine_state.get_block(block_id).clone();
-        let mut stack = stack.collect_captures(&block.captures);
+        let block = engine_state.get_block(block_id);
 
         match values {
-            Value::List { vals, .. } => Ok(vals
-                .into_iter()
-                .enumerate()
-                .map(move |(idx, x)| {
-                    stack.add_var(
-                        var_id,
-                        if numbered {
-                            Value::Record {
-                                cols: vec!["index".into(), "item".into()],
-                                vals: vec![
-                                    Value::Int {
-                                        val: idx as i64,
-                                        span: head,
-                                    },
-                                    x,
-                                ],
-                                span: head,
-                            }
-                        } else {
-                            x
-                        },
-                    );
-
-                    //let block = engine_state.get_block(block_id);
-                    match eval_block(&engine_state, &mut stack, &block, PipelineData::new(head)) {
-                        Ok(pipeline_data) => pipeline_data.into_value(head),
-                        Err(error) => Value::Error { error },
-                    }
-                })
-                .into_pipeline_data(ctrlc)),
-            Value::Range { val, .. } => Ok(val
-                .into_range_iter()?
-                .enumerate()
-                .map(move |(idx, x)| {
-                    stack.add_var(
-                        var_id,
-                        if numbered {
-                            Value::Record {
-                                cols: vec!["index".into(), "item".into()],
-                                vals: vec![
-                                    Value::Int {
-                                        val: idx as i64,
-                                        span: head,
-                                    },
-                                    x,
-                                ],
-                                span: head,
-                            }
-                        } else {
-                            x
-                        },
-                    );
-
-                    //let block = engine_state.get_block(block_id);
-                    match eval_block(&engine_state, &mut stack, &block, PipelineData::new(head)) {
-                        Ok(pipeline_data) => pipeline_data.into_value(head),
-                        Err(error) => Value::Error { error },
-                    }
-                })
-                .into_pipeline_data(ctrlc)),
+            Value::List { vals, .. } => run_loop(
+                engine_state,
+                stack,
+                block_id,
+                var_id,
+                numbered,
+                head,
+                vals.into_iter(),
+            ),
+            Value::Range { val, .. } => run_loop(
+                engine_state,
+                stack,
+                block_id,
+                var_id,
+                numbered,
+                head,
+                val.into_range_iter()?,
+            ),
             x => {
                 stack.add_var(var_id, x);
 
-                eval_block(&engine_state, &mut stack, &block, PipelineData::new(head))
+                match eval_block(engine_state, stack, block, PipelineData::new(head)) {
+                    Err(ShellError::Break(_)) | Err(ShellError::Continue(_)) => {
+                        Ok(PipelineData::new(head))
+                    }
+                    result => result,
+                }
             }
         }
     }
@@ -189,6 +157,101 @@ impl Command for For {
     }
 }
 
+// The loop is streamed, unless its body updates mutable variables. Those live in the caller's
+// stack, which a stream can't hold on to, so then the body runs in it right away. Both ways
+// share `run_body`, so `break` and `continue` behave the same, only the timing differs
+fn run_loop(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    block_id: BlockId,
+    var_id: VarId,
+    numbered: bool,
+    head: Span,
+    values: impl Iterator<Item = Value> + Send + 'static,
+) -> Result<PipelineData, ShellError> {
+    let block = engine_state.get_block(block_id);
+
+    if block
+        .captures
+        .iter()
+        .any(|var_id| engine_state.get_var(*var_id).mutable)
+    {
+        let mut output = vec![];
+
+        for (idx, x) in values.enumerate() {
+            if let Some(ctrlc) = &engine_state.ctrlc {
+                if ctrlc.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+
+            stack.add_var(var_id, loop_variable(idx, x, numbered, head));
+
+            match run_body(engine_state, stack, block, head) {
+                None => break,
+                Some(value) => output.extend(value),
+            }
+        }
+
+        return Ok(Value::List {
+            vals: output,
+            span: head,
+        }
+        .into_pipeline_data());
+    }
+
+    let ctrlc = engine_state.ctrlc.clone();
+    let engine_state = engine_state.clone();
+    let block = block.clone();
+    let mut stack = stack.collect_captures(&block.captures);
+
+    Ok(values
+        .enumerate()
+        .map(move |(idx, x)| {
+            stack.add_var(var_id, loop_variable(idx, x, numbered, head));
+
+            run_body(&engine_state, &mut stack, &block, head)
+        })
+        // `break` ends the stream, `continue` skips the item
+        .take_while(Option::is_some)
+        .flatten()
+        .flatten()
+        .into_pipeline_data(ctrlc))
+}
+
+fn loop_variable(idx: usize, x: Value, numbered: bool, head: Span) -> Value {
+    if numbered {
+        Value::Record {
+            cols: vec!["index".into(), "item".into()],
+            vals: vec![
+                Value::Int {
+                    val: idx as i64,
+                    span: head,
+                },
+                x,
+            ],
+            span: head,
+        }
+    } else {
+        x
+    }
+}
+
+// Run the body for one item. Gives `None` when the loop breaks, and no value when it continues
+fn run_body(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    block: &Block,
+    head: Span,
+) -> Option<Option<Value>> {
+    match eval_block(engine_state, stack, block, PipelineData::new(head)) {
+        Err(ShellError::Break(_)) => None,
+        Err(ShellError::Continue(_)) => Some(None),
+        Err(error) => Some(Some(Value::Error { error })),
+        Ok(pipeline_data) => Some(Some(pipeline_data.into_value(head))),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1,4 +1,4 @@
-use nu_engine::{eval_block, eval_expression};
+use nu_engine::{eval_block_in_place, eval_expression};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
@@ -30,6 +30,7 @@ impl Command for If {
                 SyntaxShape::Keyword(b"else".to_vec(), Box::new(SyntaxShape::Expression)),
                 "expression or block to run if check fails",
             )
+            .runs_in_place()
             .category(Category::Core)
     }
 
@@ -51,14 +52,12 @@ impl Command for If {
             Value::Bool { val, .. } => {
                 if *val {
                     let block = engine_state.get_block(then_block);
-                    let mut stack = stack.collect_captures(&block.captures);
-                    eval_block(engine_state, &mut stack, block, input)
+                    eval_block_in_place(engine_state, stack, block, input)
                 } else if let Some(else_case) = else_case {
                     if let Some(else_expr) = else_case.as_keyword() {
                         if let Some(block_id) = else_expr.as_block() {
                             let block = engine_state.get_block(block_id);
-                            let mut stack = stack.collect_captures(&block.captures);
-                            eval_block(engine_state, &mut stack, block, input)
+                            eval_block_in_place(engine_state, stack, block, input)
                         } else {
                             eval_expression(engine_state, stack, else_expr)
                                 .map(|x| x.into_pipeline_data())
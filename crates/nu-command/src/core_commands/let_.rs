@@ -1,7 +1,7 @@
-use nu_engine::eval_expression;
-use nu_protocol::ast::Call;
+use nu_engine::{destructure, eval_expression};
+use nu_protocol::ast::{Call, Expr};
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, Signature, SyntaxShape};
+use nu_protocol::{Category, Example, PipelineData, Signature, Span, SyntaxShape, Value};
 
 #[derive(Clone)]
 pub struct Let;
@@ -33,19 +33,26 @@ impl Command for Let {
         call: &Call,
         _input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        let var_id = call.positional[0]
-            .as_var()
-            .expect("internal error: missing variable");
-
         let keyword_expr = call.positional[1]
             .as_keyword()
             .expect("internal error: missing keyword");
 
         let rhs = eval_expression(engine_state, stack, keyword_expr)?;
 
-        //println!("Adding: {:?} to {}", rhs, var_id);
+        if let Expr::MatchPattern(pattern) = &call.positional[0].expr {
+            let mut bindings = vec![];
+            destructure(pattern, rhs, &mut bindings)?;
+
+            for (var_id, value) in bindings {
+                stack.add_var(var_id, value);
+            }
+        } else {
+            let var_id = call.positional[0]
+                .as_var()
+                .expect("internal error: missing variable");
 
-        stack.add_var(var_id, rhs);
+            stack.add_var(var_id, rhs);
+        }
         Ok(PipelineData::new(call.head))
     }
 
@@ -61,6 +68,19 @@ impl Command for Let {
                 example: "let x = 10 + 100",
                 result: None,
             },
+            Example {
+                description: "Take apart a list into variables",
+                example: "let [first, ...rest] = [1 2 3]; $rest",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(2), Value::test_int(3)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Take apart a record into variables",
+                example: "let {name, age} = {name: 'bob', age: 25}; $name",
+                result: Some(Value::test_string("bob")),
+            },
         ]
     }
 }
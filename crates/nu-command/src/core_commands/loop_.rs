@@ -0,0 +1,79 @@
+use std::sync::atomic::Ordering;
+
+use nu_engine::eval_block;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape};
+
+#[derive(Clone)]
+pub struct Loop;
+
+impl Command for Loop {
+    fn name(&self) -> &str {
+        "loop"
+    }
+
+    fn usage(&self) -> &str {
+        "Run a block in a loop until it breaks."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("loop")
+            .required("block", SyntaxShape::Block(Some(vec![])), "block to loop")
+            .is_loop()
+            .category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        let block_id = call.positional[0]
+            .as_block()
+            .expect("internal error: expected block");
+        let block = engine_state.get_block(block_id);
+
+        loop {
+            if let Some(ctrlc) = &engine_state.ctrlc {
+                if ctrlc.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+
+            // The body runs in our stack so it can update mutable variables
+            match eval_block(engine_state, stack, block, PipelineData::new(call.head)) {
+                Err(ShellError::Break(_)) => break,
+                Err(ShellError::Continue(_)) => continue,
+                Err(err) => return Err(err),
+                Ok(pipeline) => {
+                    pipeline.into_value(call.head);
+                }
+            }
+        }
+
+        Ok(PipelineData::new(call.head))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Loop until a condition is met",
+            example: "mut x = 0; loop { if $x == 10 { break }; $x += 1 }",
+            result: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Loop {})
+    }
+}
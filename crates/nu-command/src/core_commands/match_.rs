@@ -0,0 +1,129 @@
+use nu_engine::{eval_block_in_place, eval_expression, match_pattern, CallExt};
+use nu_protocol::ast::{Call, Expr};
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
+    Value,
+};
+
+#[derive(Clone)]
+pub struct Match;
+
+impl Command for Match {
+    fn name(&self) -> &str {
+        "match"
+    }
+
+    fn usage(&self) -> &str {
+        "Conditionally run a block on a matched value."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("match")
+            .required("value", SyntaxShape::Any, "value to check")
+            .required(
+                "match_block",
+                SyntaxShape::MatchBlock,
+                "patterns to match against, each followed by the value or block to use",
+            )
+            .runs_in_place()
+            .category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        let value: Value = call.req(engine_state, stack, 0)?;
+        let arms = match &call.positional[1].expr {
+            Expr::MatchBlock(arms) => arms,
+            _ => {
+                return Err(ShellError::NushellFailed(
+                    "match block missing from match".into(),
+                ))
+            }
+        };
+
+        for (pattern, result) in arms {
+            let mut bindings = vec![];
+            if !match_pattern(engine_state, stack, pattern, &value, &mut bindings)? {
+                continue;
+            }
+
+            for (var_id, value) in bindings {
+                stack.add_var(var_id, value);
+            }
+
+            if let Some(guard) = &pattern.guard {
+                match eval_expression(engine_state, stack, guard)? {
+                    Value::Bool { val: true, .. } => {}
+                    Value::Bool { val: false, .. } => continue,
+                    x => {
+                        return Err(ShellError::CantConvert(
+                            "bool".into(),
+                            x.get_type().to_string(),
+                            guard.span,
+                        ))
+                    }
+                }
+            }
+
+            return if let Some(block_id) = result.as_block() {
+                let block = engine_state.get_block(block_id);
+                eval_block_in_place(engine_state, stack, block, input)
+            } else {
+                eval_expression(engine_state, stack, result).map(|x| x.into_pipeline_data())
+            };
+        }
+
+        Ok(PipelineData::new(call.head))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Match on a value",
+                example: "match 3 { 1 => 'one', 2 => 'two', _ => 'other' }",
+                result: Some(Value::test_string("other")),
+            },
+            Example {
+                description: "Match on a range of values",
+                example: "match 7 { 1..5 => 'small', 6..10 => 'big' }",
+                result: Some(Value::test_string("big")),
+            },
+            Example {
+                description: "Take apart a list",
+                example: "match [1 2 3] { [$first, ..$rest] => $rest }",
+                result: Some(Value::List {
+                    vals: vec![Value::test_int(2), Value::test_int(3)],
+                    span: Span::test_data(),
+                }),
+            },
+            Example {
+                description: "Take apart a record",
+                example: "match {name: 'bob', age: 25} { {name: $n} => $n }",
+                result: Some(Value::test_string("bob")),
+            },
+            Example {
+                description: "Use a guard to check the bound values",
+                example: "match [2 1] { [$a, $b] if $a > $b => 'descending', _ => 'other' }",
+                result: Some(Value::test_string("descending")),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Match {})
+    }
+}
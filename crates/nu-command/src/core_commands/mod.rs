@@ -1,4 +1,6 @@
 mod alias;
+mod break_;
+mod continue_;
 mod debug;
 mod def;
 mod describe;
@@ -13,13 +15,20 @@ mod hide;
 mod history;
 mod if_;
 mod let_;
+mod loop_;
+mod match_;
 mod metadata;
 mod module;
+mod mut_;
 mod source;
+mod try_;
 mod use_;
 mod version;
+mod while_;
 
 pub use alias::Alias;
+pub use break_::Break;
+pub use continue_::Continue;
 pub use debug::Debug;
 pub use def::Def;
 pub use describe::Describe;
@@ -34,11 +43,16 @@ pub use hide::Hide;
 pub use history::History;
 pub use if_::If;
 pub use let_::Let;
+pub use loop_::Loop;
+pub use match_::Match;
 pub use metadata::Metadata;
 pub use module::Module;
+pub use mut_::Mut;
 pub use source::Source;
+pub use try_::Try;
 pub use use_::Use;
 pub use version::Version;
+pub use while_::While;
 #[cfg(feature = "plugin")]
 mod register;
 
@@ -0,0 +1,90 @@
+use nu_engine::{destructure, eval_expression};
+use nu_protocol::ast::{Call, Expr};
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, Signature, SyntaxShape, Value};
+
+#[derive(Clone)]
+pub struct Mut;
+
+impl Command for Mut {
+    fn name(&self) -> &str {
+        "mut"
+    }
+
+    fn usage(&self) -> &str {
+        "Create a mutable variable and give it a value."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("mut")
+            .required("var_name", SyntaxShape::VarWithOptType, "variable name")
+            .required(
+                "initial_value",
+                SyntaxShape::Keyword(b"=".to_vec(), Box::new(SyntaxShape::Expression)),
+                "equals sign followed by value",
+            )
+            .category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        let keyword_expr = call.positional[1]
+            .as_keyword()
+            .expect("internal error: missing keyword");
+
+        let rhs = eval_expression(engine_state, stack, keyword_expr)?;
+
+        if let Expr::MatchPattern(pattern) = &call.positional[0].expr {
+            let mut bindings = vec![];
+            destructure(pattern, rhs, &mut bindings)?;
+
+            for (var_id, value) in bindings {
+                stack.add_var(var_id, value);
+            }
+        } else {
+            let var_id = call.positional[0]
+                .as_var()
+                .expect("internal error: missing variable");
+
+            stack.add_var(var_id, rhs);
+        }
+        Ok(PipelineData::new(call.head))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Set a mutable variable to a value",
+                example: "mut x = 10",
+                result: None,
+            },
+            Example {
+                description: "Change the value of a mutable variable",
+                example: "mut x = 10; $x = 20",
+                result: None,
+            },
+            Example {
+                description: "Add to the value of a mutable variable",
+                example: "mut x = 10; $x += 5; $x",
+                result: Some(Value::test_int(15)),
+            },
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Mut {})
+    }
+}
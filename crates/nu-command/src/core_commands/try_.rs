@@ -0,0 +1,131 @@
+use nu_engine::{eval_block, eval_block_in_place};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    BlockId, Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct Try;
+
+impl Command for Try {
+    fn name(&self) -> &str {
+        "try"
+    }
+
+    fn usage(&self) -> &str {
+        "Try to run a block, if it fails then run the catch block."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("try")
+            .required(
+                "try_block",
+                SyntaxShape::Block(Some(vec![])),
+                "block to run",
+            )
+            .optional(
+                "catch_block",
+                SyntaxShape::Keyword(
+                    b"catch".to_vec(),
+                    Box::new(SyntaxShape::Block(Some(vec![SyntaxShape::Any]))),
+                ),
+                "block to run if the try block fails, given the error as a record",
+            )
+            .runs_in_place()
+            .category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        let try_block = call.positional[0]
+            .as_block()
+            .expect("internal error: expected block");
+        let catch_block = call
+            .positional
+            .get(1)
+            .and_then(|expr| expr.as_keyword())
+            .and_then(|expr| expr.as_block());
+
+        let block = engine_state.get_block(try_block);
+
+        match eval_block_in_place(engine_state, stack, block, input) {
+            // Loop control isn't an error, it belongs to the enclosing loop
+            Err(error @ ShellError::Break(_)) | Err(error @ ShellError::Continue(_)) => Err(error),
+            Err(error) | Ok(PipelineData::Value(Value::Error { error }, ..)) => {
+                run_catch(engine_state, stack, catch_block, error, call.head)
+            }
+            Ok(pipeline) => Ok(pipeline),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Try to run a division by zero",
+                example: "try { 1 / 0 }",
+                result: None,
+            },
+            Example {
+                description: "Fall back to another value if the block fails",
+                example: "try { 1 / 0 } catch { 'divided by zero' }",
+                result: Some(Value::test_string("divided by zero")),
+            },
+            Example {
+                description: "Use the message of the error",
+                example: "try { 1 / 0 } catch { |err| $err.msg }",
+                result: Some(Value::test_string("Division by zero.")),
+            },
+        ]
+    }
+}
+
+fn run_catch(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    catch_block: Option<BlockId>,
+    error: ShellError,
+    span: Span,
+) -> Result<PipelineData, ShellError> {
+    if let Some(block_id) = catch_block {
+        let block = engine_state.get_block(block_id);
+        let mut catch_stack = stack.collect_captures(&block.captures);
+
+        if let Some(var_id) = block
+            .signature
+            .get_positional(0)
+            .and_then(|param| param.var_id)
+        {
+            catch_stack.add_var(var_id, error.into_value(span));
+        }
+
+        let result = eval_block(
+            engine_state,
+            &mut catch_stack,
+            block,
+            PipelineData::new(span),
+        );
+        catch_stack.pass_mutable_captures_to(engine_state, stack, &block.captures);
+
+        result
+    } else {
+        Ok(PipelineData::new(span))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(Try {})
+    }
+}
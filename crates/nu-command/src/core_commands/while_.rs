@@ -0,0 +1,101 @@
+use std::sync::atomic::Ordering;
+
+use nu_engine::{eval_block, eval_expression};
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+
+#[derive(Clone)]
+pub struct While;
+
+impl Command for While {
+    fn name(&self) -> &str {
+        "while"
+    }
+
+    fn usage(&self) -> &str {
+        "Conditionally run a block in a loop."
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("while")
+            .required("cond", SyntaxShape::Expression, "condition to check")
+            .required(
+                "block",
+                SyntaxShape::Block(Some(vec![])),
+                "block to loop while the condition holds",
+            )
+            .is_loop()
+            .category(Category::Core)
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
+        let cond = &call.positional[0];
+        let block_id = call.positional[1]
+            .as_block()
+            .expect("internal error: expected block");
+        let block = engine_state.get_block(block_id);
+
+        loop {
+            if let Some(ctrlc) = &engine_state.ctrlc {
+                if ctrlc.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+
+            let result = eval_expression(engine_state, stack, cond)?;
+            match &result {
+                Value::Bool { val, .. } => {
+                    if !*val {
+                        break;
+                    }
+
+                    // The body runs in our stack so it can update mutable variables
+                    match eval_block(engine_state, stack, block, PipelineData::new(call.head)) {
+                        Err(ShellError::Break(_)) => break,
+                        Err(ShellError::Continue(_)) => continue,
+                        Err(err) => return Err(err),
+                        Ok(pipeline) => {
+                            pipeline.into_value(call.head);
+                        }
+                    }
+                }
+                x => {
+                    return Err(ShellError::CantConvert(
+                        "bool".into(),
+                        x.get_type().to_string(),
+                        result.span()?,
+                    ))
+                }
+            }
+        }
+
+        Ok(PipelineData::new(call.head))
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "Loop while a condition is true",
+            example: "mut x = 0; while $x < 10 { $x += 1 }",
+            result: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_examples() {
+        use crate::test_examples;
+
+        test_examples(While {})
+    }
+}
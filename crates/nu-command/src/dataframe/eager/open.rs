@@ -1,5 +1,5 @@
 use super::super::values::NuDataFrame;
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
@@ -113,7 +113,7 @@ fn from_parquet(
     let file: Spanned<PathBuf> = call.req(engine_state, stack, 0)?;
     let columns: Option<Vec<String>> = call.get_flag(engine_state, stack, "columns")?;
 
-    let r = File::open(&file.item).map_err(|e| {
+    let r = File::open(current_dir(stack)?.join(&file.item)).map_err(|e| {
         ShellError::SpannedLabeledError("Error opening file".into(), e.to_string(), file.span)
     })?;
     let reader = ParquetReader::new(r);
@@ -139,7 +139,7 @@ fn from_json(
 ) -> Result<polars::prelude::DataFrame, ShellError> {
     let file: Spanned<PathBuf> = call.req(engine_state, stack, 0)?;
 
-    let r = File::open(&file.item).map_err(|e| {
+    let r = File::open(current_dir(stack)?.join(&file.item)).map_err(|e| {
         ShellError::SpannedLabeledError("Error opening file".into(), e.to_string(), file.span)
     })?;
 
@@ -162,7 +162,7 @@ fn from_csv(
     let skip_rows: Option<usize> = call.get_flag(engine_state, stack, "skip_rows")?;
     let columns: Option<Vec<String>> = call.get_flag(engine_state, stack, "columns")?;
 
-    let csv_reader = CsvReader::from_path(&file.item)
+    let csv_reader = CsvReader::from_path(current_dir(stack)?.join(&file.item))
         .map_err(|e| {
             ShellError::SpannedLabeledError(
                 "Error creating CSV reader".into(),
@@ -1,6 +1,6 @@
 use std::{fs::File, path::PathBuf};
 
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
@@ -57,7 +57,7 @@ fn command(
 
     let df = NuDataFrame::try_from_pipeline(input, call.head)?;
 
-    let file = File::create(&file_name.item).map_err(|e| {
+    let file = File::create(current_dir(stack)?.join(&file_name.item)).map_err(|e| {
         ShellError::SpannedLabeledError(
             "Error with file name".into(),
             e.to_string(),
@@ -3,7 +3,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, Example, PipelineData, Signature, Span, Spanned, SyntaxShape, Value,
+    Category, Example, PipelineData, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 use super::utils::{parse_date_from_string, unsupported_input_error};
@@ -23,6 +23,13 @@ impl Command for SubCommand {
                 SyntaxShape::String,
                 "the desired date format",
             )
+            .input_output_type(Type::Date, Type::String)
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(Type::Nothing, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
             .category(Category::Date)
     }
 
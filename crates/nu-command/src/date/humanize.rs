@@ -3,7 +3,7 @@ use chrono::{DateTime, FixedOffset, Local};
 use chrono_humanize::HumanTime;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Value};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
 #[derive(Clone)]
 pub struct SubCommand;
 
@@ -13,7 +13,15 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("date humanize").category(Category::Date)
+        Signature::build("date humanize")
+            .input_output_type(Type::Date, Type::String)
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(Type::Nothing, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .category(Category::Date)
     }
 
     fn usage(&self) -> &str {
@@ -1,7 +1,7 @@
 use chrono_tz::TZ_VARIANTS;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, IntoInterruptiblePipelineData, PipelineData, Signature, Value};
+use nu_protocol::{Category, IntoInterruptiblePipelineData, PipelineData, Signature, Type, Value};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -12,7 +12,9 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("date list-timezone").category(Category::Date)
+        Signature::build("date list-timezone")
+            .input_output_type(Type::Nothing, Type::Table)
+            .category(Category::Date)
     }
 
     fn usage(&self) -> &str {
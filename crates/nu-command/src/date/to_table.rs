@@ -2,7 +2,7 @@ use crate::date::utils::{parse_date_from_string, unsupported_input_error};
 use chrono::{DateTime, Datelike, FixedOffset, Local, Timelike};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, Signature, Span, Value};
+use nu_protocol::{Category, Example, PipelineData, Signature, Span, Type, Value};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -13,7 +13,15 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("date to-table").category(Category::Date)
+        Signature::build("date to-table")
+            .input_output_type(Type::Date, Type::Table)
+            .input_output_type(Type::String, Type::Table)
+            .input_output_type(Type::Nothing, Type::Table)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .category(Category::Date)
     }
 
     fn usage(&self) -> &str {
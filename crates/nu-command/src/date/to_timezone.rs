@@ -5,7 +5,7 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 use chrono::{FixedOffset, TimeZone};
@@ -21,6 +21,13 @@ impl Command for SubCommand {
     fn signature(&self) -> Signature {
         Signature::build("date to-timezone")
             .required("time zone", SyntaxShape::String, "time zone description")
+            .input_output_type(Type::Date, Type::Date)
+            .input_output_type(Type::String, Type::Date)
+            .input_output_type(Type::Nothing, Type::Date)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
             .category(Category::Date)
     }
 
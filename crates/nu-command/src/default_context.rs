@@ -24,6 +24,8 @@ pub fn create_default_context() -> EngineState {
         // Core
         bind_command! {
             Alias,
+            Break,
+            Continue,
             Debug,
             Def,
             Describe,
@@ -38,11 +40,16 @@ pub fn create_default_context() -> EngineState {
             History,
             If,
             Let,
+            Loop,
+            Match,
             Metadata,
             Module,
+            Mut,
             Source,
+            Try,
             Use,
             Version,
+            While,
         };
 
         // Filters
@@ -103,6 +110,7 @@ pub fn create_default_context() -> EngineState {
         // System
         bind_command! {
             Benchmark,
+            Complete,
             External,
             Ps,
             Sys,
@@ -293,8 +301,14 @@ pub fn create_default_context() -> EngineState {
             HashSha256::default(),
         };
 
+        // Bits
+        bind_command! {
+            Bits,
+            BitsNot,
+        };
+
         #[cfg(feature = "plugin")]
-        bind_command!(Register);
+        bind_command!(Register, Plugin, PluginList, PluginReload);
 
         // This is a WIP proof of concept
         // bind_command!(ListGitBranches, Git, GitCheckout, Source);
@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use nu_engine::{eval_block, CallExt};
 use nu_protocol::{
     ast::Call,
-    engine::{Command, EngineState, Stack},
+    engine::{CaptureBlock, Command, EngineState, Stack},
     Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value,
 };
 
@@ -79,11 +79,9 @@ fn with_env(
     // let external_redirection = args.call_info.args.external_redirection;
     let variable: Value = call.req(engine_state, stack, 0)?;
 
-    let block_id = call.positional[1]
-        .as_block()
-        .expect("internal error: expected block");
-    let block = engine_state.get_block(block_id).clone();
-    let mut stack = stack.collect_captures(&block.captures);
+    let capture_block: CaptureBlock = call.req(engine_state, stack, 1)?;
+    let block = engine_state.get_block(capture_block.block_id).clone();
+    let mut stack = stack.captures_to_stack(&capture_block.captures);
 
     let mut env: HashMap<String, Value> = HashMap::new();
 
@@ -1,7 +1,7 @@
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, Signature, SyntaxShape, Value};
+use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape, Value};
 
 #[derive(Clone)]
 pub struct Cd;
@@ -30,21 +30,32 @@ impl Command for Cd {
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let path_val: Option<Value> = call.opt(engine_state, stack, 0)?;
 
+        let cwd = current_dir(stack)?;
+
         let (path, span) = match path_val {
             Some(v) => {
-                let path = nu_path::expand_path(v.as_string()?);
-                (path.to_string_lossy().to_string(), v.span()?)
+                let path = nu_path::expand_path_with(v.as_string()?, &cwd);
+                (path, v.span()?)
             }
             None => {
                 let path = nu_path::expand_tilde("~");
-                (path.to_string_lossy().to_string(), call.head)
+                (path, call.head)
             }
         };
-        let _ = std::env::set_current_dir(&path);
 
-        //FIXME: this only changes the current scope, but instead this environment variable
-        //should probably be a block that loads the information from the state in the overlay
-        stack.add_env_var("PWD".into(), Value::String { val: path, span });
+        if !path.is_dir() {
+            return Err(ShellError::DirectoryNotFound(span));
+        }
+
+        // The directory of the process is left alone. PWD is scoped like any other environment
+        // variable, so a block that changes it doesn't change the directory of its caller
+        stack.add_env_var(
+            "PWD".into(),
+            Value::String {
+                val: path.to_string_lossy().to_string(),
+                span,
+            },
+        );
         Ok(PipelineData::new(call.head))
     }
 }
@@ -1,8 +1,7 @@
-use std::env::current_dir;
 use std::path::PathBuf;
 
 use super::util::get_interactive_confirmation;
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_path::canonicalize_with;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
@@ -49,7 +48,7 @@ impl Command for Cp {
         let interactive = call.has_flag("interactive");
         let force = call.has_flag("force");
 
-        let path = current_dir()?;
+        let path = current_dir(stack)?;
         let source = path.join(source.as_str());
         let destination = path.join(destination.as_str());
 
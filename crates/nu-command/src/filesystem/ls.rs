@@ -1,10 +1,10 @@
 use chrono::{DateTime, Utc};
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, DataSource, IntoInterruptiblePipelineData, PipelineData, PipelineMetadata,
-    ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 use std::io::ErrorKind;
@@ -47,6 +47,7 @@ impl Command for Ls {
             //     "Display the apparent directory size in place of the directory metadata size",
             //     Some('d'),
             // )
+            .input_output_type(Type::Nothing, Type::Table)
             .category(Category::FileSystem)
     }
 
@@ -62,11 +63,12 @@ impl Command for Ls {
         let short_names = call.has_flag("short-names");
 
         let call_span = call.head;
+        let cwd = current_dir(stack)?;
 
         let pattern = if let Some(mut result) =
             call.opt::<Spanned<String>>(engine_state, stack, 0)?
         {
-            let path = std::path::Path::new(&result.item);
+            let path = cwd.join(&result.item);
 
             if path.is_dir() {
                 if permission_denied(&path) {
@@ -104,7 +106,10 @@ impl Command for Ls {
             "*".into()
         };
 
-        let glob = glob::glob(&pattern).map_err(|err| {
+        // A relative pattern lists the entries relative to PWD, like it was matched from there
+        let relative = Path::new(&pattern).is_relative();
+
+        let glob = glob::glob(&cwd.join(&pattern).to_string_lossy()).map_err(|err| {
             nu_protocol::ShellError::SpannedLabeledError(
                 "Error extracting glob pattern".into(),
                 err.to_string(),
@@ -144,8 +149,20 @@ impl Command for Ls {
                         return None;
                     }
 
-                    let entry =
-                        dir_entry_dict(&path, metadata.as_ref(), call_span, long, short_names);
+                    let display_name = if relative {
+                        path.strip_prefix(&cwd).unwrap_or(&path)
+                    } else {
+                        path.as_path()
+                    };
+
+                    let entry = dir_entry_dict(
+                        &path,
+                        display_name,
+                        metadata.as_ref(),
+                        call_span,
+                        long,
+                        short_names,
+                    );
 
                     match entry {
                         Ok(value) => Some(value),
@@ -244,6 +261,7 @@ pub fn get_file_type(md: &std::fs::Metadata) -> &str {
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn dir_entry_dict(
     filename: &std::path::Path,
+    display_name: &std::path::Path,
     metadata: Option<&std::fs::Metadata>,
     span: Span,
     long: bool,
@@ -255,7 +273,7 @@ pub(crate) fn dir_entry_dict(
     let name = if short_name {
         filename.file_name().and_then(|s| s.to_str())
     } else {
-        filename.to_str()
+        display_name.to_str()
     }
     .ok_or_else(|| {
         ShellError::SpannedLabeledError(
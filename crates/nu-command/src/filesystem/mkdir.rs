@@ -1,7 +1,6 @@
 use std::collections::VecDeque;
-use std::env::current_dir;
 
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
@@ -39,7 +38,7 @@ impl Command for Mkdir {
         call: &Call,
         _input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let path = current_dir()?;
+        let path = current_dir(stack)?;
         let mut directories = call
             .rest::<String>(engine_state, stack, 0)?
             .into_iter()
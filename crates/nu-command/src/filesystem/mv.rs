@@ -1,8 +1,7 @@
-use std::env::current_dir;
 use std::path::{Path, PathBuf};
 
 use super::util::get_interactive_confirmation;
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{Category, PipelineData, ShellError, Signature, Spanned, SyntaxShape};
@@ -50,7 +49,7 @@ impl Command for Mv {
         let interactive = call.has_flag("interactive");
         let force = call.has_flag("force");
 
-        let path: PathBuf = current_dir()?;
+        let path: PathBuf = current_dir(stack)?;
         let source = path.join(spanned_source.item.as_str());
         let destination = path.join(destination.as_str());
 
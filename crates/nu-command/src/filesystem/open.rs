@@ -1,4 +1,4 @@
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
@@ -44,7 +44,7 @@ impl Command for Open {
 
         let path = call.req::<Spanned<String>>(engine_state, stack, 0)?;
         let arg_span = path.span;
-        let path = Path::new(&path.item);
+        let path = current_dir(stack)?.join(&path.item);
 
         if permission_denied(&path) {
             #[cfg(unix)]
@@ -69,7 +69,7 @@ impl Command for Open {
                 None,
             ))
         } else {
-            let file = match std::fs::File::open(path) {
+            let file = match std::fs::File::open(&path) {
                 Ok(file) => file,
                 Err(err) => {
                     return Ok(PipelineData::Value(
@@ -1,6 +1,6 @@
 use std::fs::OpenOptions;
 
-use nu_engine::CallExt;
+use nu_engine::{current_dir, CallExt};
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape};
@@ -38,8 +38,14 @@ impl Command for Touch {
         let target: String = call.req(engine_state, stack, 0)?;
         let rest: Vec<String> = call.rest(engine_state, stack, 1)?;
 
+        let path = current_dir(stack)?;
+
         for (index, item) in vec![target].into_iter().chain(rest).enumerate() {
-            match OpenOptions::new().write(true).create(true).open(&item) {
+            match OpenOptions::new()
+                .write(true)
+                .create(true)
+                .open(path.join(&item))
+            {
                 Ok(_) => continue,
                 Err(err) => {
                     return Err(ShellError::CreateNotPossible(
@@ -1,6 +1,6 @@
 use std::path::{Path, PathBuf};
 
-use nu_path::canonicalize_with;
+use nu_path::canonicalize;
 use nu_protocol::ShellError;
 
 use dialoguer::Input;
@@ -48,7 +48,8 @@ impl FileStructure {
     }
 
     fn build(&mut self, src: &Path, lvl: usize) -> Result<(), ShellError> {
-        let source = canonicalize_with(src, std::env::current_dir()?)?;
+        // Callers resolve the path against PWD, so it is already absolute
+        let source = canonicalize(src)?;
 
         if source.is_dir() {
             for entry in std::fs::read_dir(src)? {
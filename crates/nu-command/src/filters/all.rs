@@ -2,7 +2,8 @@ use nu_engine::eval_block;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, SyntaxShape, Value,
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, SyntaxShape, Type,
+    Value,
 };
 
 #[derive(Clone)]
@@ -20,6 +21,7 @@ impl Command for All {
                 SyntaxShape::RowCondition,
                 "the predicate that must match",
             )
+            .input_output_type(Type::Unknown, Type::Bool)
             .category(Category::Filters)
     }
 
@@ -3,7 +3,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
-    SyntaxShape, Value,
+    SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -17,6 +17,7 @@ impl Command for Append {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("append")
             .required("row", SyntaxShape::Any, "the row to append")
+            .input_output_type(Type::Unknown, Type::List(Box::new(Type::Unknown)))
             .category(Category::Filters)
     }
 
@@ -1,7 +1,7 @@
 use nu_engine::CallExt;
 use nu_protocol::{
     ast::Call, engine::Command, engine::EngineState, engine::Stack, Category, Example,
-    PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -19,6 +19,12 @@ impl Command for Compact {
                 SyntaxShape::Any,
                 "the columns to compact from the table",
             )
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .input_output_type(Type::Table, Type::Table)
+            .input_output_type(Type::Unknown, Type::Unknown)
             .category(Category::Filters)
     }
 
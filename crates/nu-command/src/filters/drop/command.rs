@@ -4,7 +4,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
-    SyntaxShape, Value,
+    SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -22,6 +22,12 @@ impl Command for Drop {
                 SyntaxShape::Int,
                 "starting from the back, the number of rows to remove",
             )
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .input_output_type(Type::Table, Type::Table)
+            .input_output_type(Type::Unknown, Type::List(Box::new(Type::Unknown)))
             .category(Category::Filters)
     }
 
@@ -3,7 +3,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, PipelineData, PipelineIterator, ShellError,
-    Signature, Span, SyntaxShape, Value,
+    Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -17,6 +17,12 @@ impl Command for DropNth {
     fn signature(&self) -> Signature {
         Signature::build("drop nth")
             .rest("rest", SyntaxShape::Int, "the number of the row to drop")
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .input_output_type(Type::Table, Type::Table)
+            .input_output_type(Type::Unknown, Type::List(Box::new(Type::Unknown)))
             .category(Category::Filters)
     }
 
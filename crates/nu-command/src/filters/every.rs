@@ -4,7 +4,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
-    SyntaxShape, Value,
+    SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -27,6 +27,12 @@ impl Command for Every {
                 "skip the rows that would be returned, instead of selecting them",
                 Some('s'),
             )
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .input_output_type(Type::Table, Type::Table)
+            .input_output_type(Type::Unknown, Type::List(Box::new(Type::Unknown)))
             .category(Category::Filters)
     }
 
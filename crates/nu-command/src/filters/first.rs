@@ -21,6 +21,8 @@ impl Command for First {
                 SyntaxShape::Int,
                 "starting from the front, the number of rows to return",
             )
+            .input_output_type(Type::Binary, Type::Binary)
+            .input_output_type(Type::Unknown, Type::Unknown)
             .category(Category::Filters)
     }
 
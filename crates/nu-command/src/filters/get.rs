@@ -1,7 +1,7 @@
 use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, IntoPipelineData, PipelineData, Signature, SyntaxShape};
+use nu_protocol::{Category, IntoPipelineData, PipelineData, Signature, SyntaxShape, Type};
 
 #[derive(Clone)]
 pub struct Get;
@@ -22,6 +22,8 @@ impl Command for Get {
                 SyntaxShape::CellPath,
                 "the cell path to the data",
             )
+            .input_output_type(Type::List(Box::new(Type::Unknown)), Type::Unknown)
+            .input_output_type(Type::Record(vec![]), Type::Unknown)
             .category(Category::Filters)
     }
 
@@ -1,9 +1,9 @@
-use nu_engine::eval_block;
+use nu_engine::{eval_block, CallExt};
 use nu_protocol::ast::Call;
-use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::engine::{CaptureBlock, Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
-    SyntaxShape, Value,
+    SyntaxShape, Type, Value,
 };
 use rayon::prelude::*;
 
@@ -27,6 +27,11 @@ impl Command for ParEach {
                 "the block to run",
             )
             .switch("numbered", "iterate with an index", Some('n'))
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .input_output_type(Type::Unknown, Type::Unknown)
             .category(Category::Filters)
     }
 
@@ -45,15 +50,14 @@ impl Command for ParEach {
         call: &Call,
         input: PipelineData,
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
-        let block_id = call.positional[0]
-            .as_block()
-            .expect("internal error: expected block");
+        let capture_block: CaptureBlock = call.req(engine_state, stack, 0)?;
+        let block_id = capture_block.block_id;
 
         let numbered = call.has_flag("numbered");
         let ctrlc = engine_state.ctrlc.clone();
         let engine_state = engine_state.clone();
         let block = engine_state.get_block(block_id);
-        let mut stack = stack.collect_captures(&block.captures);
+        let mut stack = stack.captures_to_stack(&capture_block.captures);
         let span = call.head;
 
         match input {
@@ -223,14 +227,14 @@ impl Command for ParEach {
                 .into_iter()
                 .flatten()
                 .into_pipeline_data(ctrlc)),
-            PipelineData::ByteStream(stream, ..) => Ok(stream
+            PipelineData::ByteStream(..) | PipelineData::ExternalStream(..) => Ok(input
+                .into_iter()
                 .enumerate()
                 .par_bridge()
                 .map(move |(idx, x)| {
-                    let x = match x {
-                        Ok(x) => Value::Binary { val: x, span },
-                        Err(err) => return Value::Error { error: err }.into_pipeline_data(),
-                    };
+                    if let Value::Error { .. } = x {
+                        return x.into_pipeline_data();
+                    }
 
                     let block = engine_state.get_block(block_id);
 
@@ -2,7 +2,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Span,
-    Value,
+    Type, Value,
 };
 
 #[derive(Clone)]
@@ -14,7 +14,14 @@ impl Command for Reverse {
     }
 
     fn signature(&self) -> nu_protocol::Signature {
-        Signature::build("reverse").category(Category::Filters)
+        Signature::build("reverse")
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .input_output_type(Type::Table, Type::Table)
+            .input_output_type(Type::Unknown, Type::List(Box::new(Type::Unknown)))
+            .category(Category::Filters)
     }
 
     fn usage(&self) -> &str {
@@ -3,7 +3,7 @@ use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, ShellError,
-    Signature, Span, SyntaxShape, Value,
+    Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -21,6 +21,8 @@ impl Command for Select {
                 SyntaxShape::CellPath,
                 "the columns to select from the table",
             )
+            .input_output_type(Type::Table, Type::Table)
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
             .category(Category::Filters)
     }
 
@@ -1,6 +1,8 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature};
+use nu_protocol::{
+    Category, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Type,
+};
 use rand::prelude::SliceRandom;
 use rand::thread_rng;
 
@@ -13,7 +15,14 @@ impl Command for Shuffle {
     }
 
     fn signature(&self) -> nu_protocol::Signature {
-        Signature::build("shuffle").category(Category::Filters)
+        Signature::build("shuffle")
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Unknown)),
+            )
+            .input_output_type(Type::Table, Type::Table)
+            .input_output_type(Type::Unknown, Type::List(Box::new(Type::Unknown)))
+            .category(Category::Filters)
     }
 
     fn usage(&self) -> &str {
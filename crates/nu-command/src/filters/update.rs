@@ -3,7 +3,7 @@ use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
-    Value,
+    Type, Value,
 };
 
 #[derive(Clone)]
@@ -26,6 +26,8 @@ impl Command for Update {
                 SyntaxShape::Any,
                 "the new value to give the cell(s)",
             )
+            .input_output_type(Type::Table, Type::Table)
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
             .category(Category::Filters)
     }
 
@@ -70,10 +72,10 @@ fn update(
     let ctrlc = engine_state.ctrlc.clone();
 
     // Replace is a block, so set it up and run it instead of using it as the replacement
-    if let Ok(block_id) = replacement.as_block() {
-        let block = engine_state.get_block(block_id).clone();
+    if let Value::Block { val, captures, .. } = replacement {
+        let block = engine_state.get_block(val).clone();
 
-        let mut stack = stack.collect_captures(&block.captures);
+        let mut stack = stack.captures_to_stack(&captures);
 
         input.map(
             move |mut input| {
@@ -3,7 +3,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
-    SyntaxShape, Value,
+    SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -21,6 +21,8 @@ impl Command for Wrap {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("wrap")
             .required("name", SyntaxShape::String, "the name of the column")
+            .input_output_type(Type::List(Box::new(Type::Unknown)), Type::Table)
+            .input_output_type(Type::Unknown, Type::Record(vec![]))
             .category(Category::Filters)
     }
 
@@ -60,6 +62,11 @@ impl Command for Wrap {
                 span,
             }
             .into_pipeline_data()),
+            PipelineData::ExternalStream(stream, stream_span, ..) => Ok(Value::Binary {
+                val: stream.into_stdout(stream_span).into_vec()?,
+                span,
+            }
+            .into_pipeline_data()),
             PipelineData::Value(input, ..) => Ok(Value::Record {
                 cols: vec![name],
                 vals: vec![input],
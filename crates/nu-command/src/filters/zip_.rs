@@ -3,7 +3,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Signature,
-    SyntaxShape, Value,
+    SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -21,6 +21,10 @@ impl Command for Zip {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("zip")
             .required("other", SyntaxShape::Any, "the other input")
+            .input_output_type(
+                Type::Unknown,
+                Type::List(Box::new(Type::List(Box::new(Type::Unknown)))),
+            )
             .category(Category::Filters)
     }
 
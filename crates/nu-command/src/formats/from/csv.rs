@@ -3,7 +3,9 @@ use super::delimited::from_delimited_data;
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Type, Value,
+};
 
 #[derive(Clone)]
 pub struct FromCsv;
@@ -26,6 +28,7 @@ impl Command for FromCsv {
                 "don't treat the first row as column names",
                 Some('n'),
             )
+            .input_output_type(Type::String, Type::Table)
             .category(Category::Formats)
     }
 
@@ -7,7 +7,7 @@ use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::Category;
 use nu_protocol::Config;
 use nu_protocol::{
-    Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -28,6 +28,7 @@ impl Command for FromEml {
                 "How many bytes of the body to preview",
                 Some('b'),
             )
+            .input_output_type(Type::String, Type::Record(vec![]))
             .category(Category::Formats)
     }
 
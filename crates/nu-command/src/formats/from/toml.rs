@@ -1,7 +1,7 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Value,
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Type, Value,
 };
 
 #[derive(Clone)]
@@ -13,7 +13,9 @@ impl Command for FromToml {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("from toml").category(Category::Formats)
+        Signature::build("from toml")
+            .input_output_type(Type::String, Type::Unknown)
+            .category(Category::Formats)
     }
 
     fn usage(&self) -> &str {
@@ -1,6 +1,8 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Config, Example, PipelineData, ShellError, Signature, Span, Value};
+use nu_protocol::{
+    Category, Config, Example, PipelineData, ShellError, Signature, Span, Type, Value,
+};
 
 #[derive(Clone)]
 pub struct FromUrl;
@@ -11,7 +13,9 @@ impl Command for FromUrl {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("from url").category(Category::Formats)
+        Signature::build("from url")
+            .input_output_type(Type::String, Type::Record(vec![]))
+            .category(Category::Formats)
     }
 
     fn usage(&self) -> &str {
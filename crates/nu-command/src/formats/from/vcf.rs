@@ -5,7 +5,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Config, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span,
-    Spanned, Value,
+    Spanned, Type, Value,
 };
 
 #[derive(Clone)]
@@ -17,7 +17,9 @@ impl Command for FromVcf {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("from vcf").category(Category::Formats)
+        Signature::build("from vcf")
+            .input_output_type(Type::String, Type::Table)
+            .category(Category::Formats)
     }
 
     fn usage(&self) -> &str {
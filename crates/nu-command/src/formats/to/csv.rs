@@ -4,7 +4,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Config, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape,
-    Value,
+    Type, Value,
 };
 
 #[derive(Clone)]
@@ -28,6 +28,7 @@ impl Command for ToCsv {
                 "do not output the columns names as the first row",
                 Some('n'),
             )
+            .input_output_type(Type::Unknown, Type::String)
             .category(Category::Formats)
     }
 
@@ -4,7 +4,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Config, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned,
-    SyntaxShape, Value,
+    SyntaxShape, Type, Value,
 };
 use regex::Regex;
 use rust_embed::RustEmbed;
@@ -109,6 +109,7 @@ impl Command for ToHtml {
                 Some('t'),
             )
             .switch("list", "list the names of all available themes", Some('l'))
+            .input_output_type(Type::Unknown, Type::String)
             .category(Category::Formats)
     }
 
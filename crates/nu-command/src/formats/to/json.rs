@@ -1,7 +1,7 @@
 use nu_protocol::ast::{Call, PathMember};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Value,
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Type, Value,
 };
 
 #[derive(Clone)]
@@ -15,6 +15,7 @@ impl Command for ToJson {
     fn signature(&self) -> Signature {
         Signature::build("to json")
             .switch("raw", "remove all of the whitespace", Some('r'))
+            .input_output_type(Type::Unknown, Type::String)
             .category(Category::Formats)
     }
 
@@ -1,7 +1,7 @@
 use nu_engine::CallExt;
 use nu_protocol::ast::{Call, CellPath};
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+use nu_protocol::{Example, PipelineData, ShellError, Signature, SyntaxShape, Type, Value};
 use std::marker::PhantomData;
 
 pub trait HashDigest: digest::Digest + Clone {
@@ -36,11 +36,18 @@ where
     }
 
     fn signature(&self) -> Signature {
-        Signature::build(self.name()).rest(
-            "rest",
-            SyntaxShape::CellPath,
-            format!("optionally {} hash data by cell path", D::name()),
-        )
+        Signature::build(self.name())
+            .rest(
+                "rest",
+                SyntaxShape::CellPath,
+                format!("optionally {} hash data by cell path", D::name()),
+            )
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(Type::Binary, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::String)),
+            )
     }
 
     fn usage(&self) -> &str {
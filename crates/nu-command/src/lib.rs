@@ -1,3 +1,4 @@
+mod bits;
 mod calendar;
 mod conversions;
 mod core_commands;
@@ -20,6 +21,7 @@ mod strings;
 mod system;
 mod viewers;
 
+pub use bits::*;
 pub use calendar::*;
 pub use conversions::*;
 pub use core_commands::*;
@@ -48,3 +50,9 @@ mod dataframe;
 
 #[cfg(feature = "dataframe")]
 pub use dataframe::*;
+
+#[cfg(feature = "plugin")]
+mod plugin;
+
+#[cfg(feature = "plugin")]
+pub use plugin::*;
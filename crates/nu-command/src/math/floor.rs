@@ -1,6 +1,6 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Value};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -11,7 +11,15 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("math floor").category(Category::Math)
+        Signature::build("math floor")
+            .input_output_type(Type::Int, Type::Int)
+            .input_output_type(Type::Float, Type::Float)
+            .input_output_type(Type::Number, Type::Number)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Number)),
+            )
+            .category(Category::Math)
     }
 
     fn usage(&self) -> &str {
@@ -2,7 +2,7 @@ use crate::math::reducers::{reducer_for, Reduce};
 use crate::math::utils::run_with_function;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Value};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -13,7 +13,12 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("math min").category(Category::Math)
+        Signature::build("math min")
+            .input_output_type(Type::List(Box::new(Type::Unknown)), Type::Unknown)
+            .input_output_type(Type::Table, Type::Record(vec![]))
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
+            .input_output_type(Type::Unknown, Type::Unknown)
+            .category(Category::Math)
     }
 
     fn usage(&self) -> &str {
@@ -1,7 +1,7 @@
 use crate::math::utils::run_with_function;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Value};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Span, Type, Value};
 use std::cmp::Ordering;
 
 #[derive(Clone)]
@@ -36,7 +36,12 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("math mode").category(Category::Math)
+        Signature::build("math mode")
+            .input_output_type(Type::List(Box::new(Type::Unknown)), Type::Unknown)
+            .input_output_type(Type::Table, Type::Record(vec![]))
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
+            .input_output_type(Type::Unknown, Type::Unknown)
+            .category(Category::Math)
     }
 
     fn usage(&self) -> &str {
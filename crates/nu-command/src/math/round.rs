@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -21,6 +21,12 @@ impl Command for SubCommand {
                 "digits of precision",
                 Some('p'),
             )
+            .input_output_type(Type::Int, Type::Int)
+            .input_output_type(Type::Number, Type::Number)
+            .input_output_type(
+                Type::List(Box::new(Type::Unknown)),
+                Type::List(Box::new(Type::Number)),
+            )
             .category(Category::Math)
     }
 
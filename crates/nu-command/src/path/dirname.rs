@@ -1,7 +1,7 @@
 use std::path::Path;
 
 use nu_engine::CallExt;
-use nu_protocol::{engine::Command, Example, Signature, Span, Spanned, SyntaxShape, Value};
+use nu_protocol::{engine::Command, Example, Signature, Span, Spanned, SyntaxShape, Type, Value};
 
 use super::PathSubcommandArguments;
 
@@ -45,6 +45,13 @@ impl Command for SubCommand {
                 "Number of directories to walk up",
                 Some('n'),
             )
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::String)),
+            )
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
+            .input_output_type(Type::Table, Type::Table)
     }
 
     fn usage(&self) -> &str {
@@ -1,11 +1,12 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use nu_engine::CallExt;
-use nu_protocol::{engine::Command, Example, Signature, Span, SyntaxShape, Value};
+use nu_engine::{current_dir, CallExt};
+use nu_protocol::{engine::Command, Example, Signature, Span, SyntaxShape, Type, Value};
 
 use super::PathSubcommandArguments;
 
 struct Arguments {
+    cwd: PathBuf,
     columns: Option<Vec<String>>,
 }
 
@@ -24,12 +25,20 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("path exists").named(
-            "columns",
-            SyntaxShape::Table,
-            "Optionally operate by column path",
-            Some('c'),
-        )
+        Signature::build("path exists")
+            .named(
+                "columns",
+                SyntaxShape::Table,
+                "Optionally operate by column path",
+                Some('c'),
+            )
+            .input_output_type(Type::String, Type::Bool)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::Bool)),
+            )
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
+            .input_output_type(Type::Table, Type::Table)
     }
 
     fn usage(&self) -> &str {
@@ -45,6 +54,7 @@ impl Command for SubCommand {
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let head = call.head;
         let args = Arguments {
+            cwd: current_dir(stack)?,
             columns: call.get_flag(engine_state, stack, "columns")?,
         };
 
@@ -93,9 +103,9 @@ impl Command for SubCommand {
     }
 }
 
-fn exists(path: &Path, span: Span, _args: &Arguments) -> Value {
+fn exists(path: &Path, span: Span, args: &Arguments) -> Value {
     Value::Bool {
-        val: path.exists(),
+        val: args.cwd.join(path).exists(),
         span,
     }
 }
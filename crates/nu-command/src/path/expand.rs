@@ -1,12 +1,15 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use nu_engine::CallExt;
-use nu_path::{canonicalize, expand_path};
-use nu_protocol::{engine::Command, Example, ShellError, Signature, Span, SyntaxShape, Value};
+use nu_engine::{current_dir, CallExt};
+use nu_path::{canonicalize_with, expand_path};
+use nu_protocol::{
+    engine::Command, Example, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
 
 use super::PathSubcommandArguments;
 
 struct Arguments {
+    cwd: PathBuf,
     strict: bool,
     columns: Option<Vec<String>>,
 }
@@ -38,6 +41,13 @@ impl Command for SubCommand {
                 "Optionally operate by column path",
                 Some('c'),
             )
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::String)),
+            )
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
+            .input_output_type(Type::Table, Type::Table)
     }
 
     fn usage(&self) -> &str {
@@ -53,6 +63,7 @@ impl Command for SubCommand {
     ) -> Result<nu_protocol::PipelineData, nu_protocol::ShellError> {
         let head = call.head;
         let args = Arguments {
+            cwd: current_dir(stack)?,
             strict: call.has_flag("strict"),
             columns: call.get_flag(engine_state, stack, "columns")?,
         };
@@ -107,7 +118,7 @@ impl Command for SubCommand {
 }
 
 fn expand(path: &Path, span: Span, args: &Arguments) -> Value {
-    if let Ok(p) = canonicalize(path) {
+    if let Ok(p) = canonicalize_with(path, &args.cwd) {
         Value::string(p.to_string_lossy(), span)
     } else if args.strict {
         Value::Error {
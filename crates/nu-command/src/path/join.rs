@@ -6,7 +6,7 @@ use std::{
 use nu_engine::CallExt;
 use nu_protocol::{
     engine::Command, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape,
-    Value, ValueStream,
+    Type, Value, ValueStream,
 };
 
 use super::PathSubcommandArguments;
@@ -38,11 +38,11 @@ impl Command for SubCommand {
                 "Optionally operate by column path",
                 Some('c'),
             )
-            .optional(
-                "append",
-                SyntaxShape::Filepath,
-                "Path to append to the input",
-            )
+            .optional("append", SyntaxShape::String, "Path to append to the input")
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(Type::List(Box::new(Type::String)), Type::Unknown)
+            .input_output_type(Type::Record(vec![]), Type::Unknown)
+            .input_output_type(Type::Table, Type::Unknown)
     }
 
     fn usage(&self) -> &str {
@@ -3,7 +3,7 @@ use std::path::Path;
 use indexmap::IndexMap;
 use nu_engine::CallExt;
 use nu_protocol::{
-    engine::Command, Example, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    engine::Command, Example, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 use super::PathSubcommandArguments;
@@ -41,6 +41,10 @@ impl Command for SubCommand {
                 "Manually supply the extension (without the dot)",
                 Some('e'),
             )
+            .input_output_type(Type::String, Type::Record(vec![]))
+            .input_output_type(Type::List(Box::new(Type::String)), Type::Table)
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
+            .input_output_type(Type::Table, Type::Table)
     }
 
     fn usage(&self) -> &str {
@@ -2,7 +2,7 @@ use std::path::Path;
 
 use nu_engine::CallExt;
 use nu_protocol::{
-    engine::Command, Example, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
+    engine::Command, Example, ShellError, Signature, Span, Spanned, SyntaxShape, Type, Value,
 };
 
 use super::PathSubcommandArguments;
@@ -39,6 +39,13 @@ impl Command for SubCommand {
                 "Optionally operate by column path",
                 Some('c'),
             )
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::String)),
+            )
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
+            .input_output_type(Type::Table, Type::Table)
     }
 
     fn usage(&self) -> &str {
@@ -1,11 +1,14 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use nu_engine::CallExt;
-use nu_protocol::{engine::Command, Example, ShellError, Signature, Span, SyntaxShape, Value};
+use nu_engine::{current_dir, CallExt};
+use nu_protocol::{
+    engine::Command, Example, ShellError, Signature, Span, SyntaxShape, Type, Value,
+};
 
 use super::PathSubcommandArguments;
 
 struct Arguments {
+    cwd: PathBuf,
     columns: Option<Vec<String>>,
 }
 
@@ -24,12 +27,20 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("path type").named(
-            "columns",
-            SyntaxShape::Table,
-            "Optionally operate by column path",
-            Some('c'),
-        )
+        Signature::build("path type")
+            .named(
+                "columns",
+                SyntaxShape::Table,
+                "Optionally operate by column path",
+                Some('c'),
+            )
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::String)),
+            )
+            .input_output_type(Type::Record(vec![]), Type::Record(vec![]))
+            .input_output_type(Type::Table, Type::Table)
     }
 
     fn usage(&self) -> &str {
@@ -45,6 +56,7 @@ impl Command for SubCommand {
     ) -> Result<nu_protocol::PipelineData, ShellError> {
         let head = call.head;
         let args = Arguments {
+            cwd: current_dir(stack)?,
             columns: call.get_flag(engine_state, stack, "columns")?,
         };
 
@@ -70,8 +82,8 @@ impl Command for SubCommand {
     }
 }
 
-fn r#type(path: &Path, span: Span, _: &Arguments) -> Value {
-    let meta = std::fs::symlink_metadata(path);
+fn r#type(path: &Path, span: Span, args: &Arguments) -> Value {
+    let meta = std::fs::symlink_metadata(args.cwd.join(path));
 
     Value::string(
         match &meta {
@@ -0,0 +1,102 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Value,
+};
+use std::path::Path;
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "plugin list"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("plugin list").category(Category::Core)
+    }
+
+    fn usage(&self) -> &str {
+        "List the registered plugins and their commands."
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![Example {
+            description: "List the commands of the registered plugins",
+            example: "plugin list | select name commands",
+            result: None,
+        }]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        _stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+
+        let plugins = engine_state
+            .plugins()
+            .into_iter()
+            .map(|(path, encoding, shell)| {
+                let commands = engine_state
+                    .plugin_decls()
+                    .filter(|decl| matches!(decl.is_plugin(), Some((decl_path, ..)) if decl_path == path))
+                    .map(|decl| Value::String {
+                        val: decl.name().into(),
+                        span,
+                    })
+                    .collect();
+
+                let cols = vec![
+                    "name".into(),
+                    "filename".into(),
+                    "encoding".into(),
+                    "shell".into(),
+                    "commands".into(),
+                ];
+                let vals = vec![
+                    Value::String {
+                        val: plugin_name(path),
+                        span,
+                    },
+                    Value::String {
+                        val: path.to_string_lossy().to_string(),
+                        span,
+                    },
+                    Value::String {
+                        val: encoding.into(),
+                        span,
+                    },
+                    match shell {
+                        Some(shell) => Value::String {
+                            val: shell.to_string_lossy().to_string(),
+                            span,
+                        },
+                        None => Value::Nothing { span },
+                    },
+                    Value::List {
+                        vals: commands,
+                        span,
+                    },
+                ];
+
+                Value::Record { cols, vals, span }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(plugins
+            .into_iter()
+            .into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+}
+
+// Plugins are named after their file, e.g. nu_plugin_inc
+pub(super) fn plugin_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,7 @@
+mod command;
+mod list;
+mod reload;
+
+pub use command::Plugin;
+pub use list::SubCommand as PluginList;
+pub use reload::SubCommand as PluginReload;
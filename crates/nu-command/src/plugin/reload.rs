@@ -0,0 +1,115 @@
+use super::list::plugin_name;
+use nu_engine::CallExt;
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoInterruptiblePipelineData, PipelineData, ShellError, Signature, Spanned,
+    SyntaxShape, Value,
+};
+
+#[derive(Clone)]
+pub struct SubCommand;
+
+impl Command for SubCommand {
+    fn name(&self) -> &str {
+        "plugin reload"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build("plugin reload")
+            .optional(
+                "name",
+                SyntaxShape::String,
+                "name of the plugin to reload, all plugins are reloaded by default",
+            )
+            .category(Category::Core)
+    }
+
+    fn usage(&self) -> &str {
+        "Restart registered plugins and refresh their cached signatures."
+    }
+
+    fn extra_usage(&self) -> &str {
+        "The plugins are restarted and their commands are replaced by the reloaded ones when this command is parsed, so the new signatures can be used right after it. The command lists the reloaded plugins."
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Reload every registered plugin",
+                example: "plugin reload",
+                result: None,
+            },
+            Example {
+                description: "Reload the inc plugin after rebuilding it",
+                example: "plugin reload nu_plugin_inc",
+                result: None,
+            },
+        ]
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        _input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let name: Option<Spanned<String>> = call.opt(engine_state, stack, 0)?;
+
+        let plugins: Vec<_> = engine_state
+            .plugins()
+            .into_iter()
+            .filter(|(path, ..)| match &name {
+                Some(name) => plugin_name(path) == name.item,
+                None => true,
+            })
+            .collect();
+
+        if let (Some(name), true) = (&name, plugins.is_empty()) {
+            return Err(ShellError::SpannedLabeledError(
+                "Plugin not found".into(),
+                format!("no registered plugin is named {}", name.item),
+                name.span,
+            ));
+        }
+
+        let plugins = plugins
+            .into_iter()
+            .map(|(path, ..)| {
+                let commands = engine_state
+                    .plugin_decls()
+                    .filter(|decl| matches!(decl.is_plugin(), Some((decl_path, ..)) if decl_path == path))
+                    .map(|decl| Value::String {
+                        val: decl.name().into(),
+                        span,
+                    })
+                    .collect();
+
+                Value::Record {
+                    cols: vec!["name".into(), "filename".into(), "commands".into()],
+                    vals: vec![
+                        Value::String {
+                            val: plugin_name(path),
+                            span,
+                        },
+                        Value::String {
+                            val: path.to_string_lossy().to_string(),
+                            span,
+                        },
+                        Value::List {
+                            vals: commands,
+                            span,
+                        },
+                    ],
+                    span,
+                }
+            })
+            .collect::<Vec<Value>>();
+
+        Ok(plugins
+            .into_iter()
+            .into_pipeline_data(engine_state.ctrlc.clone()))
+    }
+}
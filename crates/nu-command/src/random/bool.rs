@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Value,
+    Category, Example, PipelineData, ShellError, Signature, Spanned, SyntaxShape, Type, Value,
 };
 use rand::prelude::{thread_rng, Rng};
 
@@ -22,6 +22,7 @@ impl Command for SubCommand {
                 "Adjusts the probability of a \"true\" outcome",
                 Some('b'),
             )
+            .input_output_type(Type::Nothing, Type::Bool)
             .category(Category::Random)
     }
 
@@ -1,7 +1,9 @@
 use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value};
+use nu_protocol::{
+    Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Type, Value,
+};
 use rand::{
     distributions::{Alphanumeric, Distribution},
     thread_rng,
@@ -20,6 +22,7 @@ impl Command for SubCommand {
     fn signature(&self) -> Signature {
         Signature::build("random chars")
             .named("length", SyntaxShape::Int, "Number of chars", Some('l'))
+            .input_output_type(Type::Nothing, Type::String)
             .category(Category::Random)
     }
 
@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, Range, ShellError, Signature, Span, SyntaxShape, Value,
+    Category, Example, PipelineData, Range, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 use rand::prelude::{thread_rng, Rng};
 use std::cmp::Ordering;
@@ -18,6 +18,7 @@ impl Command for SubCommand {
     fn signature(&self) -> Signature {
         Signature::build("random decimal")
             .optional("range", SyntaxShape::Range, "Range of values")
+            .input_output_type(Type::Nothing, Type::Float)
             .category(Category::Random)
     }
 
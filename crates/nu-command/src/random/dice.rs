@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Value, ValueStream,
+    Category, Example, PipelineData, ShellError, Signature, SyntaxShape, Type, Value, ValueStream,
 };
 use rand::prelude::{thread_rng, Rng};
 
@@ -28,6 +28,7 @@ impl Command for SubCommand {
                 "The amount of sides a die has",
                 Some('s'),
             )
+            .input_output_type(Type::Nothing, Type::List(Box::new(Type::Int)))
             .category(Category::Random)
     }
 
@@ -1,6 +1,6 @@
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Value};
+use nu_protocol::{Category, Example, PipelineData, ShellError, Signature, Type, Value};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -12,7 +12,9 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("random uuid").category(Category::Random)
+        Signature::build("random uuid")
+            .input_output_type(Type::Nothing, Type::String)
+            .category(Category::Random)
     }
 
     fn usage(&self) -> &str {
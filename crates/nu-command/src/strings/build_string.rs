@@ -3,7 +3,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, SyntaxShape,
-    Value,
+    Type, Value,
 };
 
 #[derive(Clone)]
@@ -21,6 +21,7 @@ impl Command for BuildString {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("build-string")
             .rest("rest", SyntaxShape::String, "list of string")
+            .input_output_type(Type::Unknown, Type::String)
             .category(Category::Strings)
     }
 
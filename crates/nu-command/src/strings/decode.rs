@@ -4,7 +4,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
     Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Spanned, SyntaxShape,
-    Value,
+    Type, Value,
 };
 
 #[derive(Clone)]
@@ -22,6 +22,7 @@ impl Command for Decode {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("decode")
             .required("encoding", SyntaxShape::String, "the text encoding to use")
+            .input_output_type(Type::Binary, Type::String)
             .category(Category::Strings)
     }
 
@@ -88,6 +89,12 @@ impl Command for Decode {
                 }
                 .into_pipeline_data())
             }
+            PipelineData::ExternalStream(stream, span, metadata) => self.run(
+                engine_state,
+                stack,
+                call,
+                PipelineData::ByteStream(stream.into_stdout(span), span, metadata),
+            ),
             _ => Err(ShellError::UnsupportedInput(
                 "non-binary input".into(),
                 head,
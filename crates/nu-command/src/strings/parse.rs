@@ -2,8 +2,8 @@ use nu_engine::CallExt;
 use nu_protocol::ast::Call;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
-    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Value,
-    ValueStream,
+    Category, Example, PipelineData, ShellError, Signature, Span, Spanned, SyntaxShape, Type,
+    Value, ValueStream,
 };
 use regex::Regex;
 
@@ -27,6 +27,8 @@ impl Command for Parse {
                 "the pattern to match. Eg) \"{foo}: {bar}\"",
             )
             .switch("regex", "use full regex syntax for patterns", Some('r'))
+            .input_output_type(Type::String, Type::Table)
+            .input_output_type(Type::List(Box::new(Type::String)), Type::Table)
             .category(Category::Strings)
     }
 
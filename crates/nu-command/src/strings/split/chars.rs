@@ -1,3 +1,4 @@
+use nu_protocol::Type;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
@@ -13,7 +14,13 @@ impl Command for SubCommand {
     }
 
     fn signature(&self) -> Signature {
-        Signature::build("split chars").category(Category::Strings)
+        Signature::build("split chars")
+            .input_output_type(Type::String, Type::List(Box::new(Type::String)))
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::String)),
+            )
+            .category(Category::Strings)
     }
 
     fn usage(&self) -> &str {
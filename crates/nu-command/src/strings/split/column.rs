@@ -1,4 +1,5 @@
 use nu_engine::CallExt;
+use nu_protocol::Type;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
@@ -26,6 +27,8 @@ impl Command for SubCommand {
                 SyntaxShape::String,
                 "column names to give the new columns",
             )
+            .input_output_type(Type::String, Type::Table)
+            .input_output_type(Type::List(Box::new(Type::String)), Type::Table)
             .category(Category::Strings)
     }
 
@@ -1,4 +1,5 @@
 use nu_engine::CallExt;
+use nu_protocol::Type;
 use nu_protocol::{
     ast::Call,
     engine::{Command, EngineState, Stack},
@@ -20,6 +21,11 @@ impl Command for SubCommand {
                 SyntaxShape::String,
                 "the character that denotes what separates rows",
             )
+            .input_output_type(Type::String, Type::List(Box::new(Type::String)))
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::String)),
+            )
             .category(Category::Strings)
     }
 
@@ -3,7 +3,7 @@ use nu_protocol::ast::Call;
 use nu_protocol::ast::CellPath;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::Category;
-use nu_protocol::{Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value};
+use nu_protocol::{Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value};
 
 #[derive(Clone)]
 pub struct SubCommand;
@@ -20,6 +20,11 @@ impl Command for SubCommand {
                 SyntaxShape::CellPath,
                 "optionally find length of text by column paths",
             )
+            .input_output_type(Type::String, Type::Int)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::Int)),
+            )
             .category(Category::Strings)
     }
 
@@ -4,7 +4,7 @@ use nu_protocol::ast::CellPath;
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::Category;
 use nu_protocol::Spanned;
-use nu_protocol::{Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value};
+use nu_protocol::{Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value};
 use std::sync::Arc;
 
 struct Arguments {
@@ -29,6 +29,11 @@ impl Command for SubCommand {
                 SyntaxShape::CellPath,
                 "optionally matches prefix of text by column paths",
             )
+            .input_output_type(Type::String, Type::Bool)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::Bool)),
+            )
             .category(Category::Strings)
     }
 
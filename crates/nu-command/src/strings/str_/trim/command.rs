@@ -2,7 +2,7 @@ use nu_engine::CallExt;
 use nu_protocol::{
     ast::{Call, CellPath},
     engine::{Command, EngineState, Stack},
-    Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Value,
+    Example, PipelineData, ShellError, Signature, Span, SyntaxShape, Type, Value,
 };
 
 #[derive(Clone)]
@@ -57,6 +57,11 @@ impl Command for SubCommand {
             )
             .switch("both", "trims all characters from left and right side of the string (default: whitespace)", Some('b'))
             .switch("format", "trims spaces replacing multiple characters with singles in the middle (default: whitespace)", Some('f'))
+            .input_output_type(Type::String, Type::String)
+            .input_output_type(
+                Type::List(Box::new(Type::String)),
+                Type::List(Box::new(Type::String)),
+            )
     }
     fn usage(&self) -> &str {
         "trims text"
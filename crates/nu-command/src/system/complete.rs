@@ -0,0 +1,96 @@
+use nu_protocol::ast::Call;
+use nu_protocol::engine::{Command, EngineState, Stack};
+use nu_protocol::{
+    Category, Example, IntoPipelineData, PipelineData, ShellError, Signature, Span, Type, Value,
+};
+
+#[derive(Clone)]
+pub struct Complete;
+
+impl Command for Complete {
+    fn name(&self) -> &str {
+        "complete"
+    }
+
+    fn usage(&self) -> &str {
+        "Run an external to completion and return its stdout, stderr and exit code"
+    }
+
+    fn signature(&self) -> nu_protocol::Signature {
+        Signature::build("complete")
+            .input_output_type(Type::Unknown, Type::Record(vec![]))
+            .category(Category::System)
+    }
+
+    fn run(
+        &self,
+        _engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        match input {
+            PipelineData::ExternalStream(stream, span, ..) => {
+                let mut cols = vec![];
+                let mut vals = vec![];
+
+                // Each output is read into a channel on its own thread, so reading them one
+                // after the other can't block the external
+                if let Some(stdout) = stream.stdout {
+                    cols.push("stdout".to_string());
+                    vals.push(output_to_value(stdout.into_vec()?, span));
+                }
+
+                if let Some(stderr) = stream.stderr {
+                    cols.push("stderr".to_string());
+                    vals.push(output_to_value(stderr.into_vec()?, span));
+                }
+
+                if let Some(exit_code) = stream.exit_code {
+                    for value in exit_code {
+                        if let Value::Error { error } = value {
+                            return Err(error);
+                        }
+
+                        stack.add_env_var("LAST_EXIT_CODE".to_string(), value.clone());
+
+                        cols.push("exit_code".to_string());
+                        vals.push(value);
+                    }
+                }
+
+                Ok(Value::Record { cols, vals, span }.into_pipeline_data())
+            }
+            _ => Err(ShellError::UnsupportedInput(
+                "complete only works on the output of an external command".into(),
+                call.head,
+            )),
+        }
+    }
+
+    fn examples(&self) -> Vec<Example> {
+        vec![
+            Example {
+                description: "Run an external and capture its stdout, stderr and exit code",
+                example: "^cargo build | complete",
+                result: None,
+            },
+            Example {
+                description: "Check whether an external failed without aborting the pipeline",
+                example: "(^cargo test | complete).exit_code == 0",
+                result: None,
+            },
+        ]
+    }
+}
+
+// Output that is valid UTF-8 becomes a string, anything else is kept as binary
+fn output_to_value(bytes: Vec<u8>, span: Span) -> Value {
+    match String::from_utf8(bytes) {
+        Ok(val) => Value::String { val, span },
+        Err(err) => Value::Binary {
+            val: err.into_bytes(),
+            span,
+        },
+    }
+}
@@ -1,9 +1,11 @@
 mod benchmark;
+mod complete;
 mod ps;
 mod run_external;
 mod sys;
 
 pub use benchmark::Benchmark;
+pub use complete::Complete;
 pub use ps::Ps;
-pub use run_external::{External, ExternalCommand};
+pub use run_external::{External, ExternalArg, ExternalCommand};
 pub use sys::Sys;
@@ -1,14 +1,20 @@
 use std::collections::HashMap;
 use std::env;
-use std::io::{BufRead, BufReader, Write};
-use std::process::{Command as CommandSys, Stdio};
-use std::sync::atomic::Ordering;
-use std::sync::mpsc;
-
-use nu_engine::env_to_strings;
-use nu_protocol::engine::{EngineState, Stack};
-use nu_protocol::{ast::Call, engine::Command, ShellError, Signature, SyntaxShape, Value};
-use nu_protocol::{ByteStream, Category, Config, PipelineData, Spanned};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command as CommandSys, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::Duration;
+
+use nu_engine::{current_dir, env_to_strings};
+use nu_protocol::ast::{Call, Expr};
+use nu_protocol::engine::{Command, EngineState, PendingExitCode, Stack};
+use nu_protocol::{
+    ByteStream, Category, Config, ExternalStream, PipelineData, ShellError, Signature, Span,
+    Spanned, SyntaxShape, Value, ValueStream,
+};
 
 use itertools::Itertools;
 
@@ -36,6 +42,15 @@ impl Command for External {
     fn signature(&self) -> nu_protocol::Signature {
         Signature::build("run_external")
             .switch("last_expression", "last_expression", None)
+            .switch("capture_stderr", "capture_stderr", None)
+            .named("stdout_file", SyntaxShape::String, "stdout_file", None)
+            .named("stderr_file", SyntaxShape::String, "stderr_file", None)
+            .named(
+                "stdout_and_stderr_file",
+                SyntaxShape::String,
+                "stdout_and_stderr_file",
+                None,
+            )
             .rest("rest", SyntaxShape::Any, "external command to run")
             .category(Category::System)
     }
@@ -50,21 +65,37 @@ impl Command for External {
         let name: Spanned<String> = call.req(engine_state, stack, 0)?;
         let args: Vec<Value> = call.rest(engine_state, stack, 1)?;
         let last_expression = call.has_flag("last_expression");
+        let capture_stderr = call.has_flag("capture_stderr");
+        let redirect_stdout: Option<Spanned<String>> =
+            call.get_flag(engine_state, stack, "stdout_file")?;
+        let redirect_stderr: Option<Spanned<String>> =
+            call.get_flag(engine_state, stack, "stderr_file")?;
+        let redirect_both: Option<Spanned<String>> =
+            call.get_flag(engine_state, stack, "stdout_and_stderr_file")?;
 
         // Translate environment variables from Values to Strings
         let config = stack.get_config().unwrap_or_default();
         let env_vars_str = env_to_strings(engine_state, stack, &config)?;
 
+        let cwd = current_dir(stack)?;
+
         let mut args_strs = vec![];
 
-        for arg in args {
-            if let Ok(s) = arg.as_string() {
-                args_strs.push(s);
+        for (arg, expr) in args.into_iter().zip(call.positional.iter().skip(1)) {
+            if let Expr::GlobPattern(pattern) = &expr.expr {
+                let paths = expand_glob(pattern, &cwd, expr.span)?;
+
+                args_strs.extend(paths.into_iter().map(ExternalArg::Value));
+            } else if let (Expr::String(_), Ok(s)) = (&expr.expr, arg.as_string()) {
+                // A word written in the source, not a value computed by a variable or subexpression
+                args_strs.push(ExternalArg::Literal(s));
+            } else if let Ok(s) = arg.as_string() {
+                args_strs.push(ExternalArg::Value(s));
             } else if let Value::List { vals, .. } = arg {
                 // Interpret a list as a series of arguments
                 for val in vals {
                     if let Ok(s) = val.as_string() {
-                        args_strs.push(s);
+                        args_strs.push(ExternalArg::Value(s));
                     } else {
                         return Err(ShellError::ExternalCommand(
                             "Cannot convert argument to a string".into(),
@@ -84,18 +115,41 @@ impl Command for External {
             name,
             args: args_strs,
             last_expression,
+            capture_stderr,
+            redirect_stdout: redirect_stdout.or_else(|| redirect_both.clone()),
+            redirect_stderr: redirect_stderr.or(redirect_both),
             env_vars: env_vars_str,
+            cwd,
             call,
         };
-        command.run_with_input(engine_state, input, config)
+        command.run_with_input(engine_state, stack, input, config)
+    }
+}
+
+// An argument of an external. Only the words written in the source are left for an external
+// shell to expand, values like variables and globs already expanded by nu are passed on as they are
+pub enum ExternalArg {
+    Literal(String),
+    Value(String),
+}
+
+impl ExternalArg {
+    pub fn as_str(&self) -> &str {
+        match self {
+            ExternalArg::Literal(arg) | ExternalArg::Value(arg) => arg,
+        }
     }
 }
 
 pub struct ExternalCommand<'call> {
     pub name: Spanned<String>,
-    pub args: Vec<String>,
+    pub args: Vec<ExternalArg>,
     pub last_expression: bool,
+    pub capture_stderr: bool,
+    pub redirect_stdout: Option<Spanned<String>>,
+    pub redirect_stderr: Option<Spanned<String>>,
     pub env_vars: HashMap<String, String>,
+    pub cwd: PathBuf,
     pub call: &'call Call,
 }
 
@@ -103,28 +157,36 @@ impl<'call> ExternalCommand<'call> {
     pub fn run_with_input(
         &self,
         engine_state: &EngineState,
+        stack: &mut Stack,
         input: PipelineData,
         config: Config,
     ) -> Result<PipelineData, ShellError> {
-        let mut process = self.create_command();
+        let mut process = self.create_command(&config);
         let head = self.name.span;
 
         let ctrlc = engine_state.ctrlc.clone();
 
-        // TODO. We don't have a way to know the current directory
-        // This should be information from the EvaluationContex or EngineState
-        let path = env::current_dir()?;
-
-        process.current_dir(path);
+        process.current_dir(&self.cwd);
 
         process.envs(&self.env_vars);
 
+        let (stdout_file, stderr_file) = self.redirection_files()?;
+
         // If the external is not the last command, its output will get piped
         // either as a string or binary
-        if !self.last_expression {
+        if let Some(file) = stdout_file {
+            process.stdout(file);
+        } else if !self.last_expression {
             process.stdout(Stdio::piped());
         }
 
+        // Stderr goes to the terminal unless it is redirected or the next command reads it
+        if let Some(file) = stderr_file {
+            process.stderr(file);
+        } else if self.capture_stderr {
+            process.stderr(Stdio::piped());
+        }
+
         // If there is an input from the pipeline. The stdin from the process
         // is piped so it can be used to send the input information
         if !matches!(input, PipelineData::Value(Value::Nothing { .. }, ..)) {
@@ -166,62 +228,72 @@ impl<'call> ExternalCommand<'call> {
                     });
                 }
 
-                let last_expression = self.last_expression;
+                let name = self.name.item.clone();
                 let span = self.name.span;
-                let output_ctrlc = ctrlc.clone();
-                let (tx, rx) = mpsc::channel();
-
-                std::thread::spawn(move || {
-                    // If this external is not the last expression, then its output is piped to a channel
-                    // and we create a ValueStream that can be consumed
-                    if !last_expression {
-                        let stdout = child.stdout.take().ok_or_else(|| {
-                            ShellError::ExternalCommand(
-                                "Error taking stdout from external".to_string(),
-                                span,
-                            )
-                        })?;
-
-                        // Stdout is read using the Buffer reader. It will do so until there is an
-                        // error or there are no more bytes to read
-                        let mut buf_read = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, stdout);
-                        while let Ok(bytes) = buf_read.fill_buf() {
-                            if bytes.is_empty() {
-                                break;
-                            }
 
-                            // The Cow generated from the function represents the conversion
-                            // from bytes to String. If no replacements are required, then the
-                            // borrowed value is a proper UTF-8 string. The Owned option represents
-                            // a string where the values had to be replaced, thus marking it as bytes
-                            let bytes = bytes.to_vec();
-                            let length = bytes.len();
-                            buf_read.consume(length);
-
-                            if let Some(ctrlc) = &ctrlc {
-                                if ctrlc.load(Ordering::SeqCst) {
-                                    break;
-                                }
-                            }
+                // The output of the last expression goes straight to the terminal, so we can wait
+                // for the exit code here and record it in the stack
+                if self.last_expression {
+                    let status = child
+                        .wait()
+                        .map_err(|err| ShellError::ExternalCommand(format!("{}", err), span))?;
+
+                    // Externals earlier in the pipeline are done too, and came before this one
+                    stack.record_exit_codes();
+                    stack.add_env_var(
+                        "LAST_EXIT_CODE".to_string(),
+                        Value::Int {
+                            val: exit_code(status),
+                            span,
+                        },
+                    );
+
+                    return Ok(PipelineData::new(head));
+                }
 
-                            match tx.send(bytes) {
-                                Ok(_) => continue,
-                                Err(_) => break,
-                            }
-                        }
-                    }
+                // This external is not the last expression, so its piped outputs are read into
+                // channels and given to the next command as streams
+                let stdout = child
+                    .stdout
+                    .take()
+                    .map(|stdout| read_to_stream(stdout, ctrlc.clone()));
+                let stderr = child
+                    .stderr
+                    .take()
+                    .map(|stderr| read_to_stream(stderr, ctrlc.clone()));
+
+                let (exit_code_tx, exit_code_rx) = mpsc::channel();
+
+                // The stack gets its own copy of the exit code, as the command reading the
+                // output may not read the exit code
+                let (last_exit_code_tx, last_exit_code_rx) = mpsc::channel();
+                let stop = Arc::new(AtomicBool::new(false));
+                stack.pending_exit_codes.push(PendingExitCode {
+                    exit_code: Arc::new(Mutex::new(last_exit_code_rx)),
+                    stop: stop.clone(),
+                });
 
-                    match child.wait() {
-                        Err(err) => Err(ShellError::ExternalCommand(format!("{}", err), span)),
-                        Ok(_) => Ok(()),
-                    }
+                std::thread::spawn(move || {
+                    let exit_code = match wait_unless_stopped(&mut child, &stop) {
+                        Ok(status) => Value::Int {
+                            val: exit_code(status),
+                            span,
+                        },
+                        Err(err) => Value::Error {
+                            error: ShellError::ExternalCommand(format!("{}", err), span),
+                        },
+                    };
+
+                    let _ = last_exit_code_tx.send(exit_code.clone());
+                    let _ = exit_code_tx.send(exit_code);
                 });
-                let receiver = ChannelReceiver::new(rx);
 
-                Ok(PipelineData::ByteStream(
-                    ByteStream {
-                        stream: Box::new(receiver),
-                        ctrlc: output_ctrlc,
+                Ok(PipelineData::ExternalStream(
+                    ExternalStream {
+                        name,
+                        stdout,
+                        stderr,
+                        exit_code: Some(ValueStream::from_stream(exit_code_rx.into_iter(), ctrlc)),
                     },
                     head,
                     None,
@@ -230,8 +302,34 @@ impl<'call> ExternalCommand<'call> {
         }
     }
 
-    fn create_command(&self) -> CommandSys {
-        // in all the other cases shell out
+    // Open the files that `out>`, `err>` and `out+err>` send the outputs to
+    fn redirection_files(&self) -> Result<(Option<File>, Option<File>), ShellError> {
+        let create = |target: &Spanned<String>| {
+            let path = nu_path::expand_path_with(trim_enclosing_quotes(&target.item), &self.cwd);
+
+            File::create(path)
+                .map_err(|err| ShellError::CreateNotPossible(err.to_string(), target.span))
+        };
+
+        match (&self.redirect_stdout, &self.redirect_stderr) {
+            // Both outputs go to the same file, so they share one handle rather than
+            // overwriting each other
+            (Some(stdout), Some(stderr)) if stdout.item == stderr.item => {
+                let file = create(stdout)?;
+                let clone = file
+                    .try_clone()
+                    .map_err(|err| ShellError::CreateNotPossible(err.to_string(), stdout.span))?;
+
+                Ok((Some(file), Some(clone)))
+            }
+            (stdout, stderr) => Ok((
+                stdout.as_ref().map(create).transpose()?,
+                stderr.as_ref().map(create).transpose()?,
+            )),
+        }
+    }
+
+    fn create_command(&self, config: &Config) -> CommandSys {
         if cfg!(windows) {
             //TODO. This should be modifiable from the config file.
             // We could give the option to call from powershell
@@ -241,7 +339,8 @@ impl<'call> ExternalCommand<'call> {
             } else {
                 self.spawn_simple_command()
             }
-        } else if self.name.item.ends_with(".sh") {
+        } else if config.external_shell {
+            // Shell features like globs and `$VAR` expansion are opt-in through the config
             self.spawn_sh_command()
         } else {
             self.spawn_simple_command()
@@ -250,20 +349,44 @@ impl<'call> ExternalCommand<'call> {
 
     /// Spawn a command without shelling out to an external shell
     fn spawn_simple_command(&self) -> std::process::Command {
-        let mut process = std::process::Command::new(&self.name.item);
+        let mut process = std::process::Command::new(self.program());
 
         for arg in &self.args {
-            let arg = trim_enclosing_quotes(arg);
+            let arg = trim_enclosing_quotes(arg.as_str());
             let arg = nu_path::expand_path(arg).to_string_lossy().to_string();
 
-            let arg = arg.replace("\\", "\\\\");
-
             process.arg(&arg);
         }
 
         process
     }
 
+    // The program to run, found using the PATH of the environment the external runs in. When it
+    // can't be found the name is used as is, so spawning it reports the usual "not found" error
+    fn program(&self) -> PathBuf {
+        let name = trim_enclosing_quotes(&self.name.item);
+
+        if name.contains(std::path::is_separator) {
+            return nu_path::expand_path_with(name, &self.cwd);
+        }
+
+        let path = self
+            .env_vars
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("PATH"))
+            .map(|(_, path)| path.clone())
+            .unwrap_or_default();
+
+        env::split_paths(&path)
+            .flat_map(|dir| {
+                executable_names(&name)
+                    .into_iter()
+                    .map(move |name| dir.join(name))
+            })
+            .find(|candidate| is_executable(candidate))
+            .unwrap_or_else(|| PathBuf::from(name))
+    }
+
     /// Spawn a cmd command with `cmd /c args...`
     fn spawn_cmd_command(&self) -> std::process::Command {
         let mut process = std::process::Command::new("cmd");
@@ -273,7 +396,7 @@ impl<'call> ExternalCommand<'call> {
             // Clean the args before we use them:
             // https://stackoverflow.com/questions/1200235/how-to-pass-a-quoted-pipe-character-to-cmd-exe
             // cmd.exe needs to have a caret to escape a pipe
-            let arg = arg.replace("|", "^|");
+            let arg = arg.as_str().replace("|", "^|");
             process.arg(&arg);
         }
         process
@@ -281,8 +404,23 @@ impl<'call> ExternalCommand<'call> {
 
     /// Spawn a sh command with `sh -c args...`
     fn spawn_sh_command(&self) -> std::process::Command {
-        let joined_and_escaped_arguments =
-            self.args.iter().map(|arg| shell_arg_escape(arg)).join(" ");
+        // Bare words are left for the shell to expand, quoted words and values are escaped so the
+        // shell passes them on as they are
+        let joined_and_escaped_arguments = self
+            .args
+            .iter()
+            .map(|arg| match arg {
+                ExternalArg::Literal(arg) => {
+                    let trimmed = trim_enclosing_quotes(arg);
+                    if trimmed.len() == arg.len() {
+                        trimmed
+                    } else {
+                        shell_arg_escape(&trimmed)
+                    }
+                }
+                ExternalArg::Value(arg) => shell_arg_escape(arg),
+            })
+            .join(" ");
         let cmd_with_args = vec![self.name.item.clone(), joined_and_escaped_arguments].join(" ");
         let mut process = std::process::Command::new("sh");
         process.arg("-c").arg(cmd_with_args);
@@ -290,6 +428,91 @@ impl<'call> ExternalCommand<'call> {
     }
 }
 
+// Expand a bare glob argument the way `ls` does. Relative patterns are matched against the
+// current directory and expand to relative paths
+fn expand_glob(pattern: &str, cwd: &Path, span: Span) -> Result<Vec<String>, ShellError> {
+    let expanded = nu_path::expand_tilde(pattern);
+    let full_pattern = cwd.join(&expanded);
+
+    let paths = glob::glob(&full_pattern.to_string_lossy())
+        .map_err(|err| {
+            ShellError::SpannedLabeledError(
+                "Error extracting glob pattern".into(),
+                err.to_string(),
+                span,
+            )
+        })?
+        .filter_map(Result::ok)
+        .map(|path| {
+            let path = if expanded.is_relative() {
+                path.strip_prefix(cwd).unwrap_or(&path)
+            } else {
+                &path
+            };
+
+            path.to_string_lossy().to_string()
+        })
+        .collect::<Vec<_>>();
+
+    if paths.is_empty() {
+        return Err(ShellError::FileNotFoundCustom(
+            format!("no matches found for {}", pattern),
+            span,
+        ));
+    }
+
+    Ok(paths)
+}
+
+// The file names an external can have. On Windows a bare name also matches the PATHEXT extensions
+fn executable_names(name: &str) -> Vec<String> {
+    let mut names = vec![name.to_string()];
+
+    if cfg!(windows) && Path::new(name).extension().is_none() {
+        let extensions = env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+
+        names.extend(extensions.split(';').map(|ext| format!("{}{}", name, ext)));
+    }
+
+    names
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+// An external killed by a signal has no exit code, so it is reported as -1
+// Wait for a piped external to exit. It is killed if the stack asks for it to stop, which happens
+// when the pipeline is done while the external still runs
+fn wait_unless_stopped(child: &mut Child, stop: &AtomicBool) -> std::io::Result<ExitStatus> {
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+
+        if stop.load(Ordering::SeqCst) {
+            let _ = child.kill();
+            return child.wait();
+        }
+
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+fn exit_code(status: ExitStatus) -> i64 {
+    status.code().map(i64::from).unwrap_or(-1)
+}
+
 fn has_unsafe_shell_characters(arg: &str) -> bool {
     let re: Regex = Regex::new(r"[^\w@%+=:,./-]").expect("regex to be valid");
 
@@ -317,14 +540,55 @@ fn trim_enclosing_quotes(input: &str) -> String {
     }
 }
 
+// Read the output of an external on its own thread, so that a full pipe can't block the external
+// while another of its outputs is being read
+fn read_to_stream<R: Read + Send + 'static>(
+    output: R,
+    ctrlc: Option<Arc<AtomicBool>>,
+) -> ByteStream {
+    let (tx, rx) = mpsc::channel();
+    let thread_ctrlc = ctrlc.clone();
+
+    std::thread::spawn(move || {
+        // The output is read using the Buffer reader. It will do so until there is an
+        // error or there are no more bytes to read
+        let mut buf_read = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, output);
+        while let Ok(bytes) = buf_read.fill_buf() {
+            if bytes.is_empty() {
+                break;
+            }
+
+            let bytes = bytes.to_vec();
+            let length = bytes.len();
+            buf_read.consume(length);
+
+            if let Some(ctrlc) = &thread_ctrlc {
+                if ctrlc.load(Ordering::SeqCst) {
+                    break;
+                }
+            }
+
+            match tx.send(Ok(bytes)) {
+                Ok(_) => continue,
+                Err(_) => break,
+            }
+        }
+    });
+
+    ByteStream {
+        stream: Box::new(ChannelReceiver::new(rx)),
+        ctrlc,
+    }
+}
+
 // Receiver used for the ValueStream
 // It implements iterator so it can be used as a ValueStream
 struct ChannelReceiver {
-    rx: mpsc::Receiver<Vec<u8>>,
+    rx: mpsc::Receiver<Result<Vec<u8>, ShellError>>,
 }
 
 impl ChannelReceiver {
-    pub fn new(rx: mpsc::Receiver<Vec<u8>>) -> Self {
+    pub fn new(rx: mpsc::Receiver<Result<Vec<u8>, ShellError>>) -> Self {
         Self { rx }
     }
 }
@@ -333,9 +597,6 @@ impl Iterator for ChannelReceiver {
     type Item = Result<Vec<u8>, ShellError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.rx.recv() {
-            Ok(v) => Some(Ok(v)),
-            Err(_) => None,
-        }
+        self.rx.recv().ok()
     }
 }
@@ -1,6 +1,6 @@
 use lscolors::{LsColors, Style};
 use nu_color_config::{get_color_config, style_primitive};
-use nu_engine::{env_to_string, CallExt};
+use nu_engine::{current_dir, env_to_string, CallExt};
 use nu_protocol::ast::{Call, PathMember};
 use nu_protocol::engine::{Command, EngineState, Stack};
 use nu_protocol::{
@@ -75,6 +75,12 @@ impl Command for Table {
                 head,
                 None,
             )),
+            PipelineData::ExternalStream(stream, span, metadata) => self.run(
+                engine_state,
+                stack,
+                call,
+                PipelineData::ByteStream(stream.into_stdout(span), span, metadata),
+            ),
             PipelineData::Value(Value::Binary { val, .. }, ..) => Ok(PipelineData::StringStream(
                 StringStream::from_stream(
                     vec![Ok(if val.iter().all(|x| x.is_ascii()) {
@@ -110,6 +116,8 @@ impl Command for Table {
                     }) => {
                         let config = config.clone();
                         let ctrlc = ctrlc.clone();
+                        // The names `ls` lists are relative to PWD
+                        let cwd = current_dir(stack)?;
 
                         let ls_colors = match stack.get_env_var("LS_COLORS") {
                             Some(v) => LsColors::from_string(&env_to_string(
@@ -132,7 +140,7 @@ impl Command for Table {
                                             if let Some(Value::String { val: path, span }) =
                                                 vals.get(idx)
                                             {
-                                                match std::fs::symlink_metadata(&path) {
+                                                match std::fs::symlink_metadata(cwd.join(&path)) {
                                                     Ok(metadata) => {
                                                         let style = ls_colors
                                                             .style_for_path_with_metadata(
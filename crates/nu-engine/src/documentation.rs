@@ -1,6 +1,7 @@
 use itertools::Itertools;
 use nu_protocol::{
-    engine::EngineState, Example, PositionalArg, Signature, Span, SyntaxShape, Value,
+    ast::Statement, engine::EngineState, BlockId, Example, PositionalArg, Signature, Span,
+    SyntaxShape, Value,
 };
 use std::collections::HashMap;
 
@@ -209,10 +210,16 @@ pub fn get_documentation(
             long_desc.push_str(&format!("  {}: {}\n", positional.name, positional.desc));
         }
         for positional in &sig.optional_positional {
-            long_desc.push_str(&format!(
-                "  (optional) {}: {}\n",
-                positional.name, positional.desc
-            ));
+            match get_default_value(engine_state, positional.default_value) {
+                Some(default) => long_desc.push_str(&format!(
+                    "  (optional, default: {}) {}: {}\n",
+                    default, positional.name, positional.desc
+                )),
+                None => long_desc.push_str(&format!(
+                    "  (optional) {}: {}\n",
+                    positional.name, positional.desc
+                )),
+            }
         }
 
         if let Some(rest_positional) = &sig.rest_positional {
@@ -220,7 +227,7 @@ pub fn get_documentation(
         }
     }
     if !sig.named.is_empty() {
-        long_desc.push_str(&get_flags_section(sig))
+        long_desc.push_str(&get_flags_section(sig, engine_state))
     }
 
     if !examples.is_empty() {
@@ -265,10 +272,27 @@ fn get_positional_short_name(arg: &PositionalArg, is_required: bool) -> String {
     }
 }
 
-fn get_flags_section(signature: &Signature) -> String {
+// The source of a parameter's default value, as written in its signature
+fn get_default_value(engine_state: &EngineState, block_id: Option<BlockId>) -> Option<String> {
+    let block = engine_state.get_block(block_id?);
+
+    match block.stmts.first() {
+        Some(Statement::Pipeline(pipeline)) => {
+            let expr = pipeline.expressions.first()?;
+            Some(String::from_utf8_lossy(engine_state.get_span_contents(&expr.span)).to_string())
+        }
+        _ => None,
+    }
+}
+
+fn get_flags_section(signature: &Signature, engine_state: &EngineState) -> String {
     let mut long_desc = String::new();
     long_desc.push_str("\nFlags:\n");
     for flag in &signature.named {
+        let desc = match get_default_value(engine_state, flag.default_value) {
+            Some(default) => format!("{} (default: {})", flag.desc, default),
+            None => flag.desc.clone(),
+        };
         let msg = if let Some(arg) = &flag.arg {
             if let Some(short) = flag.short {
                 if flag.required {
@@ -281,7 +305,7 @@ fn get_flags_section(signature: &Signature) -> String {
                             "".into()
                         },
                         arg,
-                        flag.desc
+                        desc
                     )
                 } else {
                     format!(
@@ -293,16 +317,16 @@ fn get_flags_section(signature: &Signature) -> String {
                             "".into()
                         },
                         arg,
-                        flag.desc
+                        desc
                     )
                 }
             } else if flag.required {
                 format!(
                     "  --{} (required parameter) {:?} {}\n",
-                    flag.long, arg, flag.desc
+                    flag.long, arg, desc
                 )
             } else {
-                format!("  --{} {:?} {}\n", flag.long, arg, flag.desc)
+                format!("  --{} {:?} {}\n", flag.long, arg, desc)
             }
         } else if let Some(short) = flag.short {
             if flag.required {
@@ -314,7 +338,7 @@ fn get_flags_section(signature: &Signature) -> String {
                     } else {
                         "".into()
                     },
-                    flag.desc
+                    desc
                 )
             } else {
                 format!(
@@ -325,13 +349,13 @@ fn get_flags_section(signature: &Signature) -> String {
                     } else {
                         "".into()
                     },
-                    flag.desc
+                    desc
                 )
             }
         } else if flag.required {
-            format!("  --{} (required parameter) {}\n", flag.long, flag.desc)
+            format!("  --{} (required parameter) {}\n", flag.long, desc)
         } else {
-            format!("  --{} {}\n", flag.long, flag.desc)
+            format!("  --{} {}\n", flag.long, desc)
         };
         long_desc.push_str(&msg);
     }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 use nu_protocol::engine::{EngineState, Stack};
 use nu_protocol::{Config, PipelineData, ShellError, Value};
@@ -140,3 +141,20 @@ pub fn env_to_strings(
 
     Ok(env_vars_str)
 }
+
+/// Get the current working directory from `$env.PWD`. The engine never changes the directory of
+/// its process, so relative paths must be resolved against this one instead of the process one.
+///
+/// Falls back to the directory of the process when PWD was never set, e.g. in an embedded engine.
+pub fn current_dir(stack: &Stack) -> Result<PathBuf, ShellError> {
+    match stack.get_env_var("PWD") {
+        Some(pwd) => Ok(PathBuf::from(pwd.as_string()?)),
+        None => std::env::current_dir()
+            .map_err(|err| ShellError::IOError(format!("Cannot get current directory: {}", err))),
+    }
+}
+
+/// Same as [`current_dir`], as a String
+pub fn current_dir_str(stack: &Stack) -> Result<String, ShellError> {
+    current_dir(stack).map(|cwd| cwd.to_string_lossy().to_string())
+}
@@ -1,14 +1,16 @@
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::io::Write;
+use std::path::Path;
 
-use nu_protocol::ast::{Block, Call, Expr, Expression, Operator, Statement};
+use nu_protocol::ast::{Block, Call, Expr, Expression, Operator, Pipeline, Redirection, Statement};
 use nu_protocol::engine::{EngineState, Stack};
 use nu_protocol::{
-    IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Range, ShellError, Span,
-    Spanned, Type, Unit, Value, VarId,
+    BlockId, IntoInterruptiblePipelineData, IntoPipelineData, PipelineData, Range, ShellError,
+    Span, Spanned, Type, Unit, Value, VarId,
 };
 
-use crate::get_full_help;
+use crate::{current_dir, current_dir_str, get_full_help};
 
 pub fn eval_operator(op: &Expression) -> Result<Operator, ShellError> {
     match op {
@@ -41,6 +43,7 @@ fn eval_call(
         let block = engine_state.get_block(block_id);
 
         let mut callee_stack = caller_stack.collect_captures(&block.captures);
+        let num_required = decl.signature().required_positional.len();
 
         for (param_idx, param) in decl
             .signature()
@@ -55,6 +58,17 @@ fn eval_call(
 
             if let Some(arg) = call.positional.get(param_idx) {
                 let result = eval_expression(engine_state, caller_stack, arg)?;
+                check_argument_type(
+                    engine_state,
+                    var_id,
+                    &param.shape.to_type(),
+                    &result,
+                    arg.span,
+                    param_idx >= num_required,
+                )?;
+                callee_stack.add_var(var_id, result);
+            } else if let Some(block_id) = param.default_value {
+                let result = eval_default_value(engine_state, &mut callee_stack, block_id, call)?;
                 callee_stack.add_var(var_id, result);
             } else {
                 callee_stack.add_var(var_id, Value::nothing(call.head));
@@ -63,12 +77,23 @@ fn eval_call(
 
         if let Some(rest_positional) = decl.signature().rest_positional {
             let mut rest_items = vec![];
+            let var_id = rest_positional
+                .var_id
+                .expect("Internal error: rest positional parameter lacks var_id");
 
             for arg in call.positional.iter().skip(
                 decl.signature().required_positional.len()
                     + decl.signature().optional_positional.len(),
             ) {
                 let result = eval_expression(engine_state, caller_stack, arg)?;
+                check_argument_type(
+                    engine_state,
+                    var_id,
+                    &rest_positional.shape.to_type(),
+                    &result,
+                    arg.span,
+                    false,
+                )?;
                 rest_items.push(result);
             }
 
@@ -79,9 +104,7 @@ fn eval_call(
             };
 
             callee_stack.add_var(
-                rest_positional
-                    .var_id
-                    .expect("Internal error: rest positional parameter lacks var_id"),
+                var_id,
                 Value::List {
                     vals: rest_items,
                     span,
@@ -96,6 +119,16 @@ fn eval_call(
                     if call_named.0.item == named.long {
                         if let Some(arg) = &call_named.1 {
                             let result = eval_expression(engine_state, caller_stack, arg)?;
+                            if let Some(shape) = &named.arg {
+                                check_argument_type(
+                                    engine_state,
+                                    var_id,
+                                    &shape.to_type(),
+                                    &result,
+                                    arg.span,
+                                    !named.required,
+                                )?;
+                            }
 
                             callee_stack.add_var(var_id, result);
                         } else {
@@ -119,10 +152,21 @@ fn eval_call(
                             span: call.head,
                         },
                     )
+                } else if !found {
+                    let result = match named.default_value {
+                        Some(block_id) => {
+                            eval_default_value(engine_state, &mut callee_stack, block_id, call)?
+                        }
+                        None => Value::nothing(call.head),
+                    };
+                    callee_stack.add_var(var_id, result);
                 }
             }
         }
-        eval_block(engine_state, &mut callee_stack, block, input)
+        let result = eval_block(engine_state, &mut callee_stack, block, input);
+        callee_stack.pass_exit_code_to(caller_stack);
+
+        result
     } else {
         // We pass caller_stack here with the knowledge that internal commands
         // are going to be specifically looking for global state in the stack
@@ -131,14 +175,64 @@ fn eval_call(
     }
 }
 
+// The parser only checks literal arguments, values coming from variables and subexpressions
+// are checked here against the declared type of the parameter
+fn check_argument_type(
+    engine_state: &EngineState,
+    var_id: VarId,
+    ty: &Type,
+    value: &Value,
+    arg_span: Span,
+    optional: bool,
+) -> Result<(), ShellError> {
+    if value_matches_type(value, ty) || (optional && matches!(value, Value::Nothing { .. })) {
+        Ok(())
+    } else {
+        Err(ShellError::ArgumentTypeMismatch(
+            ty.to_string(),
+            value.get_type().to_string(),
+            arg_span,
+            engine_state.get_var(var_id).declaration_span,
+        ))
+    }
+}
+
+fn value_matches_type(value: &Value, ty: &Type) -> bool {
+    match (ty, value) {
+        (Type::Unknown, _) => true,
+        (Type::Number, Value::Int { .. }) | (Type::Number, Value::Float { .. }) => true,
+        (Type::List(inner), Value::List { vals, .. }) => {
+            vals.iter().all(|val| value_matches_type(val, inner))
+        }
+        // A bare `record` annotation accepts a record with any fields
+        (Type::Record(_), Value::Record { .. }) => true,
+        (ty, value) => &value.get_type() == ty,
+    }
+}
+
+// Default values of parameters are evaluated each time the argument is left out
+fn eval_default_value(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    block_id: BlockId,
+    call: &Call,
+) -> Result<Value, ShellError> {
+    let block = engine_state.get_block(block_id);
+
+    Ok(eval_block(engine_state, stack, block, PipelineData::new(call.head))?.into_value(call.head))
+}
+
+#[allow(clippy::too_many_arguments)]
 fn eval_external(
     engine_state: &EngineState,
     stack: &mut Stack,
     name: &str,
     name_span: &Span,
     args: &[Expression],
+    redirections: &[(Redirection, Expression)],
     input: PipelineData,
     last_expression: bool,
+    capture_stderr: bool,
 ) -> Result<PipelineData, ShellError> {
     let decl_id = engine_state
         .find_decl("run_external".as_bytes())
@@ -169,9 +263,47 @@ fn eval_external(
         ))
     }
 
+    if capture_stderr {
+        call.named.push((
+            Spanned {
+                item: "capture_stderr".into(),
+                span: *name_span,
+            },
+            None,
+        ))
+    }
+
+    for (redirection, target) in redirections {
+        let flag = match redirection {
+            Redirection::Stdout => "stdout_file",
+            Redirection::Stderr => "stderr_file",
+            Redirection::StdoutAndStderr => "stdout_and_stderr_file",
+        };
+
+        call.named.push((
+            Spanned {
+                item: flag.into(),
+                span: target.span,
+            },
+            Some(target.clone()),
+        ))
+    }
+
     command.run(engine_state, stack, &call, input)
 }
 
+// `complete` reads the stderr of the external before it, so it has to be captured rather than
+// sent to the terminal
+fn is_followed_by_complete(engine_state: &EngineState, pipeline: &Pipeline, idx: usize) -> bool {
+    match pipeline.expressions.get(idx + 1) {
+        Some(Expression {
+            expr: Expr::Call(call),
+            ..
+        }) => engine_state.get_decl(call.decl_id).name() == "complete",
+        _ => false,
+    }
+}
+
 pub fn eval_expression(
     engine_state: &EngineState,
     stack: &mut Stack,
@@ -241,7 +373,7 @@ pub fn eval_expression(
                     .into_value(call.head),
             )
         }
-        Expr::ExternalCall(name, span, args) => {
+        Expr::ExternalCall(name, span, args, redirections) => {
             // FIXME: protect this collect with ctrl-c
             Ok(eval_external(
                 engine_state,
@@ -249,8 +381,10 @@ pub fn eval_expression(
                 name,
                 span,
                 args,
+                redirections,
                 PipelineData::new(*span),
                 false,
+                false,
             )?
             .into_value(*span))
         }
@@ -266,6 +400,7 @@ pub fn eval_expression(
                 Operator::Minus => lhs.sub(op_span, &rhs),
                 Operator::Multiply => lhs.mul(op_span, &rhs),
                 Operator::Divide => lhs.div(op_span, &rhs),
+                Operator::FloorDivision => lhs.floor_div(op_span, &rhs),
                 Operator::LessThan => lhs.lt(op_span, &rhs),
                 Operator::LessThanOrEqual => lhs.lte(op_span, &rhs),
                 Operator::GreaterThan => lhs.gt(op_span, &rhs),
@@ -274,12 +409,50 @@ pub fn eval_expression(
                 Operator::NotEqual => lhs.ne(op_span, &rhs),
                 Operator::In => lhs.r#in(op_span, &rhs),
                 Operator::NotIn => lhs.not_in(op_span, &rhs),
-                Operator::Contains => lhs.contains(op_span, &rhs),
-                Operator::NotContains => lhs.not_contains(op_span, &rhs),
+                Operator::RegexMatch => lhs.regex_match(engine_state, op_span, &rhs, false),
+                Operator::NotRegexMatch => lhs.regex_match(engine_state, op_span, &rhs, true),
+                Operator::StartsWith => lhs.starts_with(op_span, &rhs),
+                Operator::EndsWith => lhs.ends_with(op_span, &rhs),
                 Operator::Modulo => lhs.modulo(op_span, &rhs),
                 Operator::And => lhs.and(op_span, &rhs),
                 Operator::Or => lhs.or(op_span, &rhs),
                 Operator::Pow => lhs.pow(op_span, &rhs),
+                Operator::BitOr => lhs.bit_or(op_span, &rhs),
+                Operator::BitXor => lhs.bit_xor(op_span, &rhs),
+                Operator::BitAnd => lhs.bit_and(op_span, &rhs),
+                Operator::ShiftLeft => lhs.bit_shl(op_span, &rhs),
+                Operator::ShiftRight => lhs.bit_shr(op_span, &rhs),
+                Operator::Assign | Operator::PlusAssign | Operator::MinusAssign => {
+                    Err(ShellError::UnsupportedOperator(op, op_span))
+                }
+            }
+        }
+        Expr::Assignment(lhs, op, rhs) => {
+            let op_span = op.span;
+            let op = eval_operator(op)?;
+            let rhs = eval_expression(engine_state, stack, rhs)?;
+
+            match &lhs.expr {
+                Expr::Var(var_id) => {
+                    let value = match op {
+                        Operator::Assign => rhs,
+                        Operator::PlusAssign => {
+                            let lhs = eval_variable(engine_state, stack, *var_id, lhs.span)?;
+                            lhs.add(op_span, &rhs)?
+                        }
+                        Operator::MinusAssign => {
+                            let lhs = eval_variable(engine_state, stack, *var_id, lhs.span)?;
+                            lhs.sub(op_span, &rhs)?
+                        }
+                        _ => return Err(ShellError::UnsupportedOperator(op, op_span)),
+                    };
+
+                    // Mutable variables can't be captured by blocks, so they live in this stack
+                    stack.add_var(*var_id, value);
+
+                    Ok(Value::Nothing { span: expr.span })
+                }
+                _ => Err(ShellError::UnsupportedOperator(op, op_span)),
             }
         }
         Expr::RowCondition(block_id) | Expr::Subexpression(block_id) => {
@@ -291,10 +464,23 @@ pub fn eval_expression(
                     .into_value(expr.span),
             )
         }
-        Expr::Block(block_id) => Ok(Value::Block {
-            val: *block_id,
-            span: expr.span,
-        }),
+        Expr::Block(block_id) => {
+            let block = engine_state.get_block(*block_id);
+            let mut captures = HashMap::new();
+
+            // Snapshot the captured variables so the block can be run later as a closure
+            for var_id in &block.captures {
+                if let Ok(value) = stack.get_var(*var_id) {
+                    captures.insert(*var_id, value);
+                }
+            }
+
+            Ok(Value::Block {
+                val: *block_id,
+                captures,
+                span: expr.span,
+            })
+        }
         Expr::List(x) => {
             let mut output = vec![];
             for expr in x {
@@ -365,19 +551,40 @@ pub fn eval_expression(
             span: expr.span,
         }),
         Expr::Filepath(s) => Ok(Value::String {
-            val: s.clone(),
+            val: expand_path_in_cwd(s, stack)?,
             span: expr.span,
         }),
         Expr::GlobPattern(s) => Ok(Value::String {
-            val: s.clone(),
+            val: expand_path_in_cwd(s, stack)?,
             span: expr.span,
         }),
         Expr::Signature(_) => Ok(Value::Nothing { span: expr.span }),
+        Expr::MatchBlock(_) => Ok(Value::Nothing { span: expr.span }),
+        Expr::MatchPattern(_) => Ok(Value::Nothing { span: expr.span }),
         Expr::Garbage => Ok(Value::Nothing { span: expr.span }),
         Expr::Nothing => Ok(Value::Nothing { span: expr.span }),
     }
 }
 
+// Expands `~` and the dots of a path against `$env.PWD`. Paths under it are kept relative, as
+// commands join them to `$env.PWD` themselves
+fn expand_path_in_cwd(path: &str, stack: &Stack) -> Result<String, ShellError> {
+    let cwd = current_dir(stack)?;
+    let expanded = nu_path::expand_path_with(path, &cwd);
+
+    let expanded = if Path::new(path).is_relative() && !path.starts_with('~') {
+        match expanded.strip_prefix(&cwd) {
+            Ok(relative) if relative.as_os_str().is_empty() => Path::new(".").to_path_buf(),
+            Ok(relative) => relative.to_path_buf(),
+            Err(_) => expanded,
+        }
+    } else {
+        expanded
+    };
+
+    Ok(expanded.to_string_lossy().to_string())
+}
+
 pub fn eval_block(
     engine_state: &EngineState,
     stack: &mut Stack,
@@ -396,7 +603,7 @@ pub fn eval_block(
                         input = eval_call(engine_state, stack, call, input)?;
                     }
                     Expression {
-                        expr: Expr::ExternalCall(name, name_span, args),
+                        expr: Expr::ExternalCall(name, name_span, args, redirections),
                         ..
                     } => {
                         input = eval_external(
@@ -405,8 +612,10 @@ pub fn eval_block(
                             name,
                             name_span,
                             args,
+                            redirections,
                             input,
                             i == pipeline.expressions.len() - 1,
+                            is_followed_by_complete(engine_state, pipeline, i),
                         )?;
                     }
 
@@ -434,6 +643,7 @@ pub fn eval_block(
                         let stdout = std::io::stdout();
 
                         if let Value::Error { error } = item {
+                            stack.record_exit_codes();
                             return Err(error);
                         }
 
@@ -451,6 +661,7 @@ pub fn eval_block(
                         let stdout = std::io::stdout();
 
                         if let Value::Error { error } = item {
+                            stack.record_exit_codes();
                             return Err(error);
                         }
 
@@ -465,6 +676,9 @@ pub fn eval_block(
                 }
             };
 
+            // The pipeline has been read, so the externals in it are done
+            stack.record_exit_codes();
+
             input = PipelineData::new(Span { start: 0, end: 0 })
         }
     }
@@ -472,6 +686,21 @@ pub fn eval_block(
     Ok(input)
 }
 
+/// Run a block in place, like a branch of `if`. It runs with a copy of the variables it captured,
+/// and gives the mutable ones back to the caller once it's done
+pub fn eval_block_in_place(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    block: &Block,
+    input: PipelineData,
+) -> Result<PipelineData, ShellError> {
+    let mut block_stack = stack.collect_captures(&block.captures);
+    let result = eval_block(engine_state, &mut block_stack, block, input);
+    block_stack.pass_mutable_captures_to(engine_state, stack, &block.captures);
+
+    result
+}
+
 pub fn eval_subexpression(
     engine_state: &EngineState,
     stack: &mut Stack,
@@ -480,7 +709,7 @@ pub fn eval_subexpression(
 ) -> Result<PipelineData, ShellError> {
     for stmt in block.stmts.iter() {
         if let Statement::Pipeline(pipeline) = stmt {
-            for elem in pipeline.expressions.iter() {
+            for (i, elem) in pipeline.expressions.iter().enumerate() {
                 match elem {
                     Expression {
                         expr: Expr::Call(call),
@@ -489,7 +718,7 @@ pub fn eval_subexpression(
                         input = eval_call(engine_state, stack, call, input)?;
                     }
                     Expression {
-                        expr: Expr::ExternalCall(name, name_span, args),
+                        expr: Expr::ExternalCall(name, name_span, args, redirections),
                         ..
                     } => {
                         input = eval_external(
@@ -498,8 +727,10 @@ pub fn eval_subexpression(
                             name,
                             name_span,
                             args,
+                            redirections,
                             input,
                             false,
+                            is_followed_by_complete(engine_state, pipeline, i),
                         )?;
                     }
 
@@ -588,16 +819,9 @@ pub fn eval_variable(
             }
         }
 
-        // since the env var PWD doesn't exist on all platforms
-        // lets just get the current directory
-        if let Ok(current_dir) = std::env::current_dir() {
-            if let Some(cwd) = current_dir.to_str() {
-                output_cols.push("cwd".into());
-                output_vals.push(Value::String {
-                    val: cwd.into(),
-                    span,
-                })
-            }
+        if let Ok(cwd) = current_dir_str(stack) {
+            output_cols.push("cwd".into());
+            output_vals.push(Value::String { val: cwd, span })
         }
 
         if let Some(home_path) = nu_path::home_dir() {
@@ -639,7 +863,7 @@ pub fn eval_variable(
                 var_names.push(String::from_utf8_lossy(var.0).to_string());
 
                 let var = engine_state.get_var(*var.1);
-                var_types.push(Value::string(var.to_string(), span));
+                var_types.push(Value::string(var.ty.to_string(), span));
             }
 
             for command in &frame.decls {
@@ -2,8 +2,10 @@ mod call_ext;
 mod documentation;
 mod env;
 mod eval;
+mod pattern_match;
 
 pub use call_ext::CallExt;
 pub use documentation::{generate_docs, get_brief_help, get_documentation, get_full_help};
 pub use env::*;
-pub use eval::{eval_block, eval_expression, eval_operator};
+pub use eval::{eval_block, eval_block_in_place, eval_expression, eval_operator};
+pub use pattern_match::{destructure, match_pattern};
@@ -0,0 +1,176 @@
+use nu_protocol::ast::{MatchPattern, Pattern};
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{ShellError, Value, VarId};
+
+use crate::eval_expression;
+
+/// Check a value against a pattern of a `match`. The variables the pattern binds are
+/// collected in `bindings`, so the caller can add them to the stack before running the arm.
+/// The guard of the pattern is not checked here, as it needs those variables to be set.
+pub fn match_pattern(
+    engine_state: &EngineState,
+    stack: &mut Stack,
+    pattern: &MatchPattern,
+    value: &Value,
+    bindings: &mut Vec<(VarId, Value)>,
+) -> Result<bool, ShellError> {
+    match &pattern.pattern {
+        Pattern::IgnoreValue => Ok(true),
+        Pattern::Variable(var_id) => {
+            bindings.push((*var_id, value.clone()));
+            Ok(true)
+        }
+        Pattern::Value(expr) => match eval_expression(engine_state, stack, expr)? {
+            Value::Range { val, .. } => Ok(val.contains(value)),
+            pattern_value => Ok(&pattern_value == value),
+        },
+        Pattern::List(items) => {
+            let vals = match value {
+                Value::List { vals, .. } => vals,
+                _ => return Ok(false),
+            };
+
+            for (idx, item) in items.iter().enumerate() {
+                match &item.pattern {
+                    // The parser only allows these at the end of the list
+                    Pattern::IgnoreRest => return Ok(true),
+                    Pattern::Rest(var_id) => {
+                        bindings.push((
+                            *var_id,
+                            Value::List {
+                                vals: vals.iter().skip(idx).cloned().collect(),
+                                span: value.span()?,
+                            },
+                        ));
+                        return Ok(true);
+                    }
+                    _ => match vals.get(idx) {
+                        Some(val) => {
+                            if !match_pattern(engine_state, stack, item, val, bindings)? {
+                                return Ok(false);
+                            }
+                        }
+                        None => return Ok(false),
+                    },
+                }
+            }
+
+            Ok(items.len() == vals.len())
+        }
+        Pattern::Record(fields) => {
+            let (cols, vals) = match value {
+                Value::Record { cols, vals, .. } => (cols, vals),
+                _ => return Ok(false),
+            };
+
+            for (name, field) in fields {
+                match cols.iter().position(|col| col == name) {
+                    Some(idx) => {
+                        if !match_pattern(engine_state, stack, field, &vals[idx], bindings)? {
+                            return Ok(false);
+                        }
+                    }
+                    None => return Ok(false),
+                }
+            }
+
+            Ok(true)
+        }
+        Pattern::Rest(_) | Pattern::IgnoreRest | Pattern::Garbage => Ok(false),
+    }
+}
+
+/// Bind the parts of a value to the variables of a destructuring `let`, eg) `let [a, b] = $list`.
+/// Unlike `match_pattern`, a value of the wrong shape is an error.
+pub fn destructure(
+    pattern: &MatchPattern,
+    value: Value,
+    bindings: &mut Vec<(VarId, Value)>,
+) -> Result<(), ShellError> {
+    let value_span = value.span()?;
+    let mismatch = |expected: String, found: String| ShellError::DestructureMismatch {
+        expected,
+        pattern_span: pattern.span,
+        found,
+        value_span,
+    };
+
+    match &pattern.pattern {
+        Pattern::Variable(var_id) => bindings.push((*var_id, value)),
+        Pattern::List(items) => {
+            let vals = match value {
+                Value::List { vals, .. } => vals,
+                x => return Err(mismatch("a list".into(), x.get_type().to_string())),
+            };
+
+            let has_rest = matches!(
+                items.last(),
+                Some(MatchPattern {
+                    pattern: Pattern::Rest(_),
+                    ..
+                })
+            );
+            let required = if has_rest {
+                items.len() - 1
+            } else {
+                items.len()
+            };
+
+            if vals.len() < required || (!has_rest && vals.len() > required) {
+                let expected = if has_rest {
+                    format!("a list of at least {} items", required)
+                } else {
+                    format!("a list of {} items", required)
+                };
+                return Err(mismatch(
+                    expected,
+                    format!("a list of {} items", vals.len()),
+                ));
+            }
+
+            let mut vals = vals.into_iter();
+            for item in items {
+                if let Pattern::Rest(var_id) = &item.pattern {
+                    bindings.push((
+                        *var_id,
+                        Value::List {
+                            vals: vals.by_ref().collect(),
+                            span: value_span,
+                        },
+                    ));
+                } else if let Some(val) = vals.next() {
+                    destructure(item, val, bindings)?;
+                }
+            }
+        }
+        Pattern::Record(fields) => {
+            let (cols, mut vals) = match value {
+                Value::Record { cols, vals, .. } => (cols, vals),
+                x => return Err(mismatch("a record".into(), x.get_type().to_string())),
+            };
+
+            for (name, field) in fields {
+                match cols.iter().position(|col| col == name) {
+                    Some(idx) => {
+                        let val = std::mem::replace(&mut vals[idx], Value::nothing(value_span));
+                        destructure(field, val, bindings)?;
+                    }
+                    None => {
+                        return Err(mismatch(
+                            format!("a field named '{}'", name),
+                            "a record without it".into(),
+                        ))
+                    }
+                }
+            }
+        }
+        Pattern::IgnoreValue => {}
+        Pattern::Value(_) | Pattern::Rest(_) | Pattern::IgnoreRest | Pattern::Garbage => {
+            return Err(ShellError::NushellFailed(
+                "unexpected pattern in destructuring".into(),
+            ))
+        }
+    }
+
+    Ok(())
+}
@@ -203,6 +203,51 @@ pub enum ParseError {
     )]
     LetNotStatement(#[label = "let statement part of a pipeline"] Span),
 
+    #[error("Assignment operations require a variable.")]
+    #[diagnostic(
+        code(nu::parser::assignment_requires_variable),
+        url(docsrs),
+        help("try assigning to a variable\neg) $x = 10")
+    )]
+    AssignmentRequiresVar(#[label = "needs to be a variable"] Span),
+
+    #[error("Assignment to an immutable variable.")]
+    #[diagnostic(
+        code(nu::parser::assignment_requires_mutable_variable),
+        url(docsrs),
+        help("declare the variable with 'mut' instead of 'let'")
+    )]
+    AssignmentRequiresMutableVar(#[label = "needs to be a mutable variable"] Span),
+
+    #[error("Capture of mutable variable.")]
+    #[diagnostic(
+        code(nu::parser::capture_of_mutable_var),
+        url(docsrs),
+        help("blocks work on a copy of the variables they use, so mutable variables can only be used directly inside of loop bodies")
+    )]
+    CaptureOfMutableVar(#[label = "block uses a mutable variable"] Span),
+
+    #[error("'{0}' used outside of a loop.")]
+    #[diagnostic(
+        code(nu::parser::not_in_loop),
+        url(docsrs),
+        help("'{0}' can only be used inside the body of 'while', 'loop' or 'for'")
+    )]
+    NotInLoop(String, #[label = "not inside of a loop"] Span),
+
+    #[error("Command does not support {1} input.")]
+    #[diagnostic(
+        code(nu::parser::input_type_mismatch),
+        url(docsrs),
+        help("'{0}' takes {2} as input")
+    )]
+    InputMismatch(
+        String,
+        Type,
+        String,
+        #[label("doesn't support {1} input")] Span,
+    ),
+
     #[error("{0}")]
     #[diagnostic()]
     LabeledError(String, String, #[label("{1}")] Span),
@@ -1,5 +1,6 @@
 use nu_protocol::ast::{
-    Block, Expr, Expression, ImportPatternMember, PathMember, Pipeline, Statement,
+    Block, Expr, Expression, ImportPatternMember, MatchPattern, PathMember, Pattern, Pipeline,
+    Statement,
 };
 use nu_protocol::{engine::StateWorkingSet, Span};
 use std::fmt::{Display, Formatter, Result};
@@ -61,6 +62,26 @@ pub fn flatten_block(working_set: &StateWorkingSet, block: &Block) -> Vec<(Span,
     output
 }
 
+pub fn flatten_pattern(
+    working_set: &StateWorkingSet,
+    match_pattern: &MatchPattern,
+) -> Vec<(Span, FlatShape)> {
+    match &match_pattern.pattern {
+        Pattern::Value(expr) => flatten_expression(working_set, expr),
+        Pattern::Variable(_) | Pattern::Rest(_) => vec![(match_pattern.span, FlatShape::Variable)],
+        Pattern::List(items) => items
+            .iter()
+            .flat_map(|item| flatten_pattern(working_set, item))
+            .collect(),
+        Pattern::Record(fields) => fields
+            .iter()
+            .flat_map(|(_, field)| flatten_pattern(working_set, field))
+            .collect(),
+        Pattern::IgnoreRest | Pattern::IgnoreValue => vec![],
+        Pattern::Garbage => vec![(match_pattern.span, FlatShape::Garbage)],
+    }
+}
+
 pub fn flatten_statement(
     working_set: &StateWorkingSet,
     stmt: &Statement,
@@ -80,7 +101,7 @@ pub fn flatten_expression(
     }
 
     match &expr.expr {
-        Expr::BinaryOp(lhs, op, rhs) => {
+        Expr::BinaryOp(lhs, op, rhs) | Expr::Assignment(lhs, op, rhs) => {
             let mut output = vec![];
             output.extend(flatten_expression(working_set, lhs));
             output.extend(flatten_expression(working_set, op));
@@ -107,10 +128,13 @@ pub fn flatten_expression(
             output.extend(args);
             output
         }
-        Expr::ExternalCall(_, name_span, args) => {
+        Expr::ExternalCall(_, name_span, args, redirections) => {
             let mut output = vec![(*name_span, FlatShape::External)];
 
-            for arg in args {
+            for arg in args
+                .iter()
+                .chain(redirections.iter().map(|(_, target)| target))
+            {
                 //output.push((*arg, FlatShape::ExternalArg));
                 match arg {
                     Expression {
@@ -245,6 +269,18 @@ pub fn flatten_expression(
             }
             output
         }
+        Expr::MatchBlock(arms) => {
+            let mut output = vec![];
+            for (pattern, expr) in arms {
+                output.extend(flatten_pattern(working_set, pattern));
+                if let Some(guard) = &pattern.guard {
+                    output.extend(flatten_expression(working_set, guard));
+                }
+                output.extend(flatten_expression(working_set, expr));
+            }
+            output
+        }
+        Expr::MatchPattern(pattern) => flatten_pattern(working_set, pattern),
         Expr::Keyword(_, span, expr) => {
             let mut output = vec![(*span, FlatShape::InternalCall)];
             output.extend(flatten_expression(working_set, expr));
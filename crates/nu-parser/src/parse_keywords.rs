@@ -1,4 +1,4 @@
-use nu_path::canonicalize;
+use nu_path::canonicalize_with;
 use nu_protocol::{
     ast::{
         Block, Call, Expr, Expression, ImportPattern, ImportPatternHead, ImportPatternMember,
@@ -8,13 +8,15 @@ use nu_protocol::{
     span, Exportable, Overlay, Span, SyntaxShape, Type, CONFIG_VARIABLE_ID,
 };
 use std::collections::{HashMap, HashSet};
+#[cfg(feature = "plugin")]
+use std::path::PathBuf;
 
 use crate::{
     lex, lite_parse,
     parser::{
         check_call, check_name, garbage, garbage_statement, parse, parse_block_expression,
-        parse_import_pattern, parse_internal_call, parse_multispan_value, parse_signature,
-        parse_string, parse_var_with_opt_type, trim_quotes,
+        parse_destructure_pattern, parse_expression, parse_import_pattern, parse_internal_call,
+        parse_multispan_value, parse_signature, parse_string, parse_var_with_opt_type, trim_quotes,
     },
     ParseError,
 };
@@ -648,7 +650,8 @@ pub fn parse_use(
                 // TODO: Do not close over when loading module from file
                 // It could be a file
                 if let Ok(module_filename) = String::from_utf8(import_pattern.head.name) {
-                    if let Ok(module_path) = canonicalize(&module_filename) {
+                    let cwd = working_set.get_cwd();
+                    if let Ok(module_path) = canonicalize_with(&module_filename, cwd) {
                         let module_name = if let Some(stem) = module_path.file_stem() {
                             stem.to_string_lossy().to_string()
                         } else {
@@ -913,10 +916,26 @@ pub fn parse_hide(
 pub fn parse_let(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
+) -> (Statement, Option<ParseError>) {
+    parse_variable_declaration(working_set, spans, b"let", false)
+}
+
+pub fn parse_mut(
+    working_set: &mut StateWorkingSet,
+    spans: &[Span],
+) -> (Statement, Option<ParseError>) {
+    parse_variable_declaration(working_set, spans, b"mut", true)
+}
+
+fn parse_variable_declaration(
+    working_set: &mut StateWorkingSet,
+    spans: &[Span],
+    keyword: &[u8],
+    mutable: bool,
 ) -> (Statement, Option<ParseError>) {
     let name = working_set.get_span_contents(spans[0]);
 
-    if name == b"let" {
+    if name == keyword {
         if let Some((span, err)) = check_name(working_set, spans) {
             return (
                 Statement::Pipeline(Pipeline::from_vec(vec![garbage(*span)])),
@@ -924,7 +943,7 @@ pub fn parse_let(
             );
         }
 
-        if let Some(decl_id) = working_set.find_decl(b"let") {
+        if let Some(decl_id) = working_set.find_decl(keyword) {
             if spans.len() >= 4 {
                 // This is a bit of by-hand parsing to get around the issue where we want to parse in the reverse order
                 // so that the var-id created by the variable isn't visible in the expression that init it
@@ -946,9 +965,21 @@ pub fn parse_let(
                             error = error.or(Some(ParseError::ExtraPositional(spans[idx + 1])));
                         }
 
-                        let mut idx = 0;
+                        let lhs = working_set.get_span_contents(spans[1]);
                         let (lvalue, err) =
-                            parse_var_with_opt_type(working_set, &spans[1..(span.0)], &mut idx);
+                            if span.0 == 2 && (lhs.starts_with(b"[") || lhs.starts_with(b"{")) {
+                                // Destructuring, eg) `let [a, b] = $list` or `let {name} = $record`
+                                let ty = rvalue.ty.clone();
+                                parse_destructure_pattern(working_set, spans[1], &ty, mutable)
+                            } else {
+                                let mut idx = 0;
+                                parse_var_with_opt_type(
+                                    working_set,
+                                    &spans[1..(span.0)],
+                                    &mut idx,
+                                    mutable,
+                                )
+                            };
                         error = error.or(err);
 
                         let var_id = lvalue.as_var();
@@ -998,12 +1029,30 @@ pub fn parse_let(
     (
         garbage_statement(spans),
         Some(ParseError::UnknownState(
-            "internal error: let statement unparseable".into(),
+            format!(
+                "internal error: {} statement unparseable",
+                String::from_utf8_lossy(keyword)
+            ),
             span(spans),
         )),
     )
 }
 
+pub fn parse_loop_control(
+    working_set: &mut StateWorkingSet,
+    spans: &[Span],
+) -> (Statement, Option<ParseError>) {
+    let name = String::from_utf8_lossy(working_set.get_span_contents(spans[0])).to_string();
+
+    let (expr, mut error) = parse_expression(working_set, spans, true);
+
+    if working_set.loop_depth == 0 {
+        error = error.or(Some(ParseError::NotInLoop(name, spans[0])));
+    }
+
+    (Statement::Pipeline(Pipeline::from_vec(vec![expr])), error)
+}
+
 pub fn parse_source(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
@@ -1022,7 +1071,8 @@ pub fn parse_source(
             if spans.len() >= 2 {
                 let name_expr = working_set.get_span_contents(spans[1]);
                 if let Ok(filename) = String::from_utf8(name_expr.to_vec()) {
-                    if let Ok(path) = canonicalize(&filename) {
+                    let cwd = working_set.get_cwd();
+                    if let Ok(path) = canonicalize_with(&filename, cwd) {
                         if let Ok(contents) = std::fs::read(&path) {
                             // This will load the defs from the file into the
                             // working set, if it was a successful parse.
@@ -1106,7 +1156,7 @@ pub fn parse_register(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
 ) -> (Statement, Option<ParseError>) {
-    use nu_plugin::{get_signature, EncodingType, PluginDeclaration};
+    use nu_plugin::{get_cached_signature, get_signature, EncodingType, PluginDeclaration};
     use nu_protocol::Signature;
 
     // Checking that the function is used with the correct name
@@ -1157,6 +1207,9 @@ pub fn parse_register(
         }
     };
 
+    // Relative paths are resolved against `$env.PWD`
+    let cwd = working_set.get_cwd();
+
     // Extracting the required arguments from the call and keeping them together in a tuple
     // The ? operator is not used because the error has to be kept to be printed in the shell
     // For that reason the values are kept in a result that will be passed at the end of this call
@@ -1168,7 +1221,8 @@ pub fn parse_register(
             String::from_utf8(name_expr.to_vec())
                 .map_err(|_| ParseError::NonUtf8(expr.span))
                 .and_then(|name| {
-                    canonicalize(&name).map_err(|_| ParseError::FileNotFound(name, expr.span))
+                    canonicalize_with(&name, &cwd)
+                        .map_err(|_| ParseError::FileNotFound(name, expr.span))
                 })
                 .and_then(|path| {
                     if path.exists() & path.is_file() {
@@ -1187,7 +1241,7 @@ pub fn parse_register(
                             ParseError::IncorrectValue(
                                 "wrong encoding".into(),
                                 expr.span,
-                                "Encodings available: capnp and json".into(),
+                                "Encodings available: capnp, json and msgpack".into(),
                             )
                         })
                 })
@@ -1215,7 +1269,8 @@ pub fn parse_register(
         String::from_utf8(shell_expr.to_vec())
             .map_err(|_| ParseError::NonUtf8(expr.span))
             .and_then(|name| {
-                canonicalize(&name).map_err(|_| ParseError::FileNotFound(name, expr.span))
+                canonicalize_with(&name, &cwd)
+                    .map_err(|_| ParseError::FileNotFound(name, expr.span))
             })
             .and_then(|path| {
                 if path.exists() & path.is_file() {
@@ -1253,7 +1308,14 @@ pub fn parse_register(
             })
         }),
         None => arguments.and_then(|(path, encoding)| {
-            get_signature(path.as_path(), &encoding, &shell)
+            // Signatures are cached next to the plugin file, the plugin is only started
+            // if its file changed since its signatures were cached
+            let signatures = match working_set.permanent_state.plugin_cache_file() {
+                Some(cache_file) => get_cached_signature(&path, &encoding, &shell, &cache_file),
+                None => get_signature(path.as_path(), &encoding, &shell),
+            };
+
+            signatures
                 .map_err(|err| {
                     ParseError::LabeledError(
                         "Error getting signatures".into(),
@@ -1291,3 +1353,81 @@ pub fn parse_register(
         error,
     )
 }
+
+// `plugin reload` asks the plugins for their signatures while it is parsed, like `register`
+// does, so the reloaded commands replace the old ones in the scope. The command itself only
+// lists the reloaded plugins
+#[cfg(feature = "plugin")]
+pub fn parse_plugin_reload(
+    working_set: &mut StateWorkingSet,
+    spans: &[Span],
+) -> (Statement, Option<ParseError>) {
+    use nu_plugin::{get_signature, reload_signature, EncodingType, PluginDeclaration};
+
+    let (expr, err) = parse_expression(working_set, spans, true);
+    let call = match &expr.expr {
+        Expr::Call(call) if err.is_none() && !call.has_flag("help") => call,
+        _ => return (Statement::Pipeline(Pipeline::from_vec(vec![expr])), err),
+    };
+
+    let name = call.positional.get(0).and_then(|expr| match &expr.expr {
+        Expr::String(name) => Some(name.clone()),
+        _ => None,
+    });
+
+    let plugins: Vec<(PathBuf, String, Option<PathBuf>)> = working_set
+        .permanent_state
+        .plugins()
+        .into_iter()
+        .filter(|(path, ..)| match &name {
+            Some(name) => path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy() == name.as_str())
+                .unwrap_or(false),
+            None => true,
+        })
+        .map(|(path, encoding, shell)| (path.clone(), encoding.to_string(), shell.clone()))
+        .collect();
+
+    let cache_file = working_set.permanent_state.plugin_cache_file();
+    let mut error = None;
+
+    for (path, encoding, shell) in plugins {
+        let encoding = match EncodingType::try_from_bytes(encoding.as_bytes()) {
+            Some(encoding) => encoding,
+            None => continue,
+        };
+
+        let signatures = match &cache_file {
+            Some(cache_file) => reload_signature(&path, &encoding, &shell, cache_file),
+            None => get_signature(&path, &encoding, &shell),
+        };
+
+        match signatures {
+            Ok(signatures) => {
+                for signature in signatures {
+                    let plugin_decl = PluginDeclaration::new(
+                        path.clone(),
+                        signature,
+                        encoding.clone(),
+                        shell.clone(),
+                    );
+                    working_set.add_decl(Box::new(plugin_decl));
+                }
+
+                working_set.mark_plugins_file_dirty();
+            }
+            Err(err) => {
+                error = error.or_else(|| {
+                    Some(ParseError::LabeledError(
+                        "Error getting signatures".into(),
+                        err.to_string(),
+                        expr.span,
+                    ))
+                })
+            }
+        }
+    }
+
+    (Statement::Pipeline(Pipeline::from_vec(vec![expr])), error)
+}
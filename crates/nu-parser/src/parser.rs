@@ -1,15 +1,15 @@
 use crate::{
     lex, lite_parse,
     parse_keywords::parse_source,
-    type_check::{math_result_type, type_compatible},
+    type_check::{call_output_type, check_call_input, math_result_type, type_compatible},
     LiteBlock, ParseError, Token, TokenContents,
 };
 
 use nu_protocol::{
     ast::{
         Block, Call, CellPath, Expr, Expression, FullCellPath, ImportPattern, ImportPatternHead,
-        ImportPatternMember, Operator, PathMember, Pipeline, RangeInclusion, RangeOperator,
-        Statement,
+        ImportPatternMember, MatchPattern, Operator, PathMember, Pattern, Pipeline, RangeInclusion,
+        RangeOperator, Redirection, Statement,
     },
     engine::StateWorkingSet,
     span, Flag, PositionalArg, Signature, Span, Spanned, SyntaxShape, Type, Unit, VarId,
@@ -17,13 +17,14 @@ use nu_protocol::{
 };
 
 use crate::parse_keywords::{
-    parse_alias, parse_def, parse_def_predecl, parse_hide, parse_let, parse_module, parse_use,
+    parse_alias, parse_def, parse_def_predecl, parse_hide, parse_let, parse_loop_control,
+    parse_module, parse_mut, parse_use,
 };
 
 use std::collections::HashSet;
 
 #[cfg(feature = "plugin")]
-use crate::parse_keywords::parse_register;
+use crate::parse_keywords::{parse_plugin_reload, parse_register};
 
 #[derive(Debug, Clone)]
 pub enum Import {}
@@ -163,29 +164,49 @@ pub fn parse_external_call(
     spans: &[Span],
 ) -> (Expression, Option<ParseError>) {
     let mut args = vec![];
+    let mut redirections = vec![];
     let name_span = spans[0];
     let name = String::from_utf8_lossy(working_set.get_span_contents(name_span)).to_string();
     let mut error = None;
 
-    for span in &spans[1..] {
-        let contents = working_set.get_span_contents(*span);
+    let mut spans_idx = 1;
+    while spans_idx < spans.len() {
+        let span = spans[spans_idx];
+        let contents = working_set.get_span_contents(span);
+
+        let redirection = match contents {
+            b"out>" => Some(Redirection::Stdout),
+            b"err>" => Some(Redirection::Stderr),
+            b"out+err>" => Some(Redirection::StdoutAndStderr),
+            _ => None,
+        };
 
-        if contents.starts_with(b"$") || contents.starts_with(b"(") {
-            let (arg, err) = parse_expression(working_set, &[*span], true);
+        if let Some(redirection) = redirection {
+            // The next argument is the file the output is sent to
+            spans_idx += 1;
+            if let Some(target_span) = spans.get(spans_idx) {
+                let (target, err) = parse_external_arg(working_set, *target_span);
+                error = error.or(err);
+                redirections.push((redirection, target));
+            } else {
+                error = error.or_else(|| {
+                    Some(ParseError::MissingPositional(
+                        "redirection target".into(),
+                        span,
+                    ))
+                });
+            }
+        } else {
+            let (arg, err) = parse_external_arg(working_set, span);
             error = error.or(err);
             args.push(arg);
-        } else {
-            args.push(Expression {
-                expr: Expr::String(String::from_utf8_lossy(contents).to_string()),
-                span: *span,
-                ty: Type::String,
-                custom_completion: None,
-            })
         }
+
+        spans_idx += 1;
     }
     (
         Expression {
-            expr: Expr::ExternalCall(name, name_span, args),
+            expr: Expr::ExternalCall(name, name_span, args, redirections),
             span: span(spans),
             ty: Type::Unknown,
             custom_completion: None,
@@ -194,6 +215,39 @@ pub fn parse_external_call(
     )
 }
 
+fn parse_external_arg(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+) -> (Expression, Option<ParseError>) {
+    let contents = working_set.get_span_contents(span);
+
+    if contents.starts_with(b"$") || contents.starts_with(b"(") {
+        parse_expression(working_set, &[span], true)
+    } else if is_bare_glob(contents) {
+        // Bare globs are expanded when the external runs, quoted ones stay literal
+        parse_glob_pattern(working_set, span)
+    } else {
+        (
+            Expression {
+                expr: Expr::String(String::from_utf8_lossy(contents).to_string()),
+                span,
+                ty: Type::String,
+                custom_completion: None,
+            },
+            None,
+        )
+    }
+}
+
+// Only `*` and `[` make an external argument a glob. A `?` is too common in other arguments, eg
+// the query of a url, and urls are never globs
+fn is_bare_glob(contents: &[u8]) -> bool {
+    !matches!(contents.first(), Some(b'"') | Some(b'\'') | Some(b'`'))
+        && !contents.windows(3).any(|w| w == b"://")
+        && !contents.contains(&b'?')
+        && contents.iter().any(|b| matches!(b, b'*' | b'['))
+}
+
 fn parse_long_flag(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
@@ -417,7 +471,7 @@ pub fn parse_multispan_value(
 
     match shape {
         SyntaxShape::VarWithOptType => {
-            let (arg, err) = parse_var_with_opt_type(working_set, spans, spans_idx);
+            let (arg, err) = parse_var_with_opt_type(working_set, spans, spans_idx, false);
             error = error.or(err);
 
             (arg, error)
@@ -514,6 +568,8 @@ pub fn parse_internal_call(
     call.head = command_span;
 
     let signature = working_set.get_decl(decl_id).signature();
+    let in_place_blocks =
+        std::mem::replace(&mut working_set.in_place_blocks, signature.runs_in_place);
 
     if signature.creates_scope {
         working_set.enter_scope();
@@ -589,6 +645,11 @@ pub fn parse_internal_call(
 
         // Parse a positional arg if there is one
         if let Some(positional) = signature.get_positional(positional_idx) {
+            // Rest arguments are values given to the blocks, so they don't run in place
+            working_set.in_place_blocks = signature.runs_in_place
+                && positional_idx
+                    < signature.required_positional.len() + signature.optional_positional.len();
+
             let end = calculate_end_span(working_set, &signature, spans, spans_idx, positional_idx);
 
             // println!(
@@ -608,12 +669,17 @@ pub fn parse_internal_call(
             }
 
             let orig_idx = spans_idx;
-            let (arg, err) = parse_multispan_value(
-                working_set,
-                &spans[..end],
-                &mut spans_idx,
-                &positional.shape,
-            );
+            let (arg, err) =
+                if signature.is_loop && matches!(positional.shape, SyntaxShape::Block(_)) {
+                    parse_loop_body(working_set, &positional.shape, spans[spans_idx])
+                } else {
+                    parse_multispan_value(
+                        working_set,
+                        &spans[..end],
+                        &mut spans_idx,
+                        &positional.shape,
+                    )
+                };
             error = error.or(err);
 
             let arg = if !type_compatible(&positional.shape.to_type(), &arg.ty) {
@@ -647,6 +713,8 @@ pub fn parse_internal_call(
         working_set.exit_scope();
     }
 
+    working_set.in_place_blocks = in_place_blocks;
+
     // FIXME: output type unknown
     (Box::new(call), error)
 }
@@ -762,11 +830,12 @@ pub fn parse_call(
             &spans[pos..],
             decl_id,
         );
+        let ty = call_output_type(&working_set.get_decl(decl_id).signature());
         (
             Expression {
                 expr: Expr::Call(call),
                 span: span(spans),
-                ty: Type::Unknown, // FIXME: calls should have known output types
+                ty,
                 custom_completion: None,
             },
             err,
@@ -1267,7 +1336,7 @@ pub fn parse_variable_expr(
                 Expression {
                     expr: Expr::Var(id),
                     span,
-                    ty: working_set.get_variable(id).clone(),
+                    ty: working_set.get_variable(id).ty.clone(),
                     custom_completion: None,
                 },
                 None,
@@ -1471,11 +1540,10 @@ pub fn parse_filepath(
     let bytes = trim_quotes(bytes);
 
     if let Ok(token) = String::from_utf8(bytes.into()) {
-        let filepath = nu_path::expand_path(token);
-        let filepath = filepath.to_string_lossy().to_string();
+        // Expanded against `$env.PWD` when evaluated, which may be changed by then
         (
             Expression {
-                expr: Expr::Filepath(filepath),
+                expr: Expr::Filepath(token),
                 span,
                 ty: Type::String,
                 custom_completion: None,
@@ -1685,12 +1753,10 @@ pub fn parse_glob_pattern(
     let bytes = trim_quotes(bytes);
 
     if let Ok(token) = String::from_utf8(bytes.into()) {
-        let filepath = nu_path::expand_path(token);
-        let filepath = filepath.to_string_lossy().to_string();
-
+        // Expanded against `$env.PWD` when evaluated, which may be changed by then
         (
             Expression {
-                expr: Expr::GlobPattern(filepath),
+                expr: Expr::GlobPattern(token),
                 span,
                 ty: Type::String,
                 custom_completion: None,
@@ -1801,6 +1867,7 @@ pub fn parse_shape_name(
         b"signature" => SyntaxShape::Signature,
         b"expr" => SyntaxShape::Expression,
         b"bool" => SyntaxShape::Boolean,
+        b"record" => SyntaxShape::Record,
         _ => return (SyntaxShape::Any, Some(ParseError::UnknownType(span))),
     };
 
@@ -1939,6 +2006,7 @@ pub fn parse_var_with_opt_type(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
     spans_idx: &mut usize,
+    mutable: bool,
 ) -> (Expression, Option<ParseError>) {
     let bytes = working_set.get_span_contents(spans[*spans_idx]).to_vec();
 
@@ -1957,7 +2025,12 @@ pub fn parse_var_with_opt_type(
 
             let ty = parse_type(working_set, type_bytes);
 
-            let id = working_set.add_variable(bytes[0..(bytes.len() - 1)].to_vec(), ty.clone());
+            let id = working_set.add_variable(
+                bytes[0..(bytes.len() - 1)].to_vec(),
+                spans[*spans_idx - 1],
+                ty.clone(),
+                mutable,
+            );
 
             (
                 Expression {
@@ -1969,7 +2042,12 @@ pub fn parse_var_with_opt_type(
                 None,
             )
         } else {
-            let id = working_set.add_variable(bytes[0..(bytes.len() - 1)].to_vec(), Type::Unknown);
+            let id = working_set.add_variable(
+                bytes[0..(bytes.len() - 1)].to_vec(),
+                spans[*spans_idx],
+                Type::Unknown,
+                mutable,
+            );
             (
                 Expression {
                     expr: Expr::VarDecl(id),
@@ -1991,7 +2069,7 @@ pub fn parse_var_with_opt_type(
             None,
         )
     } else {
-        let id = working_set.add_variable(bytes, Type::Unknown);
+        let id = working_set.add_variable(bytes, spans[*spans_idx], Type::Unknown, mutable);
 
         (
             Expression {
@@ -2005,6 +2083,201 @@ pub fn parse_var_with_opt_type(
     }
 }
 
+/// Parse the left-hand side of a destructuring `let`, eg) `[a, b, ...rest]` or `{name, age}`.
+/// The type of the right-hand side, if known, gives the types of the new variables.
+pub fn parse_destructure_pattern(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+    ty: &Type,
+    mutable: bool,
+) -> (Expression, Option<ParseError>) {
+    let (pattern, error) = parse_destructure_item(working_set, span, ty, mutable);
+
+    (
+        Expression {
+            expr: Expr::MatchPattern(Box::new(pattern)),
+            span,
+            ty: ty.clone(),
+            custom_completion: None,
+        },
+        error,
+    )
+}
+
+fn parse_destructure_item(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+    ty: &Type,
+    mutable: bool,
+) -> (MatchPattern, Option<ParseError>) {
+    let bytes = working_set.get_span_contents(span).to_vec();
+    let mut error = None;
+
+    let pattern = if bytes == b"_" {
+        Pattern::IgnoreValue
+    } else if bytes.starts_with(b"[") || bytes.starts_with(b"{") {
+        let is_list = bytes.starts_with(b"[");
+        let expected_ty = if is_list {
+            Type::List(Box::new(Type::Unknown))
+        } else {
+            Type::Record(vec![])
+        };
+
+        match (is_list, ty) {
+            (true, Type::List(_) | Type::Table | Type::Unknown)
+            | (false, Type::Record(_) | Type::Unknown) => {}
+            _ => {
+                error =
+                    error.or_else(|| Some(ParseError::TypeMismatch(expected_ty, ty.clone(), span)))
+            }
+        }
+
+        let close = if is_list { b"]" } else { b"}" };
+        let start = span.start + 1;
+        let mut end = span.end;
+
+        if bytes.ends_with(close) {
+            end -= 1;
+        } else {
+            error = error.or_else(|| {
+                Some(ParseError::Unclosed(
+                    String::from_utf8_lossy(close).into(),
+                    Span { start: end, end },
+                ))
+            });
+        }
+
+        let source = working_set.get_span_contents(Span { start, end });
+        let special_tokens: &[u8] = if is_list { &[] } else { &[b':'] };
+        let (tokens, err) = lex(source, start, &[b'\n', b'\r', b','], special_tokens, true);
+        error = error.or(err);
+
+        if is_list {
+            let (items, err) = parse_destructure_list(working_set, &tokens, ty, mutable);
+            error = error.or(err);
+            Pattern::List(items)
+        } else {
+            let (fields, err) = parse_destructure_record(working_set, &tokens, ty, mutable);
+            error = error.or(err);
+            Pattern::Record(fields)
+        }
+    } else if is_variable(&bytes) {
+        Pattern::Variable(working_set.add_variable(bytes, span, ty.clone(), mutable))
+    } else {
+        error = error.or(Some(ParseError::VariableNotValid(span)));
+        Pattern::Garbage
+    };
+
+    (
+        MatchPattern {
+            pattern,
+            guard: None,
+            span,
+        },
+        error,
+    )
+}
+
+fn parse_destructure_list(
+    working_set: &mut StateWorkingSet,
+    tokens: &[Token],
+    ty: &Type,
+    mutable: bool,
+) -> (Vec<MatchPattern>, Option<ParseError>) {
+    let mut error = None;
+    let item_ty = match ty {
+        Type::List(item_ty) => *item_ty.clone(),
+        _ => Type::Unknown,
+    };
+
+    let mut items = vec![];
+    for (idx, token) in tokens.iter().enumerate() {
+        let contents = working_set.get_span_contents(token.span);
+
+        if let Some(name) = contents.strip_prefix(b"...") {
+            if idx != tokens.len() - 1 {
+                error = error.or_else(|| {
+                    Some(ParseError::Expected(
+                        "rest variable at the end of the list".into(),
+                        token.span,
+                    ))
+                });
+            }
+
+            let pattern = if is_variable(name) {
+                let rest_ty = Type::List(Box::new(item_ty.clone()));
+                Pattern::Rest(working_set.add_variable(name.to_vec(), token.span, rest_ty, mutable))
+            } else {
+                error = error.or(Some(ParseError::VariableNotValid(token.span)));
+                Pattern::Garbage
+            };
+
+            items.push(MatchPattern {
+                pattern,
+                guard: None,
+                span: token.span,
+            });
+        } else {
+            let (item, err) = parse_destructure_item(working_set, token.span, &item_ty, mutable);
+            error = error.or(err);
+            items.push(item);
+        }
+    }
+
+    (items, error)
+}
+
+fn parse_destructure_record(
+    working_set: &mut StateWorkingSet,
+    tokens: &[Token],
+    ty: &Type,
+    mutable: bool,
+) -> (Vec<(String, MatchPattern)>, Option<ParseError>) {
+    let mut error = None;
+    let mut fields = vec![];
+    let mut idx = 0;
+
+    while idx < tokens.len() {
+        let key_span = tokens[idx].span;
+        let key = String::from_utf8_lossy(trim_quotes(working_set.get_span_contents(key_span)))
+            .to_string();
+
+        let field_ty = match ty {
+            Type::Record(field_types) => field_types
+                .iter()
+                .find(|(name, _)| name == &key)
+                .map(|(_, ty)| ty.clone())
+                .unwrap_or(Type::Unknown),
+            _ => Type::Unknown,
+        };
+
+        let has_colon = matches!(
+            tokens.get(idx + 1),
+            Some(token) if working_set.get_span_contents(token.span) == b":"
+        );
+
+        if !has_colon {
+            // Shorthand for binding a field to a variable of the same name, eg) `{name}`
+            let (pattern, err) = parse_destructure_item(working_set, key_span, &field_ty, mutable);
+            error = error.or(err);
+            fields.push((key.trim_start_matches('$').to_string(), pattern));
+            idx += 1;
+        } else if let Some(value) = tokens.get(idx + 2) {
+            let (pattern, err) =
+                parse_destructure_item(working_set, value.span, &field_ty, mutable);
+            error = error.or(err);
+            fields.push((key, pattern));
+            idx += 3;
+        } else {
+            error =
+                error.or_else(|| Some(ParseError::Expected("field: variable".into(), key_span)));
+            break;
+        }
+    }
+
+    (fields, error)
+}
+
 pub fn expand_to_cell_path(
     working_set: &mut StateWorkingSet,
     expression: &mut Expression,
@@ -2027,8 +2300,8 @@ pub fn parse_row_condition(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
 ) -> (Expression, Option<ParseError>) {
-    let var_id = working_set.add_variable(b"$it".to_vec(), Type::Unknown);
-    let (expression, err) = parse_math_expression(working_set, spans, Some(var_id));
+    let var_id = working_set.add_variable(b"$it".to_vec(), span(spans), Type::Unknown, false);
+    let (expression, mut error) = parse_math_expression(working_set, spans, Some(var_id));
     let span = span(spans);
 
     let block_id = match expression.expr {
@@ -2046,10 +2319,12 @@ pub fn parse_row_condition(
                 desc: "row condition".into(),
                 shape: SyntaxShape::Any,
                 var_id: Some(var_id),
+                default_value: None,
             });
 
             let mut seen = vec![];
             let captures = find_captures_in_block(working_set, &block, &mut seen);
+            error = error.or_else(|| check_mutable_captures(working_set, &captures, span));
 
             block.captures = captures;
 
@@ -2064,7 +2339,7 @@ pub fn parse_row_condition(
             expr: Expr::RowCondition(block_id),
             custom_completion: None,
         },
-        err,
+        error,
     )
 }
 
@@ -2119,6 +2394,7 @@ pub fn parse_signature_helper(
     enum ParseMode {
         ArgMode,
         TypeMode,
+        DefaultValueMode,
     }
 
     enum Arg {
@@ -2129,7 +2405,13 @@ pub fn parse_signature_helper(
     let mut error = None;
     let source = working_set.get_span_contents(span);
 
-    let (output, err) = lex(source, span.start, &[b'\n', b'\r', b','], &[b':'], false);
+    let (output, err) = lex(
+        source,
+        span.start,
+        &[b'\n', b'\r', b','],
+        &[b':', b'='],
+        false,
+    );
     error = error.or(err);
 
     let mut args: Vec<Arg> = vec![];
@@ -2155,6 +2437,26 @@ pub fn parse_signature_helper(
                             error =
                                 error.or_else(|| Some(ParseError::Expected("type".into(), span)));
                         }
+                        ParseMode::DefaultValueMode => {
+                            error = error.or_else(|| {
+                                Some(ParseError::Expected("default value".into(), span))
+                            });
+                        }
+                    }
+                } else if contents == b"=" {
+                    match parse_mode {
+                        ParseMode::ArgMode => {
+                            parse_mode = ParseMode::DefaultValueMode;
+                        }
+                        ParseMode::TypeMode => {
+                            error =
+                                error.or_else(|| Some(ParseError::Expected("type".into(), span)));
+                        }
+                        ParseMode::DefaultValueMode => {
+                            error = error.or_else(|| {
+                                Some(ParseError::Expected("default value".into(), span))
+                            });
+                        }
                     }
                 } else {
                     match parse_mode {
@@ -2166,7 +2468,12 @@ pub fn parse_signature_helper(
 
                                 let long = String::from_utf8_lossy(&flags[0][2..]).to_string();
                                 let variable_name = flags[0][2..].to_vec();
-                                let var_id = working_set.add_variable(variable_name, Type::Unknown);
+                                let var_id = working_set.add_variable(
+                                    variable_name,
+                                    span,
+                                    Type::Unknown,
+                                    false,
+                                );
 
                                 if flags.len() == 1 {
                                     args.push(Arg::Flag(Flag {
@@ -2176,6 +2483,7 @@ pub fn parse_signature_helper(
                                         short: None,
                                         required: false,
                                         var_id: Some(var_id),
+                                        default_value: None,
                                     }));
                                 } else {
                                     let short_flag = &flags[1];
@@ -2195,8 +2503,12 @@ pub fn parse_signature_helper(
                                     let chars: Vec<char> = short_flag.chars().collect();
                                     let long = String::from_utf8_lossy(&flags[0][2..]).to_string();
                                     let variable_name = flags[0][2..].to_vec();
-                                    let var_id =
-                                        working_set.add_variable(variable_name, Type::Unknown);
+                                    let var_id = working_set.add_variable(
+                                        variable_name,
+                                        span,
+                                        Type::Unknown,
+                                        false,
+                                    );
 
                                     if chars.len() == 1 {
                                         args.push(Arg::Flag(Flag {
@@ -2206,6 +2518,7 @@ pub fn parse_signature_helper(
                                             short: Some(chars[0]),
                                             required: false,
                                             var_id: Some(var_id),
+                                            default_value: None,
                                         }));
                                     } else {
                                         error = error.or_else(|| {
@@ -2232,13 +2545,18 @@ pub fn parse_signature_helper(
                                         short: None,
                                         required: false,
                                         var_id: None,
+                                        default_value: None,
                                     }));
                                 } else {
                                     let mut encoded_var_name = vec![0u8; 4];
                                     let len = chars[0].encode_utf8(&mut encoded_var_name).len();
                                     let variable_name = encoded_var_name[0..len].to_vec();
-                                    let var_id =
-                                        working_set.add_variable(variable_name, Type::Unknown);
+                                    let var_id = working_set.add_variable(
+                                        variable_name,
+                                        span,
+                                        Type::Unknown,
+                                        false,
+                                    );
 
                                     args.push(Arg::Flag(Flag {
                                         arg: None,
@@ -2247,6 +2565,7 @@ pub fn parse_signature_helper(
                                         short: Some(chars[0]),
                                         required: false,
                                         var_id: Some(var_id),
+                                        default_value: None,
                                     }));
                                 }
                             } else if contents.starts_with(b"(-") {
@@ -2296,7 +2615,8 @@ pub fn parse_signature_helper(
                                 let contents: Vec<_> = contents[..(contents.len() - 1)].into();
                                 let name = String::from_utf8_lossy(&contents).to_string();
 
-                                let var_id = working_set.add_variable(contents, Type::Unknown);
+                                let var_id =
+                                    working_set.add_variable(contents, span, Type::Unknown, false);
 
                                 // Positional arg, optional
                                 args.push(Arg::Positional(
@@ -2305,6 +2625,7 @@ pub fn parse_signature_helper(
                                         name,
                                         shape: SyntaxShape::Any,
                                         var_id: Some(var_id),
+                                        default_value: None,
                                     },
                                     false,
                                 ))
@@ -2312,7 +2633,12 @@ pub fn parse_signature_helper(
                                 let name = String::from_utf8_lossy(contents).to_string();
                                 let contents_vec: Vec<u8> = contents.to_vec();
 
-                                let var_id = working_set.add_variable(contents_vec, Type::Unknown);
+                                let var_id = working_set.add_variable(
+                                    contents_vec,
+                                    span,
+                                    Type::Unknown,
+                                    false,
+                                );
 
                                 if rest_arg.is_none() {
                                     rest_arg = Some(Arg::Positional(
@@ -2321,6 +2647,7 @@ pub fn parse_signature_helper(
                                             name,
                                             shape: SyntaxShape::Any,
                                             var_id: Some(var_id),
+                                            default_value: None,
                                         },
                                         false,
                                     ));
@@ -2331,7 +2658,12 @@ pub fn parse_signature_helper(
                                 let name = String::from_utf8_lossy(contents).to_string();
                                 let contents_vec = contents.to_vec();
 
-                                let var_id = working_set.add_variable(contents_vec, Type::Unknown);
+                                let var_id = working_set.add_variable(
+                                    contents_vec,
+                                    span,
+                                    Type::Unknown,
+                                    false,
+                                );
 
                                 // Positional arg, required
                                 args.push(Arg::Positional(
@@ -2340,6 +2672,7 @@ pub fn parse_signature_helper(
                                         name,
                                         shape: SyntaxShape::Any,
                                         var_id: Some(var_id),
+                                        default_value: None,
                                     },
                                     true,
                                 ))
@@ -2367,6 +2700,57 @@ pub fn parse_signature_helper(
                             }
                             parse_mode = ParseMode::ArgMode;
                         }
+                        ParseMode::DefaultValueMode => {
+                            if let Some(last) = args.last_mut() {
+                                let shape = match last {
+                                    Arg::Positional(PositionalArg { shape, .. }, ..) => {
+                                        shape.clone()
+                                    }
+                                    Arg::Flag(Flag { arg, .. }) => match arg {
+                                        Some(arg) => arg.clone(),
+                                        None => {
+                                            // Switches are false when missing, they can't have a default
+                                            error = error.or_else(|| {
+                                                Some(ParseError::Expected(
+                                                    "flag with a type".into(),
+                                                    span,
+                                                ))
+                                            });
+                                            SyntaxShape::Any
+                                        }
+                                    },
+                                };
+
+                                let (expr, err) = parse_value(working_set, span, &shape);
+                                error = error.or(err);
+
+                                if !type_compatible(&shape.to_type(), &expr.ty) {
+                                    error = error.or_else(|| {
+                                        Some(ParseError::TypeMismatch(
+                                            shape.to_type(),
+                                            expr.ty.clone(),
+                                            expr.span,
+                                        ))
+                                    });
+                                }
+
+                                let block = Block::from(
+                                    vec![Statement::Pipeline(Pipeline::from_vec(vec![expr]))]
+                                        .into_iter(),
+                                );
+                                let block_id = working_set.add_block(block);
+
+                                match last {
+                                    Arg::Positional(positional, required) => {
+                                        // A positional with a default can be left out
+                                        positional.default_value = Some(block_id);
+                                        *required = false;
+                                    }
+                                    Arg::Flag(flag) => flag.default_value = Some(block_id),
+                                }
+                            }
+                            parse_mode = ParseMode::ArgMode;
+                        }
                     }
                 }
             }
@@ -2620,6 +3004,41 @@ pub fn parse_block_expression(
     working_set: &mut StateWorkingSet,
     shape: &SyntaxShape,
     span: Span,
+) -> (Expression, Option<ParseError>) {
+    // Only blocks that run in place, like the branches of `if`, stay inside of the enclosing loop
+    // and give their assignments back to the caller. Others are run as closures, where `break`
+    // and `continue` can't reach the loop and mutable variables would only be copies
+    let in_place = working_set.in_place_blocks;
+    let loop_depth = if in_place { working_set.loop_depth } else { 0 };
+    let loop_depth = std::mem::replace(&mut working_set.loop_depth, loop_depth);
+    let in_place_blocks = std::mem::take(&mut working_set.in_place_blocks);
+
+    let output = parse_block_expression_helper(working_set, shape, span, !in_place);
+
+    working_set.in_place_blocks = in_place_blocks;
+    working_set.loop_depth = loop_depth;
+
+    output
+}
+
+// Loop bodies run in the caller's stack, so unlike other blocks they can use mutable variables
+fn parse_loop_body(
+    working_set: &mut StateWorkingSet,
+    shape: &SyntaxShape,
+    span: Span,
+) -> (Expression, Option<ParseError>) {
+    working_set.loop_depth += 1;
+    let output = parse_block_expression_helper(working_set, shape, span, false);
+    working_set.loop_depth -= 1;
+
+    output
+}
+
+fn parse_block_expression_helper(
+    working_set: &mut StateWorkingSet,
+    shape: &SyntaxShape,
+    span: Span,
+    check_captures: bool,
 ) -> (Expression, Option<ParseError>) {
     let bytes = working_set.get_span_contents(span);
     let mut error = None;
@@ -2700,11 +3119,12 @@ pub fn parse_block_expression(
     if let SyntaxShape::Block(Some(v)) = shape {
         if signature.is_none() && v.len() == 1 {
             // We'll assume there's an `$it` present
-            let var_id = working_set.add_variable(b"$it".to_vec(), Type::Unknown);
+            let var_id = working_set.add_variable(b"$it".to_vec(), span, Type::Unknown, false);
 
             let mut new_sigature = Signature::new("");
             new_sigature.required_positional.push(PositionalArg {
                 var_id: Some(var_id),
+                default_value: None,
                 name: "$it".into(),
                 desc: String::new(),
                 shape: SyntaxShape::Any,
@@ -2726,6 +3146,7 @@ pub fn parse_block_expression(
             let mut signature = Signature::new("");
             signature.required_positional.push(PositionalArg {
                 var_id: Some(*var_id),
+                default_value: None,
                 name: "$it".into(),
                 desc: String::new(),
                 shape: SyntaxShape::Any,
@@ -2736,6 +3157,9 @@ pub fn parse_block_expression(
 
     let mut seen = vec![];
     let captures = find_captures_in_block(working_set, &output, &mut seen);
+    if check_captures {
+        error = error.or_else(|| check_mutable_captures(working_set, &captures, span));
+    }
 
     output.captures = captures;
 
@@ -2754,6 +3178,317 @@ pub fn parse_block_expression(
     )
 }
 
+pub fn parse_match_block_expression(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+) -> (Expression, Option<ParseError>) {
+    let bytes = working_set.get_span_contents(span);
+    let mut error = None;
+
+    let mut start = span.start;
+    let mut end = span.end;
+
+    if bytes.starts_with(b"{") {
+        start += 1;
+    } else {
+        return (
+            garbage(span),
+            Some(ParseError::Expected("match block".into(), span)),
+        );
+    }
+    if bytes.ends_with(b"}") {
+        end -= 1;
+    } else {
+        error = error.or_else(|| Some(ParseError::Unclosed("}".into(), Span { start: end, end })));
+    }
+
+    let inner_span = Span { start, end };
+    let source = working_set.get_span_contents(inner_span);
+
+    let (output, err) = lex(source, start, &[b'\n', b'\r', b','], &[], true);
+    error = error.or(err);
+
+    let mut arms = vec![];
+    let mut position = 0;
+
+    // Each arm is `pattern [if guard] => result`, where the result is a single value
+    while position < output.len() {
+        working_set.enter_scope();
+
+        let (pattern, err) = parse_match_pattern(working_set, output[position].span);
+        error = error.or(err);
+        position += 1;
+
+        let mut pattern = pattern;
+        if position < output.len() && working_set.get_span_contents(output[position].span) == b"if"
+        {
+            let guard_start = position + 1;
+            let mut guard_end = guard_start;
+            while guard_end < output.len()
+                && working_set.get_span_contents(output[guard_end].span) != b"=>"
+            {
+                guard_end += 1;
+            }
+
+            if guard_start == guard_end {
+                error = error.or_else(|| {
+                    Some(ParseError::Expected(
+                        "guard condition".into(),
+                        output[position].span,
+                    ))
+                });
+            } else {
+                let spans: Vec<Span> = output[guard_start..guard_end]
+                    .iter()
+                    .map(|token| token.span)
+                    .collect();
+                let (guard, err) = parse_expression(working_set, &spans, true);
+                error = error.or(err);
+
+                if !type_compatible(&Type::Bool, &guard.ty) {
+                    error = error.or_else(|| {
+                        Some(ParseError::TypeMismatch(
+                            Type::Bool,
+                            guard.ty.clone(),
+                            guard.span,
+                        ))
+                    });
+                }
+                pattern.guard = Some(Box::new(guard));
+            }
+
+            position = guard_end;
+        }
+
+        if position >= output.len() || working_set.get_span_contents(output[position].span) != b"=>"
+        {
+            let span = output
+                .get(position)
+                .map_or(pattern.span, |token| token.span);
+            error = error.or_else(|| Some(ParseError::Expected("=>".into(), span)));
+            working_set.exit_scope();
+            break;
+        }
+        position += 1;
+
+        let result_span = match output.get(position) {
+            Some(token) => token.span,
+            None => {
+                error = error.or_else(|| {
+                    Some(ParseError::Expected(
+                        "match result".into(),
+                        output[position - 1].span,
+                    ))
+                });
+                working_set.exit_scope();
+                break;
+            }
+        };
+        position += 1;
+
+        let result_shape = if working_set.get_span_contents(result_span).starts_with(b"{") {
+            SyntaxShape::Block(None)
+        } else {
+            SyntaxShape::Any
+        };
+        let (result, err) = parse_value(working_set, result_span, &result_shape);
+        error = error.or(err);
+
+        working_set.exit_scope();
+
+        arms.push((pattern, result));
+    }
+
+    (
+        Expression {
+            expr: Expr::MatchBlock(arms),
+            span,
+            ty: Type::Unknown,
+            custom_completion: None,
+        },
+        error,
+    )
+}
+
+pub fn parse_match_pattern(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+) -> (MatchPattern, Option<ParseError>) {
+    let bytes = working_set.get_span_contents(span);
+
+    let (pattern, error) = if bytes == b"_" {
+        (Pattern::IgnoreValue, None)
+    } else if bytes.starts_with(b"$") {
+        parse_variable_pattern(working_set, span)
+    } else if bytes.starts_with(b"[") {
+        parse_list_pattern(working_set, span)
+    } else if bytes.starts_with(b"{") {
+        parse_record_pattern(working_set, span)
+    } else {
+        let (value, err) = parse_value(working_set, span, &SyntaxShape::Any);
+        (Pattern::Value(Box::new(value)), err)
+    };
+
+    (
+        MatchPattern {
+            pattern,
+            guard: None,
+            span,
+        },
+        error,
+    )
+}
+
+fn parse_variable_pattern(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+) -> (Pattern, Option<ParseError>) {
+    let bytes = working_set.get_span_contents(span).to_vec();
+
+    if is_variable(&bytes) {
+        let var_id = working_set.add_variable(bytes, span, Type::Unknown, false);
+        (Pattern::Variable(var_id), None)
+    } else {
+        (
+            Pattern::Garbage,
+            Some(ParseError::Expected("valid variable name".into(), span)),
+        )
+    }
+}
+
+fn parse_list_pattern(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+) -> (Pattern, Option<ParseError>) {
+    let bytes = working_set.get_span_contents(span);
+    let mut error = None;
+
+    let start = span.start + 1;
+    let mut end = span.end;
+
+    if bytes.ends_with(b"]") {
+        end -= 1;
+    } else {
+        error = error.or_else(|| Some(ParseError::Unclosed("]".into(), Span { start: end, end })));
+    }
+
+    let inner_span = Span { start, end };
+    let source = working_set.get_span_contents(inner_span);
+
+    let (output, err) = lex(source, start, &[b'\n', b'\r', b','], &[], true);
+    error = error.or(err);
+
+    let mut items = vec![];
+    for (idx, token) in output.iter().enumerate() {
+        let contents = working_set.get_span_contents(token.span);
+
+        let item = if contents == b".." {
+            Pattern::IgnoreRest
+        } else if contents.starts_with(b"..$") {
+            let name = contents[2..].to_vec();
+            if is_variable(&name) {
+                Pattern::Rest(working_set.add_variable(name, token.span, Type::Unknown, false))
+            } else {
+                error = error.or_else(|| {
+                    Some(ParseError::Expected(
+                        "valid variable name".into(),
+                        token.span,
+                    ))
+                });
+                Pattern::Garbage
+            }
+        } else {
+            let (item, err) = parse_match_pattern(working_set, token.span);
+            error = error.or(err);
+            items.push(item);
+            continue;
+        };
+
+        if idx != output.len() - 1 {
+            error = error.or_else(|| {
+                Some(ParseError::Expected(
+                    "rest pattern at the end of the list".into(),
+                    token.span,
+                ))
+            });
+        }
+
+        items.push(MatchPattern {
+            pattern: item,
+            guard: None,
+            span: token.span,
+        });
+    }
+
+    (Pattern::List(items), error)
+}
+
+fn parse_record_pattern(
+    working_set: &mut StateWorkingSet,
+    span: Span,
+) -> (Pattern, Option<ParseError>) {
+    let bytes = working_set.get_span_contents(span);
+    let mut error = None;
+
+    let start = span.start + 1;
+    let mut end = span.end;
+
+    if bytes.ends_with(b"}") {
+        end -= 1;
+    } else {
+        error = error.or_else(|| Some(ParseError::Unclosed("}".into(), Span { start: end, end })));
+    }
+
+    let inner_span = Span { start, end };
+    let source = working_set.get_span_contents(inner_span);
+
+    let (output, err) = lex(source, start, &[b'\n', b'\r', b','], &[b':'], true);
+    error = error.or(err);
+
+    let mut fields = vec![];
+    let mut idx = 0;
+
+    while idx < output.len() {
+        let key_span = output[idx].span;
+        let key = working_set.get_span_contents(key_span).to_vec();
+
+        let has_colon = matches!(
+            output.get(idx + 1),
+            Some(token) if working_set.get_span_contents(token.span) == b":"
+        );
+
+        if !has_colon && key.starts_with(b"$") {
+            // Shorthand for binding a field to a variable of the same name, eg) `{$name}`
+            let (pattern, err) = parse_variable_pattern(working_set, key_span);
+            error = error.or(err);
+
+            fields.push((
+                String::from_utf8_lossy(&key[1..]).to_string(),
+                MatchPattern {
+                    pattern,
+                    guard: None,
+                    span: key_span,
+                },
+            ));
+            idx += 1;
+        } else if let (true, Some(value)) = (has_colon, output.get(idx + 2)) {
+            let (pattern, err) = parse_match_pattern(working_set, value.span);
+            error = error.or(err);
+
+            fields.push((
+                String::from_utf8_lossy(trim_quotes(&key)).to_string(),
+                pattern,
+            ));
+            idx += 3;
+        } else {
+            error = error.or_else(|| Some(ParseError::Expected("field: pattern".into(), key_span)));
+            break;
+        }
+    }
+
+    (Pattern::Record(fields), error)
+}
+
 pub fn parse_value(
     working_set: &mut StateWorkingSet,
     span: Span,
@@ -2778,6 +3513,9 @@ pub fn parse_value(
             return parse_full_cell_path(working_set, None, span);
         }
     } else if bytes.starts_with(b"{") {
+        if shape == &SyntaxShape::MatchBlock {
+            return parse_match_block_expression(working_set, span);
+        }
         if !matches!(shape, SyntaxShape::Block(..)) {
             if let (expr, None) = parse_full_cell_path(working_set, None, span) {
                 return (expr, None);
@@ -2830,6 +3568,14 @@ pub fn parse_value(
                 )
             }
         }
+        SyntaxShape::MatchBlock => (
+            garbage(span),
+            Some(ParseError::Expected("match block".into(), span)),
+        ),
+        SyntaxShape::Record => (
+            garbage(span),
+            Some(ParseError::Expected("record".into(), span)),
+        ),
         SyntaxShape::Signature => {
             if bytes.starts_with(b"[") {
                 parse_signature(working_set, span)
@@ -2943,18 +3689,26 @@ pub fn parse_operator(
         b"<=" => Operator::LessThanOrEqual,
         b">" => Operator::GreaterThan,
         b">=" => Operator::GreaterThanOrEqual,
-        b"=~" => Operator::Contains,
-        b"!~" => Operator::NotContains,
+        b"=~" => Operator::RegexMatch,
+        b"!~" => Operator::NotRegexMatch,
         b"+" => Operator::Plus,
         b"-" => Operator::Minus,
         b"*" => Operator::Multiply,
         b"/" => Operator::Divide,
+        b"//" => Operator::FloorDivision,
         b"in" => Operator::In,
         b"not-in" => Operator::NotIn,
+        b"starts-with" => Operator::StartsWith,
+        b"ends-with" => Operator::EndsWith,
         b"mod" => Operator::Modulo,
         b"&&" => Operator::And,
         b"||" => Operator::Or,
         b"**" => Operator::Pow,
+        b"bit-or" => Operator::BitOr,
+        b"bit-xor" => Operator::BitXor,
+        b"bit-and" => Operator::BitAnd,
+        b"bit-shl" => Operator::ShiftLeft,
+        b"bit-shr" => Operator::ShiftRight,
         _ => {
             return (
                 garbage(span),
@@ -3093,6 +3847,76 @@ pub fn parse_math_expression(
     (output, error)
 }
 
+fn is_assignment(working_set: &StateWorkingSet, spans: &[Span]) -> bool {
+    spans.len() > 1
+        && working_set.get_span_contents(spans[0]).starts_with(b"$")
+        && matches!(
+            working_set.get_span_contents(spans[1]),
+            b"=" | b"+=" | b"-="
+        )
+}
+
+pub fn parse_assignment(
+    working_set: &mut StateWorkingSet,
+    spans: &[Span],
+) -> (Expression, Option<ParseError>) {
+    let mut error = None;
+
+    let (mut lhs, err) = parse_value(working_set, spans[0], &SyntaxShape::Any);
+    error = error.or(err);
+
+    // `$x` is parsed as a cell path without members
+    if let Expr::FullCellPath(cell_path) = &lhs.expr {
+        if cell_path.tail.is_empty() {
+            lhs = cell_path.head.clone();
+        }
+    }
+
+    match &lhs.expr {
+        Expr::Var(var_id) => {
+            if !working_set.get_variable(*var_id).mutable {
+                error = error.or(Some(ParseError::AssignmentRequiresMutableVar(lhs.span)));
+            }
+        }
+        _ => error = error.or(Some(ParseError::AssignmentRequiresVar(lhs.span))),
+    }
+
+    let operator = match working_set.get_span_contents(spans[1]) {
+        b"+=" => Operator::PlusAssign,
+        b"-=" => Operator::MinusAssign,
+        _ => Operator::Assign,
+    };
+    let mut op = Expression {
+        expr: Expr::Operator(operator),
+        span: spans[1],
+        ty: Type::Unknown,
+        custom_completion: None,
+    };
+
+    if spans.len() < 3 {
+        return (
+            garbage(span(spans)),
+            error.or(Some(ParseError::IncompleteMathExpression(spans[1]))),
+        );
+    }
+
+    let (mut rhs, err) = parse_expression(working_set, &spans[2..], true);
+    error = error.or(err);
+
+    let (ty, err) = math_result_type(working_set, &mut lhs, &mut op, &mut rhs);
+    error = error.or(err);
+
+    (
+        Expression {
+            expr: Expr::Assignment(Box::new(lhs), Box::new(op), Box::new(rhs)),
+            span: span(spans),
+            ty,
+            custom_completion: None,
+        },
+        error,
+    )
+}
+
 pub fn parse_expression(
     working_set: &mut StateWorkingSet,
     spans: &[Span],
@@ -3156,7 +3980,9 @@ pub fn parse_expression(
 
     let bytes = working_set.get_span_contents(spans[pos]);
 
-    let (output, err) = if is_math_expression_byte(bytes[0]) {
+    let (output, err) = if is_assignment(working_set, &spans[pos..]) {
+        parse_assignment(working_set, &spans[pos..])
+    } else if is_math_expression_byte(bytes[0]) {
         parse_math_expression(working_set, &spans[pos..], None)
     } else {
         parse_call(working_set, &spans[pos..], expand_aliases, spans[0])
@@ -3174,6 +4000,7 @@ pub fn parse_expression(
 
             let mut seen = vec![];
             let captures = find_captures_in_block(working_set, &block, &mut seen);
+            let err = err.or_else(|| check_mutable_captures(working_set, &captures, span(spans)));
             block.captures = captures;
 
             let block_id = working_set.add_block(block);
@@ -3247,6 +4074,8 @@ pub fn parse_statement(
     match name {
         b"def" => parse_def(working_set, spans),
         b"let" => parse_let(working_set, spans),
+        b"mut" => parse_mut(working_set, spans),
+        b"break" | b"continue" => parse_loop_control(working_set, spans),
         b"alias" => parse_alias(working_set, spans),
         b"module" => parse_module(working_set, spans),
         b"use" => parse_use(working_set, spans),
@@ -3258,6 +4087,10 @@ pub fn parse_statement(
         b"hide" => parse_hide(working_set, spans),
         #[cfg(feature = "plugin")]
         b"register" => parse_register(working_set, spans),
+        #[cfg(feature = "plugin")]
+        b"plugin" if spans.len() > 1 && working_set.get_span_contents(spans[1]) == b"reload" => {
+            parse_plugin_reload(working_set, spans)
+        }
         _ => {
             let (expr, err) = parse_expression(working_set, spans, true);
             (Statement::Pipeline(Pipeline::from_vec(vec![expr])), err)
@@ -3382,19 +4215,36 @@ pub fn parse_block(
                     })
                     .collect::<Vec<Expression>>();
 
-                if let Some(let_call_id) = working_set.find_decl(b"let") {
-                    for expr in output.iter() {
-                        if let Expr::Call(x) = &expr.expr {
-                            if let_call_id == x.decl_id && output.len() != 1 && error.is_none() {
-                                error = Some(ParseError::LetNotStatement(expr.span));
+                for keyword in [b"let", b"mut"] {
+                    if let Some(let_call_id) = working_set.find_decl(keyword) {
+                        for expr in output.iter() {
+                            if let Expr::Call(x) = &expr.expr {
+                                if let_call_id == x.decl_id && output.len() != 1 && error.is_none()
+                                {
+                                    error = Some(ParseError::LetNotStatement(expr.span));
+                                }
                             }
                         }
                     }
                 }
 
+                for idx in 1..output.len() {
+                    let input = output[idx - 1].ty.clone();
+                    let err = check_call_input(working_set, &mut output[idx], &input);
+
+                    if error.is_none() {
+                        error = err;
+                    }
+                }
+
                 for expr in output.iter_mut().skip(1) {
                     if expr.has_in_variable(working_set) {
-                        *expr = wrap_expr_with_collect(working_set, expr);
+                        let (wrapped, err) = wrap_expr_with_collect(working_set, expr);
+                        *expr = wrapped;
+
+                        if error.is_none() {
+                            error = err;
+                        }
                     }
                 }
 
@@ -3462,6 +4312,24 @@ fn find_captures_in_block(
     output
 }
 
+// Blocks run with a copy of the variables they capture, so changes to a mutable variable
+// inside of a block would be lost. Loop bodies and blocks that run in place aren't checked,
+// as they give their changes back to the caller
+fn check_mutable_captures(
+    working_set: &StateWorkingSet,
+    captures: &[VarId],
+    span: Span,
+) -> Option<ParseError> {
+    if captures
+        .iter()
+        .any(|var_id| working_set.get_variable(*var_id).mutable)
+    {
+        Some(ParseError::CaptureOfMutableVar(span))
+    } else {
+        None
+    }
+}
+
 fn find_captures_in_pipeline(
     working_set: &StateWorkingSet,
     pipeline: &Pipeline,
@@ -3483,7 +4351,7 @@ pub fn find_captures_in_expr(
 ) -> Vec<VarId> {
     let mut output = vec![];
     match &expr.expr {
-        Expr::BinaryOp(lhs, _, rhs) => {
+        Expr::BinaryOp(lhs, _, rhs) | Expr::Assignment(lhs, _, rhs) => {
             let lhs_result = find_captures_in_expr(working_set, lhs, seen);
             let rhs_result = find_captures_in_expr(working_set, rhs, seen);
 
@@ -3510,8 +4378,11 @@ pub fn find_captures_in_expr(
             }
         }
         Expr::CellPath(_) => {}
-        Expr::ExternalCall(_, _, exprs) => {
-            for expr in exprs {
+        Expr::ExternalCall(_, _, exprs, redirections) => {
+            for expr in exprs
+                .iter()
+                .chain(redirections.iter().map(|(_, target)| target))
+            {
                 let result = find_captures_in_expr(working_set, expr, seen);
                 output.extend(&result);
             }
@@ -3558,6 +4429,20 @@ pub fn find_captures_in_expr(
                 output.extend(&find_captures_in_expr(working_set, field_value, seen));
             }
         }
+        Expr::MatchPattern(pattern) => {
+            seen.extend(pattern.variables());
+        }
+        Expr::MatchBlock(arms) => {
+            for (pattern, expr) in arms {
+                // Variables bound by a pattern are declared by the match itself
+                seen.extend(pattern.variables());
+
+                if let Some(guard) = &pattern.guard {
+                    output.extend(&find_captures_in_expr(working_set, guard, seen));
+                }
+                output.extend(&find_captures_in_expr(working_set, expr, seen));
+            }
+        }
         Expr::Signature(_) => {}
         Expr::String(_) => {}
         Expr::StringInterpolation(exprs) => {
@@ -3599,16 +4484,20 @@ pub fn find_captures_in_expr(
     output
 }
 
-fn wrap_expr_with_collect(working_set: &mut StateWorkingSet, expr: &Expression) -> Expression {
+fn wrap_expr_with_collect(
+    working_set: &mut StateWorkingSet,
+    expr: &Expression,
+) -> (Expression, Option<ParseError>) {
     let span = expr.span;
 
     if let Some(decl_id) = working_set.find_decl(b"collect") {
         let mut output = vec![];
 
-        let var_id = working_set.next_var_id();
+        let var_id = working_set.add_variable(b"$in".to_vec(), span, Type::Unknown, false);
         let mut signature = Signature::new("");
         signature.required_positional.push(PositionalArg {
             var_id: Some(var_id),
+            default_value: None,
             name: "$it".into(),
             desc: String::new(),
             shape: SyntaxShape::Any,
@@ -3627,6 +4516,7 @@ fn wrap_expr_with_collect(working_set: &mut StateWorkingSet, expr: &Expression)
 
         let mut seen = vec![];
         let captures = find_captures_in_block(working_set, &block, &mut seen);
+        let error = check_mutable_captures(working_set, &captures, span);
 
         block.captures = captures;
 
@@ -3639,19 +4529,22 @@ fn wrap_expr_with_collect(working_set: &mut StateWorkingSet, expr: &Expression)
             custom_completion: None,
         });
 
-        Expression {
-            expr: Expr::Call(Box::new(Call {
-                head: span,
-                named: vec![],
-                positional: output,
-                decl_id,
-            })),
-            span,
-            ty: Type::String,
-            custom_completion: None,
-        }
+        (
+            Expression {
+                expr: Expr::Call(Box::new(Call {
+                    head: span,
+                    named: vec![],
+                    positional: output,
+                    decl_id,
+                })),
+                span,
+                ty: Type::String,
+                custom_completion: None,
+            },
+            error,
+        )
     } else {
-        Expression::garbage(span)
+        (Expression::garbage(span), None)
     }
 }
 
@@ -2,20 +2,114 @@ use crate::ParseError;
 use nu_protocol::{
     ast::{Expr, Expression, Operator},
     engine::StateWorkingSet,
-    Type,
+    Signature, Type,
 };
 
 pub fn type_compatible(lhs: &Type, rhs: &Type) -> bool {
     match (lhs, rhs) {
         (Type::List(c), Type::List(d)) => type_compatible(c, d),
+        (Type::Record(_), Type::Record(_)) => true,
         (Type::Unknown, _) => true,
         (_, Type::Unknown) => true,
         (lhs, rhs) => lhs == rhs,
     }
 }
 
+/// Whether a value of the `found` type can be piped into a command that takes `expected` input
+pub fn input_compatible(expected: &Type, found: &Type) -> bool {
+    match (expected, found) {
+        (Type::Unknown, _) | (_, Type::Unknown) => true,
+        (Type::Number, Type::Int | Type::Float) => true,
+        (Type::List(e), Type::List(f)) => input_compatible(e, f),
+        // A table is a list of records
+        (Type::List(e), Type::Table) | (Type::Table, Type::List(e)) => {
+            matches!(**e, Type::Unknown | Type::Record(_))
+        }
+        // Ranges stream their numbers
+        (Type::List(e), Type::Range) => {
+            matches!(**e, Type::Unknown | Type::Number | Type::Int | Type::Float)
+        }
+        (Type::Record(_), Type::Record(_)) => true,
+        (expected, found) => expected == found,
+    }
+}
+
+/// The output type of a command when its input type isn't known
+pub fn call_output_type(signature: &Signature) -> Type {
+    match signature.input_output_types.split_first() {
+        Some(((_, output), rest)) if rest.iter().all(|(_, other)| other == output) => {
+            output.clone()
+        }
+        _ => Type::Unknown,
+    }
+}
+
+/// Check the pipeline input of a call against the input types of its command, and set the type
+/// of the call to the output the command gives for that input
+pub fn check_call_input(
+    working_set: &StateWorkingSet,
+    expr: &mut Expression,
+    input: &Type,
+) -> Option<ParseError> {
+    let call = match &expr.expr {
+        Expr::Call(call) => call,
+        _ => return None,
+    };
+
+    let signature = working_set.get_decl(call.decl_id).signature();
+    if signature.input_output_types.is_empty() || input == &Type::Unknown {
+        return None;
+    }
+
+    // Commands given column paths as their rest arguments work on those columns of their table
+    // or record input
+    let fixed_positionals =
+        signature.required_positional.len() + signature.optional_positional.len();
+    let has_cell_paths = call
+        .positional
+        .iter()
+        .skip(fixed_positionals)
+        .any(|arg| matches!(arg.expr, Expr::CellPath(_)));
+    if has_cell_paths && matches!(input, Type::Table | Type::Record(_) | Type::List(_)) {
+        expr.ty = input.clone();
+        return None;
+    }
+
+    // An input type the command lists exactly is preferred over the first one it is compatible
+    // with, so a table isn't taken for the list of anything that comes before it
+    let found = signature
+        .input_output_types
+        .iter()
+        .find(|(expected, _)| expected == input)
+        .or_else(|| {
+            signature
+                .input_output_types
+                .iter()
+                .find(|(expected, _)| input_compatible(expected, input))
+        });
+
+    if let Some((_, output)) = found {
+        expr.ty = output.clone();
+        return None;
+    }
+
+    let expected = signature
+        .input_output_types
+        .iter()
+        .map(|(expected, _)| expected.to_string())
+        .collect::<Vec<_>>()
+        .join(" or ");
+
+    Some(ParseError::InputMismatch(
+        signature.name,
+        input.clone(),
+        expected,
+        call.head,
+    ))
+}
+
 pub fn math_result_type(
-    _working_set: &StateWorkingSet,
+    working_set: &StateWorkingSet,
     lhs: &mut Expression,
     op: &mut Expression,
     rhs: &mut Expression,
@@ -31,6 +125,7 @@ pub fn math_result_type(
                 (Type::String, Type::String) => (Type::String, None),
                 (Type::Duration, Type::Duration) => (Type::Duration, None),
                 (Type::Filesize, Type::Filesize) => (Type::Filesize, None),
+                (Type::Date, Type::Duration) => (Type::Date, None),
 
                 (Type::Unknown, _) => (Type::Unknown, None),
                 (_, Type::Unknown) => (Type::Unknown, None),
@@ -69,6 +164,7 @@ pub fn math_result_type(
                 (Type::Float, Type::Float) => (Type::Float, None),
                 (Type::Duration, Type::Duration) => (Type::Duration, None),
                 (Type::Filesize, Type::Filesize) => (Type::Filesize, None),
+                (Type::Date, Type::Duration) => (Type::Date, None),
 
                 (Type::Unknown, _) => (Type::Unknown, None),
                 (_, Type::Unknown) => (Type::Unknown, None),
@@ -108,7 +204,57 @@ pub fn math_result_type(
                     )
                 }
             },
-            Operator::Divide | Operator::Modulo => match (&lhs.ty, &rhs.ty) {
+            Operator::FloorDivision => match (&lhs.ty, &rhs.ty) {
+                (Type::Int, Type::Int) => (Type::Int, None),
+                (Type::Float, Type::Int) => (Type::Float, None),
+                (Type::Int, Type::Float) => (Type::Float, None),
+                (Type::Float, Type::Float) => (Type::Float, None),
+                (Type::Filesize, Type::Filesize) => (Type::Int, None),
+                (Type::Filesize, Type::Int) => (Type::Filesize, None),
+                (Type::Duration, Type::Duration) => (Type::Int, None),
+                (Type::Duration, Type::Int) => (Type::Duration, None),
+
+                (Type::Unknown, _) => (Type::Unknown, None),
+                (_, Type::Unknown) => (Type::Unknown, None),
+                _ => {
+                    *op = Expression::garbage(op.span);
+                    (
+                        Type::Unknown,
+                        Some(ParseError::UnsupportedOperation(
+                            op.span,
+                            lhs.span,
+                            lhs.ty.clone(),
+                            rhs.span,
+                            rhs.ty.clone(),
+                        )),
+                    )
+                }
+            },
+            Operator::Modulo => match (&lhs.ty, &rhs.ty) {
+                (Type::Int, Type::Int) => (Type::Int, None),
+                (Type::Float, Type::Int) => (Type::Float, None),
+                (Type::Int, Type::Float) => (Type::Float, None),
+                (Type::Float, Type::Float) => (Type::Float, None),
+                (Type::Filesize, Type::Filesize) => (Type::Filesize, None),
+                (Type::Duration, Type::Duration) => (Type::Duration, None),
+
+                (Type::Unknown, _) => (Type::Unknown, None),
+                (_, Type::Unknown) => (Type::Unknown, None),
+                _ => {
+                    *op = Expression::garbage(op.span);
+                    (
+                        Type::Unknown,
+                        Some(ParseError::UnsupportedOperation(
+                            op.span,
+                            lhs.span,
+                            lhs.ty.clone(),
+                            rhs.span,
+                            rhs.ty.clone(),
+                        )),
+                    )
+                }
+            },
+            Operator::Divide => match (&lhs.ty, &rhs.ty) {
                 (Type::Int, Type::Int) => (Type::Int, None),
                 (Type::Float, Type::Int) => (Type::Float, None),
                 (Type::Int, Type::Float) => (Type::Float, None),
@@ -130,6 +276,29 @@ pub fn math_result_type(
                     )
                 }
             },
+            Operator::BitOr
+            | Operator::BitXor
+            | Operator::BitAnd
+            | Operator::ShiftLeft
+            | Operator::ShiftRight => match (&lhs.ty, &rhs.ty) {
+                (Type::Int, Type::Int) => (Type::Int, None),
+
+                (Type::Unknown, _) => (Type::Unknown, None),
+                (_, Type::Unknown) => (Type::Unknown, None),
+                _ => {
+                    *op = Expression::garbage(op.span);
+                    (
+                        Type::Unknown,
+                        Some(ParseError::UnsupportedOperation(
+                            op.span,
+                            lhs.span,
+                            lhs.ty.clone(),
+                            rhs.span,
+                            rhs.ty.clone(),
+                        )),
+                    )
+                }
+            },
             Operator::And | Operator::Or => match (&lhs.ty, &rhs.ty) {
                 (Type::Bool, Type::Bool) => (Type::Bool, None),
 
@@ -156,6 +325,7 @@ pub fn math_result_type(
                 (Type::Float, Type::Float) => (Type::Bool, None),
                 (Type::Duration, Type::Duration) => (Type::Bool, None),
                 (Type::Filesize, Type::Filesize) => (Type::Bool, None),
+                (Type::Date, Type::Date) => (Type::Bool, None),
 
                 (Type::Unknown, _) => (Type::Bool, None),
                 (_, Type::Unknown) => (Type::Bool, None),
@@ -180,6 +350,7 @@ pub fn math_result_type(
                 (Type::Float, Type::Float) => (Type::Bool, None),
                 (Type::Duration, Type::Duration) => (Type::Bool, None),
                 (Type::Filesize, Type::Filesize) => (Type::Bool, None),
+                (Type::Date, Type::Date) => (Type::Bool, None),
 
                 (Type::Unknown, _) => (Type::Bool, None),
                 (_, Type::Unknown) => (Type::Bool, None),
@@ -204,6 +375,7 @@ pub fn math_result_type(
                 (Type::Float, Type::Float) => (Type::Bool, None),
                 (Type::Duration, Type::Duration) => (Type::Bool, None),
                 (Type::Filesize, Type::Filesize) => (Type::Bool, None),
+                (Type::Date, Type::Date) => (Type::Bool, None),
 
                 (Type::Unknown, _) => (Type::Bool, None),
                 (_, Type::Unknown) => (Type::Bool, None),
@@ -228,6 +400,7 @@ pub fn math_result_type(
                 (Type::Float, Type::Float) => (Type::Bool, None),
                 (Type::Duration, Type::Duration) => (Type::Bool, None),
                 (Type::Filesize, Type::Filesize) => (Type::Bool, None),
+                (Type::Date, Type::Date) => (Type::Bool, None),
 
                 (Type::Unknown, _) => (Type::Bool, None),
                 (_, Type::Unknown) => (Type::Bool, None),
@@ -296,25 +469,10 @@ pub fn math_result_type(
                     )
                 }
             },
-            Operator::Contains => match (&lhs.ty, &rhs.ty) {
-                (Type::String, Type::String) => (Type::Bool, None),
-                (Type::Unknown, _) => (Type::Bool, None),
-                (_, Type::Unknown) => (Type::Bool, None),
-                _ => {
-                    *op = Expression::garbage(op.span);
-                    (
-                        Type::Unknown,
-                        Some(ParseError::UnsupportedOperation(
-                            op.span,
-                            lhs.span,
-                            lhs.ty.clone(),
-                            rhs.span,
-                            rhs.ty.clone(),
-                        )),
-                    )
-                }
-            },
-            Operator::NotContains => match (&lhs.ty, &rhs.ty) {
+            Operator::RegexMatch
+            | Operator::NotRegexMatch
+            | Operator::StartsWith
+            | Operator::EndsWith => match (&lhs.ty, &rhs.ty) {
                 (Type::String, Type::String) => (Type::Bool, None),
                 (Type::Unknown, _) => (Type::Bool, None),
                 (_, Type::Unknown) => (Type::Bool, None),
@@ -376,6 +534,41 @@ pub fn math_result_type(
                     )
                 }
             },
+            Operator::Assign => {
+                if type_compatible(&lhs.ty, &rhs.ty) {
+                    (Type::Nothing, None)
+                } else {
+                    (
+                        Type::Nothing,
+                        Some(ParseError::TypeMismatch(
+                            lhs.ty.clone(),
+                            rhs.ty.clone(),
+                            rhs.span,
+                        )),
+                    )
+                }
+            }
+            Operator::PlusAssign | Operator::MinusAssign => {
+                // Check the operation as if it was written out, eg) `$x = $x + 1`
+                let operator = if operator == &Operator::PlusAssign {
+                    Operator::Plus
+                } else {
+                    Operator::Minus
+                };
+                let mut math_op = Expression {
+                    expr: Expr::Operator(operator),
+                    ..op.clone()
+                };
+
+                match math_result_type(working_set, lhs, &mut math_op, rhs) {
+                    (_, Some(err)) => (Type::Nothing, Some(err)),
+                    (ty, None) if type_compatible(&lhs.ty, &ty) => (Type::Nothing, None),
+                    (ty, None) => (
+                        Type::Nothing,
+                        Some(ParseError::TypeMismatch(lhs.ty.clone(), ty, rhs.span)),
+                    ),
+                }
+            }
         },
         _ => {
             *op = Expression::garbage(op.span);
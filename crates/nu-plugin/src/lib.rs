@@ -5,6 +5,11 @@ mod serializers;
 #[allow(dead_code)]
 mod plugin_capnp;
 
-pub use plugin::{get_signature, serve_plugin, Plugin, PluginDeclaration};
-pub use protocol::{EvaluatedCall, LabeledError};
-pub use serializers::{capnp::CapnpSerializer, json::JsonSerializer, EncodingType};
+pub use plugin::{
+    get_cached_signature, get_signature, reload_signature, serve_plugin, shutdown_plugins,
+    EngineInterface, Plugin, PluginDeclaration,
+};
+pub use protocol::{ErrorLabel, EvaluatedCall, LabeledError, ProtocolInfo, PROTOCOL_VERSION};
+pub use serializers::{
+    capnp::CapnpSerializer, json::JsonSerializer, msgpack::MsgPackSerializer, EncodingType,
+};
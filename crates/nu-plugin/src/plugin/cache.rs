@@ -0,0 +1,154 @@
+use super::get_signature;
+use crate::EncodingType;
+
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use nu_protocol::{ShellError, Signature};
+use serde::{Deserialize, Serialize};
+
+// Signatures of the registered plugins. The cache is saved next to the plugin file so
+// nushell doesn't have to start every plugin when it starts. A plugin is only asked
+// again for its signatures when its file changes
+#[derive(Default, Serialize, Deserialize)]
+struct SignatureCache {
+    plugins: Vec<CachedPlugin>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedPlugin {
+    filename: PathBuf,
+    shell: Option<PathBuf>,
+    stamp: FileStamp,
+    signatures: Vec<Signature>,
+}
+
+// Modification time, in nanoseconds since the epoch, and size of the plugin file
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct FileStamp {
+    modified: u128,
+    size: u64,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        let modified = metadata
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_nanos();
+
+        Some(Self {
+            modified,
+            size: metadata.len(),
+        })
+    }
+}
+
+impl SignatureCache {
+    // A missing or unreadable cache is the same as an empty one
+    fn load(cache_file: &Path) -> Self {
+        std::fs::read(cache_file)
+            .ok()
+            .and_then(|contents| serde_json::from_slice(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    // The cache only saves work at startup, failing to write it is not an error
+    fn save(&self, cache_file: &Path) {
+        if let Ok(contents) = serde_json::to_vec(self) {
+            let _ = std::fs::write(cache_file, contents);
+        }
+    }
+
+    fn get(&self, path: &Path, shell: &Option<PathBuf>, stamp: FileStamp) -> Option<&[Signature]> {
+        self.plugins
+            .iter()
+            .find(|plugin| {
+                plugin.filename == path && &plugin.shell == shell && plugin.stamp == stamp
+            })
+            .map(|plugin| plugin.signatures.as_slice())
+    }
+
+    fn insert(&mut self, path: &Path, shell: &Option<PathBuf>, signatures: &[Signature]) {
+        self.plugins.retain(|plugin| plugin.filename != path);
+
+        if let Some(stamp) = FileStamp::read(path) {
+            self.plugins.push(CachedPlugin {
+                filename: path.to_path_buf(),
+                shell: shell.clone(),
+                stamp,
+                signatures: signatures.to_vec(),
+            })
+        }
+    }
+}
+
+// Returns the cached signatures of the plugin if its file didn't change since they were
+// cached. Otherwise the plugin is asked for its signatures and the cache is updated
+pub fn get_cached_signature(
+    path: &Path,
+    encoding: &EncodingType,
+    shell: &Option<PathBuf>,
+    cache_file: &Path,
+) -> Result<Vec<Signature>, ShellError> {
+    let cache = SignatureCache::load(cache_file);
+
+    let cached = FileStamp::read(path).and_then(|stamp| cache.get(path, shell, stamp));
+    match cached {
+        Some(signatures) => Ok(signatures.to_vec()),
+        None => reload_signature(path, encoding, shell, cache_file),
+    }
+}
+
+// Asks the plugin for its signatures, whether they are cached or not, and updates the
+// cache. The idle processes of the plugin are stopped so a rebuilt plugin is picked up
+pub fn reload_signature(
+    path: &Path,
+    encoding: &EncodingType,
+    shell: &Option<PathBuf>,
+    cache_file: &Path,
+) -> Result<Vec<Signature>, ShellError> {
+    let signatures = get_signature(path, encoding, shell)?;
+
+    let mut cache = SignatureCache::load(cache_file);
+    cache.insert(path, shell, &signatures);
+    cache.save(cache_file);
+
+    Ok(signatures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_is_invalidated_when_the_file_changes() {
+        let dir = tempfile::tempdir().expect("unable to create test dir");
+
+        let plugin = dir.path().join("nu_plugin_test");
+        let cache_file = dir.path().join("plugin-cache.json");
+        std::fs::write(&plugin, "plugin").expect("unable to write plugin file");
+
+        let signatures = vec![Signature::build("test")];
+        let mut cache = SignatureCache::default();
+        cache.insert(&plugin, &None, &signatures);
+        cache.save(&cache_file);
+
+        let stamp = FileStamp::read(&plugin).expect("plugin file stamp");
+        let cache = SignatureCache::load(&cache_file);
+        assert_eq!(
+            cache
+                .get(&plugin, &None, stamp)
+                .map(|signatures| signatures.len()),
+            Some(1)
+        );
+        assert!(cache.get(&plugin, &Some("python".into()), stamp).is_none());
+
+        std::fs::write(&plugin, "rebuilt plugin").expect("unable to write plugin file");
+        let stamp = FileStamp::read(&plugin).expect("plugin file stamp");
+        assert!(cache.get(&plugin, &None, stamp).is_none());
+    }
+}
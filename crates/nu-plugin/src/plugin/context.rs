@@ -0,0 +1,164 @@
+use crate::protocol::{
+    attach_source, check_source, EngineCall, EngineCallResponse, EvalBlock, PluginSource,
+};
+
+use nu_engine::{current_dir_str, eval_block};
+use nu_protocol::engine::{EngineState, Stack};
+use nu_protocol::{BlockId, PipelineData, ShellError, Span, Value, VarId, CONFIG_VARIABLE_ID};
+use std::collections::HashMap;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+
+// Captures of the blocks sent to the plugin, by block id. The capnp encoding sends blocks
+// without their captures, so they are looked up here when the plugin evaluates a block
+pub(crate) type BlockCaptures = Arc<Mutex<HashMap<BlockId, HashMap<VarId, Value>>>>;
+
+pub(crate) fn remember_captures(value: &Value, block_captures: &BlockCaptures) {
+    match value {
+        Value::Block { val, captures, .. } => {
+            if let Ok(mut block_captures) = block_captures.lock() {
+                block_captures.insert(*val, captures.clone());
+            }
+        }
+        Value::List { vals, .. } | Value::Record { vals, .. } => vals
+            .iter()
+            .for_each(|value| remember_captures(value, block_captures)),
+        _ => {}
+    }
+}
+
+// State of the engine a plugin call was made from. It answers the engine calls the
+// plugin sends while it serves the call, including while its output is streamed
+pub(crate) struct PluginContext {
+    engine_state: EngineState,
+    stack: Stack,
+    source: PluginSource,
+    span: Span,
+    block_captures: BlockCaptures,
+}
+
+impl PluginContext {
+    pub fn new(
+        engine_state: &EngineState,
+        stack: &Stack,
+        source: PluginSource,
+        span: Span,
+    ) -> Self {
+        Self {
+            engine_state: engine_state.clone(),
+            stack: stack.clone(),
+            source,
+            span,
+            block_captures: BlockCaptures::default(),
+        }
+    }
+
+    pub fn is_interrupted(&self) -> bool {
+        self.engine_state
+            .ctrlc
+            .as_ref()
+            .map_or(false, |ctrlc| ctrlc.load(Ordering::SeqCst))
+    }
+
+    pub fn block_captures(&self) -> BlockCaptures {
+        self.block_captures.clone()
+    }
+
+    pub fn engine_call(&mut self, engine_call: EngineCall) -> EngineCallResponse {
+        let value = match engine_call {
+            EngineCall::EvalBlock(eval) => self.eval_block(*eval),
+            EngineCall::GetEnvVar(name) => Ok(self
+                .stack
+                .get_env_var(&name)
+                .unwrap_or(Value::Nothing { span: self.span })),
+            EngineCall::GetEnvVars => {
+                let (cols, vals): (Vec<_>, Vec<_>) = self.stack.get_env_vars().into_iter().unzip();
+
+                Ok(Value::Record {
+                    cols,
+                    vals,
+                    span: self.span,
+                })
+            }
+            EngineCall::GetConfig => self.stack.get_var(CONFIG_VARIABLE_ID),
+            EngineCall::GetCurrentDir => current_dir_str(&self.stack).map(|cwd| Value::String {
+                val: cwd,
+                span: self.span,
+            }),
+        };
+
+        // Only the plugin's own custom values can be sent back to it
+        match value.and_then(|mut value| {
+            check_source(&mut value, &self.source)?;
+            remember_captures(&value, &self.block_captures);
+            Ok(value)
+        }) {
+            Ok(value) => EngineCallResponse::Value(Box::new(value)),
+            Err(err) => EngineCallResponse::Error(err.into()),
+        }
+    }
+
+    // The values are bound to the block's parameters in order. The values left after
+    // the required and optional parameters are given to the rest parameter as a list
+    fn eval_block(&mut self, eval: EvalBlock) -> Result<Value, ShellError> {
+        let EvalBlock {
+            block,
+            mut positional,
+            mut input,
+        } = eval;
+
+        let span = block.span().unwrap_or(self.span);
+        let block_id = block.as_block()?;
+        let captures = match block {
+            Value::Block { captures, .. } if !captures.is_empty() => Some(captures),
+            _ => self
+                .block_captures
+                .lock()
+                .ok()
+                .and_then(|block_captures| block_captures.get(&block_id).cloned()),
+        };
+
+        // A block the plugin didn't receive from nushell has its captures collected from
+        // the stack of the call that ran the plugin
+        let block = self.engine_state.get_block(block_id).clone();
+        let mut stack = match captures {
+            Some(captures) => self.stack.captures_to_stack(&captures),
+            None => self.stack.collect_captures(&block.captures),
+        };
+
+        for value in positional.iter_mut().chain(std::iter::once(&mut input)) {
+            attach_source(value, &self.source);
+        }
+
+        let signature = &block.signature;
+        let num_params = signature.required_positional.len() + signature.optional_positional.len();
+        let rest = positional.split_off(num_params.min(positional.len()));
+
+        for (param, value) in signature
+            .required_positional
+            .iter()
+            .chain(signature.optional_positional.iter())
+            .zip(positional)
+        {
+            if let Some(var_id) = param.var_id {
+                stack.add_var(var_id, value);
+            }
+        }
+
+        if let Some(var_id) = signature
+            .rest_positional
+            .as_ref()
+            .and_then(|rest| rest.var_id)
+        {
+            stack.add_var(var_id, Value::List { vals: rest, span });
+        }
+
+        eval_block(
+            &self.engine_state,
+            &mut stack,
+            &block,
+            PipelineData::Value(input, None),
+        )
+        .map(|output| output.into_value(span))
+    }
+}
@@ -1,12 +1,15 @@
 use crate::{EncodingType, EvaluatedCall};
 
-use super::{create_command, OUTPUT_BUFFER_SIZE};
-use crate::protocol::{CallInfo, PluginCall, PluginResponse};
-use std::io::BufReader;
-use std::path::{Path, PathBuf};
+use super::context::{remember_captures, PluginContext};
+use super::persistent::{call_plugin, PluginOutput};
+use super::stream::{from_chunks, into_chunks, Chunks, PipelineChunks};
+use crate::protocol::{
+    attach_source, check_source, CallInfo, CallInput, PluginCall, PluginResponse, PluginSource,
+};
+use std::path::PathBuf;
 
 use nu_protocol::engine::{Command, EngineState, Stack};
-use nu_protocol::{ast::Call, Signature, Value};
+use nu_protocol::{ast::Call, Signature};
 use nu_protocol::{PipelineData, ShellError};
 
 #[derive(Clone)]
@@ -33,6 +36,14 @@ impl PluginDeclaration {
             shell,
         }
     }
+
+    fn source(&self) -> PluginSource {
+        PluginSource {
+            filename: self.filename.clone(),
+            shell: self.shell.clone(),
+            encoding: self.encoding.clone(),
+        }
+    }
 }
 
 impl Command for PluginDeclaration {
@@ -58,100 +69,84 @@ impl Command for PluginDeclaration {
         // Call the command with self path
         // Decode information from plugin
         // Create PipelineData
-        let source_file = Path::new(&self.filename);
-        let mut plugin_cmd = create_command(source_file, &self.shell);
-
-        let mut child = plugin_cmd.spawn().map_err(|err| {
-            let decl = engine_state.get_decl(call.decl_id);
-            ShellError::SpannedLabeledError(
-                format!("Unable to spawn plugin for {}", decl.name()),
-                format!("{}", err),
-                call.head,
-            )
-        })?;
-
-        let input = match input {
-            PipelineData::Value(value, ..) => value,
-            PipelineData::ListStream(stream, ..) => {
-                let values = stream.collect::<Vec<Value>>();
-
-                Value::List {
-                    vals: values,
-                    span: call.head,
-                }
+        // Custom values can only be sent back to the plugin that created them
+        let source = self.source();
+        let context = PluginContext::new(engine_state, stack, source.clone(), call.head);
+        // The blocks sent to the plugin keep their captures when the plugin evaluates them
+        let block_captures = context.block_captures();
+
+        let (input, input_stream) = match into_chunks(input) {
+            PipelineChunks::Value(mut value) => {
+                check_source(&mut value, &source)?;
+                remember_captures(&value, &block_captures);
+                (CallInput::Value(value), None)
             }
-            PipelineData::StringStream(stream, ..) => {
-                let val = stream.into_string("")?;
-
-                Value::String {
-                    val,
-                    span: call.head,
-                }
-            }
-            PipelineData::ByteStream(stream, ..) => {
-                let val = stream.into_vec()?;
-
-                Value::Binary {
-                    val,
-                    span: call.head,
-                }
+            PipelineChunks::Stream(kind, chunks) => {
+                let chunk_source = source.clone();
+                let chunk_captures = block_captures.clone();
+                let chunks: Chunks = Box::new(chunks.map(move |chunk| {
+                    let mut value = chunk?;
+                    check_source(&mut value, &chunk_source)?;
+                    remember_captures(&value, &chunk_captures);
+                    Ok(value)
+                }));
+                (CallInput::Stream(kind), Some(chunks))
             }
         };
 
-        // Create message to plugin to indicate that signature is required and
-        // send call to plugin asking for signature
-        if let Some(mut stdin_writer) = child.stdin.take() {
-            let encoding_clone = self.encoding.clone();
-            let plugin_call = PluginCall::CallInfo(Box::new(CallInfo {
-                name: self.name.clone(),
-                call: EvaluatedCall::try_from_call(call, engine_state, stack)?,
-                input,
-            }));
-            std::thread::spawn(move || {
-                // PluginCall information
-                encoding_clone.encode_call(&plugin_call, &mut stdin_writer)
-            });
+        let mut evaluated_call = EvaluatedCall::try_from_call(call, engine_state, stack)?;
+        for value in evaluated_call.values_mut() {
+            check_source(value, &source)?;
+            remember_captures(value, &block_captures);
         }
 
-        // Deserialize response from plugin to extract the resulting value
-        let pipeline_data = if let Some(stdout_reader) = &mut child.stdout {
-            let reader = stdout_reader;
-            let mut buf_read = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, reader);
-
-            let response = self
-                .encoding
-                .decode_response(&mut buf_read)
-                .map_err(|err| {
-                    let decl = engine_state.get_decl(call.decl_id);
-                    ShellError::SpannedLabeledError(
-                        format!("Unable to decode call for {}", decl.name()),
-                        err.to_string(),
-                        call.head,
-                    )
-                })?;
+        let plugin_call = PluginCall::CallInfo(Box::new(CallInfo {
+            name: self.name.clone(),
+            call: evaluated_call,
+            input,
+        }));
+
+        // The call is sent to an idle process of the plugin. A new process is started
+        // if all the plugin's processes are busy
+        let output = call_plugin(
+            &self.filename,
+            &self.encoding,
+            &self.shell,
+            plugin_call,
+            input_stream,
+            Some(context),
+        )
+        .map_err(|err| {
+            let decl = engine_state.get_decl(call.decl_id);
+            ShellError::SpannedLabeledError(
+                format!("Unable to call plugin for {}", decl.name()),
+                err.to_string(),
+                call.head,
+            )
+        })?;
 
-            match response {
-                PluginResponse::Value(value) => {
-                    Ok(PipelineData::Value(value.as_ref().clone(), None))
-                }
-                PluginResponse::Error(err) => Err(err.into()),
-                PluginResponse::Signature(..) => Err(ShellError::SpannedLabeledError(
-                    "Plugin missing value".into(),
-                    "Received a signature from plugin instead of value".into(),
-                    call.head,
-                )),
+        match output {
+            PluginOutput::Response(PluginResponse::Value(mut value)) => {
+                attach_source(&mut value, &source);
+                Ok(PipelineData::Value(*value, None))
             }
-        } else {
-            Err(ShellError::SpannedLabeledError(
-                "Error with stdout reader".into(),
-                "no stdout reader".into(),
+            PluginOutput::Response(PluginResponse::Error(err)) => Err(err.into()),
+            PluginOutput::Stream(kind, stream) => Ok(from_chunks(
+                kind,
+                stream.map(move |chunk| {
+                    let mut value = chunk?;
+                    attach_source(&mut value, &source);
+                    Ok(value)
+                }),
                 call.head,
-            ))
-        }?;
-
-        // There is no need to wait for the child process to finish
-        // The response has been collected from the plugin call
-        Ok(pipeline_data)
+                engine_state.ctrlc.clone(),
+            )),
+            PluginOutput::Response(..) => Err(ShellError::SpannedLabeledError(
+                "Plugin missing value".into(),
+                "Received a signature from plugin instead of value".into(),
+                call.head,
+            )),
+        }
     }
 
     fn is_plugin(&self) -> Option<(&PathBuf, &str, &Option<PathBuf>)> {
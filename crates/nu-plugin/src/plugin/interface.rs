@@ -0,0 +1,209 @@
+use super::{PluginEncoder, OUTPUT_BUFFER_SIZE};
+use crate::protocol::{
+    deserialize_custom_values, serialize_custom_values, EngineCall, EngineCallResponse, EvalBlock,
+    PluginCall, PluginCallId, PluginResponse, ProtocolInfo,
+};
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Stdin, Write};
+use std::sync::{Arc, Mutex};
+
+use nu_protocol::{Config, ShellError, Value};
+
+// Chunks kept while waiting for an engine call. Nushell holds its input stream back while
+// it answers, so more chunks than this means it isn't doing so
+const MAX_PENDING_CHUNKS: usize = 4096;
+
+// Messages read by the plugin from nushell. The reader is shared by the main plugin
+// loop, the input streams and the engine calls. Chunks of an input stream that arrive
+// while the plugin waits for the answer to an engine call are kept until the input
+// stream is read. Nushell stops sending chunks until it answers, so only the chunks
+// that were already on their way are kept
+pub(crate) struct PluginInput<E> {
+    reader: BufReader<Stdin>,
+    encoder: E,
+    pending: VecDeque<(PluginCallId, PluginCall)>,
+}
+
+impl<E: PluginEncoder> PluginInput<E> {
+    pub fn new(encoder: E) -> Self {
+        Self {
+            reader: BufReader::with_capacity(OUTPUT_BUFFER_SIZE, std::io::stdin()),
+            encoder,
+            pending: VecDeque::new(),
+        }
+    }
+
+    // Returns None once nushell closes the plugin's stdin
+    pub fn read_call(&mut self) -> Option<Result<(PluginCallId, PluginCall), ShellError>> {
+        if let Some(call) = self.pending.pop_front() {
+            return Some(Ok(call));
+        }
+
+        match self.reader.fill_buf() {
+            Ok(buf) if !buf.is_empty() => Some(self.encoder.decode_call(&mut self.reader)),
+            _ => None,
+        }
+    }
+
+    fn read_engine_response(&mut self, id: PluginCallId) -> Result<EngineCallResponse, ShellError> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) if !buf.is_empty() => {}
+                _ => {
+                    return Err(ShellError::PluginFailedToDecode(
+                        "Nushell closed the plugin input before answering an engine call".into(),
+                    ))
+                }
+            }
+
+            if self.pending.len() >= MAX_PENDING_CHUNKS {
+                return Err(ShellError::PluginFailedToDecode(format!(
+                    "Received more than {} chunks of input while waiting for the answer to an engine call",
+                    MAX_PENDING_CHUNKS
+                )));
+            }
+
+            match self.encoder.decode_call(&mut self.reader)? {
+                (call_id, PluginCall::EngineCallResponse(response)) if call_id == id => {
+                    return Ok(*response)
+                }
+                (call_id, PluginCall::StreamData(value)) => self
+                    .pending
+                    .push_back((call_id, PluginCall::StreamData(value))),
+                (call_id, PluginCall::StreamEnd) => {
+                    self.pending.push_back((call_id, PluginCall::StreamEnd))
+                }
+                (call_id, _) => {
+                    return Err(ShellError::PluginFailedToDecode(format!(
+                        "Received call {} while waiting for the answer to an engine call",
+                        call_id
+                    )))
+                }
+            }
+        }
+    }
+}
+
+// Responses are written while stdout is locked, so engine calls made from other
+// threads of the plugin don't get mixed with them
+pub(crate) fn write_response(
+    encoder: &impl PluginEncoder,
+    id: PluginCallId,
+    response: &PluginResponse,
+) -> Result<(), ShellError> {
+    let stdout = std::io::stdout();
+    let mut stdout = stdout.lock();
+
+    encoder.encode_response(id, response, &mut stdout)?;
+    stdout
+        .flush()
+        .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))
+}
+
+pub(crate) trait EngineCaller: Send + Sync {
+    fn engine_call(
+        &self,
+        id: PluginCallId,
+        engine_call: EngineCall,
+    ) -> Result<EngineCallResponse, ShellError>;
+}
+
+impl<E: PluginEncoder + Send> EngineCaller for Mutex<PluginInput<E>> {
+    // The input stays locked until the answer arrives so only one engine call is
+    // waiting for an answer at a time
+    fn engine_call(
+        &self,
+        id: PluginCallId,
+        engine_call: EngineCall,
+    ) -> Result<EngineCallResponse, ShellError> {
+        let mut input = self
+            .lock()
+            .map_err(|_| ShellError::PluginFailedToDecode("Plugin input lock poisoned".into()))?;
+
+        let request = PluginResponse::EngineCall(Box::new(engine_call));
+        write_response(&input.encoder, id, &request)?;
+
+        input.read_engine_response(id)
+    }
+}
+
+// Handle that lets a plugin call nushell while it serves a call. It can be cloned and
+// moved into the iterators of an output stream, since the call lasts until its output
+// stream ends
+#[derive(Clone)]
+pub struct EngineInterface {
+    id: PluginCallId,
+    caller: Arc<dyn EngineCaller>,
+    engine_info: Option<Arc<ProtocolInfo>>,
+}
+
+impl EngineInterface {
+    pub(crate) fn new(
+        id: PluginCallId,
+        caller: Arc<dyn EngineCaller>,
+        engine_info: Option<Arc<ProtocolInfo>>,
+    ) -> Self {
+        Self {
+            id,
+            caller,
+            engine_info,
+        }
+    }
+
+    // Protocol version and features announced by nushell when the plugin started
+    pub fn protocol_info(&self) -> Option<&ProtocolInfo> {
+        self.engine_info.as_deref()
+    }
+
+    fn call(&self, engine_call: EngineCall) -> Result<Value, ShellError> {
+        match self.caller.engine_call(self.id, engine_call)? {
+            EngineCallResponse::Value(mut value) => {
+                deserialize_custom_values(&mut value)?;
+                Ok(*value)
+            }
+            EngineCallResponse::Error(err) => Err(err.into()),
+        }
+    }
+
+    // Evaluates a block received by the plugin, e.g. as an argument of the call. The
+    // positional values are bound to the block's parameters in order
+    pub fn eval_block(
+        &self,
+        block: &Value,
+        mut positional: Vec<Value>,
+        mut input: Value,
+    ) -> Result<Value, ShellError> {
+        for value in positional.iter_mut().chain(std::iter::once(&mut input)) {
+            serialize_custom_values(value)?;
+        }
+
+        self.call(EngineCall::EvalBlock(Box::new(EvalBlock {
+            block: block.clone(),
+            positional,
+            input,
+        })))
+    }
+
+    pub fn get_env_var(&self, name: &str) -> Result<Option<Value>, ShellError> {
+        match self.call(EngineCall::GetEnvVar(name.into()))? {
+            Value::Nothing { .. } => Ok(None),
+            value => Ok(Some(value)),
+        }
+    }
+
+    pub fn get_env_vars(&self) -> Result<HashMap<String, Value>, ShellError> {
+        let env_vars = self.call(EngineCall::GetEnvVars)?;
+        let (cols, vals) = env_vars.as_record()?;
+
+        Ok(cols.iter().cloned().zip(vals.iter().cloned()).collect())
+    }
+
+    pub fn get_config(&self) -> Result<Config, ShellError> {
+        self.call(EngineCall::GetConfig)?.into_config()
+    }
+
+    pub fn get_current_dir(&self) -> Result<String, ShellError> {
+        self.call(EngineCall::GetCurrentDir)?.as_string()
+    }
+}
@@ -1,30 +1,51 @@
+mod cache;
+mod context;
 mod declaration;
+mod interface;
+mod persistent;
+mod stream;
+pub use cache::{get_cached_signature, reload_signature};
 pub use declaration::PluginDeclaration;
+pub use interface::EngineInterface;
+pub use persistent::shutdown_plugins;
+pub(crate) use persistent::{call_plugin, PluginOutput};
 
-use crate::protocol::{LabeledError, PluginCall, PluginResponse};
+use crate::protocol::{
+    deserialize_custom_values, serialize_custom_values, CallInfo, CallInput, LabeledError,
+    PluginCall, PluginCallId, PluginResponse, ProtocolInfo,
+};
 use crate::EncodingType;
-use std::io::BufReader;
+use interface::{write_response, PluginInput};
 use std::path::{Path, PathBuf};
 use std::process::{Command as CommandSys, Stdio};
+use std::sync::{Arc, Mutex};
+use stream::{InputStream, PipelineChunks};
 
 use nu_protocol::ShellError;
-use nu_protocol::{Signature, Value};
+use nu_protocol::{PipelineData, Signature, Value};
 
 use super::EvaluatedCall;
 
 const OUTPUT_BUFFER_SIZE: usize = 8192;
 
+// Encoders read and write a single message at a time. Several messages can follow
+// each other in the same stream, each one tagged with the id of the call it belongs to
 pub trait PluginEncoder: Clone {
     fn encode_call(
         &self,
+        id: PluginCallId,
         plugin_call: &PluginCall,
         writer: &mut impl std::io::Write,
     ) -> Result<(), ShellError>;
 
-    fn decode_call(&self, reader: &mut impl std::io::BufRead) -> Result<PluginCall, ShellError>;
+    fn decode_call(
+        &self,
+        reader: &mut impl std::io::BufRead,
+    ) -> Result<(PluginCallId, PluginCall), ShellError>;
 
     fn encode_response(
         &self,
+        id: PluginCallId,
         plugin_response: &PluginResponse,
         writer: &mut impl std::io::Write,
     ) -> Result<(), ShellError>;
@@ -32,7 +53,7 @@ pub trait PluginEncoder: Clone {
     fn decode_response(
         &self,
         reader: &mut impl std::io::BufRead,
-    ) -> Result<PluginResponse, ShellError>;
+    ) -> Result<(PluginCallId, PluginResponse), ShellError>;
 }
 
 fn create_command(path: &Path, shell: &Option<PathBuf>) -> CommandSys {
@@ -71,8 +92,12 @@ fn create_command(path: &Path, shell: &Option<PathBuf>) -> CommandSys {
         (None, None) => std::process::Command::new(path),
     };
 
-    // Both stdout and stdin are piped so we can receive information from the plugin
-    process.stdout(Stdio::piped()).stdin(Stdio::piped());
+    // Both stdout and stdin are piped so we can receive information from the plugin.
+    // The stderr is captured too, it is shown when a call to the plugin fails
+    process
+        .stdout(Stdio::piped())
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped());
 
     process
 }
@@ -82,43 +107,18 @@ pub fn get_signature(
     encoding: &EncodingType,
     shell: &Option<PathBuf>,
 ) -> Result<Vec<Signature>, ShellError> {
-    let mut plugin_cmd = create_command(path, shell);
-
-    let mut child = plugin_cmd.spawn().map_err(|err| {
-        ShellError::PluginFailedToLoad(format!("Error spawning child process: {}", err))
-    })?;
-
-    // Create message to plugin to indicate that signature is required and
-    // send call to plugin asking for signature
-    if let Some(mut stdin_writer) = child.stdin.take() {
-        let encoding_clone = encoding.clone();
-        std::thread::spawn(move || {
-            encoding_clone.encode_call(&PluginCall::Signature, &mut stdin_writer)
-        });
-    }
+    // Asking for the signature always starts a new plugin process, that way a plugin
+    // that was rebuilt is picked up when it is registered again
+    persistent::stop_plugin(path, shell);
 
-    // deserialize response from plugin to extract the signature
-    let signatures = if let Some(stdout_reader) = &mut child.stdout {
-        let reader = stdout_reader;
-        let mut buf_read = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, reader);
-        let response = encoding.decode_response(&mut buf_read)?;
-
-        match response {
-            PluginResponse::Signature(sign) => Ok(sign),
-            PluginResponse::Error(err) => Err(err.into()),
-            _ => Err(ShellError::PluginFailedToLoad(
-                "Plugin missing signature".into(),
-            )),
-        }
-    } else {
-        Err(ShellError::PluginFailedToLoad(
-            "Plugin missing stdout reader".into(),
-        ))
-    }?;
-
-    match child.wait() {
-        Ok(_) => Ok(signatures),
-        Err(err) => Err(ShellError::PluginFailedToLoad(format!("{}", err))),
+    let output = persistent::call_plugin(path, encoding, shell, PluginCall::Signature, None, None)?;
+
+    match output {
+        PluginOutput::Response(PluginResponse::Signature(sign)) => Ok(sign),
+        PluginOutput::Response(PluginResponse::Error(err)) => Err(err.into()),
+        _ => Err(ShellError::PluginFailedToLoad(
+            "Plugin missing signature".into(),
+        )),
     }
 }
 
@@ -132,6 +132,24 @@ pub trait Plugin {
         call: &EvaluatedCall,
         input: &Value,
     ) -> Result<Value, LabeledError>;
+
+    // Plugins that work with streams or that need to call nushell implement this
+    // function. The input stream is read from nushell while the plugin consumes it and
+    // a stream returned by the plugin is sent back chunk by chunk. The engine interface
+    // evaluates blocks and reads the environment and the config of the caller.
+    // By default the input is collected and passed to `run`
+    fn run_stream(
+        &mut self,
+        _engine: &EngineInterface,
+        name: &str,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        let input = input.into_value(call.head);
+
+        self.run(name, call, &input)
+            .map(|value| PipelineData::Value(value, None))
+    }
 }
 
 // Function used in the plugin definition for the communication protocol between
@@ -145,44 +163,156 @@ pub trait Plugin {
 //
 // where plugin is your struct that implements the Plugin trait
 //
+// The plugin keeps answering calls until nushell sends a Goodbye call or closes
+// the plugin's stdin. Every response has to carry the id of the call it answers
+//
 // Note. When defining a plugin in other language but Rust, you will have to compile
 // the plugin.capnp schema to create the object definitions that will be returned from
 // the plugin.
 // The object that is expected to be received by nushell is the PluginResponse struct.
 // That should be encoded correctly and sent to StdOut for nushell to decode and
 // and present its result
-pub fn serve_plugin(plugin: &mut impl Plugin, encoder: impl PluginEncoder) {
-    let mut stdin_buf = BufReader::with_capacity(OUTPUT_BUFFER_SIZE, std::io::stdin());
-    let plugin_call = encoder.decode_call(&mut stdin_buf);
-
-    match plugin_call {
-        Err(err) => {
-            let response = PluginResponse::Error(err.into());
-            encoder
-                .encode_response(&response, &mut std::io::stdout())
-                .expect("Error encoding response");
-        }
-        Ok(plugin_call) => {
-            match plugin_call {
-                // Sending the signature back to nushell to create the declaration definition
-                PluginCall::Signature => {
-                    let response = PluginResponse::Signature(plugin.signature());
-                    encoder
-                        .encode_response(&response, &mut std::io::stdout())
-                        .expect("Error encoding response");
+pub fn serve_plugin(plugin: &mut impl Plugin, encoder: impl PluginEncoder + Send + 'static) {
+    // The input is shared with the input streams and the engine interfaces given to
+    // the plugin
+    let input = Arc::new(Mutex::new(PluginInput::new(encoder.clone())));
+    // Protocol spoken by nushell, received in its Hello call
+    let mut engine_info = None;
+
+    loop {
+        let call = input
+            .lock()
+            .expect("Plugin input lock poisoned")
+            .read_call();
+
+        let (id, plugin_call) = match call {
+            Some(Ok(call)) => call,
+            // Nushell closed the plugin's stdin, there won't be more calls
+            None => break,
+            Some(Err(err)) => {
+                // Without a call there is no id to answer to and no way to find where
+                // the next call starts. The error is reported and the plugin stops
+                let _ = write_response(&encoder, 0, &PluginResponse::Error(err.into()));
+                break;
+            }
+        };
+
+        let respond = |response: &PluginResponse| {
+            write_response(&encoder, id, response).expect("Error encoding response")
+        };
+
+        match plugin_call {
+            // The plugin always answers with its own protocol. Nushell checks that both
+            // are compatible and stops the plugin when they are not
+            PluginCall::Hello(info) => {
+                let plugin_info = ProtocolInfo::default();
+                respond(&PluginResponse::Hello(plugin_info.clone()));
+
+                if !plugin_info.is_compatible(&info) {
+                    break;
+                }
+                engine_info = Some(Arc::new(info));
+            }
+            // Sending the signature back to nushell to create the declaration definition
+            PluginCall::Signature => respond(&PluginResponse::Signature(plugin.signature())),
+            PluginCall::CallInfo(call_info) => {
+                let CallInfo {
+                    name,
+                    mut call,
+                    input: mut call_input,
+                } = *call_info;
+
+                // Custom values sent back by nushell are turned into the plugin's types
+                let decoded = call
+                    .values_mut()
+                    .try_for_each(deserialize_custom_values)
+                    .and_then(|_| match &mut call_input {
+                        CallInput::Value(value) => deserialize_custom_values(value),
+                        CallInput::Stream(..) => Ok(()),
+                    });
+
+                let (call_input, input_stream) = match call_input {
+                    CallInput::Value(value) => (PipelineData::Value(value, None), None),
+                    CallInput::Stream(kind) => {
+                        let input_stream = InputStream::new(id, input.clone());
+                        let chunks = input_stream.clone().map(|chunk| {
+                            let mut value = chunk?;
+                            deserialize_custom_values(&mut value)?;
+                            Ok(value)
+                        });
+                        let input = stream::from_chunks(kind, chunks, call.head, None);
+
+                        (input, Some(input_stream))
+                    }
+                };
+
+                let engine = EngineInterface::new(id, input.clone(), engine_info.clone());
+                let output = decoded
+                    .map_err(LabeledError::from)
+                    .and_then(|_| plugin.run_stream(&engine, &name, &call, call_input));
+
+                match output {
+                    Ok(output) => match stream::into_chunks(output) {
+                        PipelineChunks::Value(mut value) => {
+                            match serialize_custom_values(&mut value) {
+                                Ok(()) => respond(&PluginResponse::Value(Box::new(value))),
+                                Err(err) => respond(&PluginResponse::Error(err.into())),
+                            }
+                        }
+                        PipelineChunks::Stream(kind, chunks) => {
+                            respond(&PluginResponse::StreamStart(kind));
+
+                            // An error finishes the stream
+                            let mut end = PluginResponse::StreamEnd;
+                            for chunk in chunks {
+                                match chunk.and_then(|mut value| {
+                                    serialize_custom_values(&mut value)?;
+                                    Ok(value)
+                                }) {
+                                    Ok(value) => {
+                                        respond(&PluginResponse::StreamData(Box::new(value)))
+                                    }
+                                    Err(err) => {
+                                        end = PluginResponse::Error(err.into());
+                                        break;
+                                    }
+                                }
+                            }
+
+                            respond(&end);
+                        }
+                    },
+                    Err(err) => respond(&PluginResponse::Error(err)),
                 }
-                PluginCall::CallInfo(call_info) => {
-                    let value = plugin.run(&call_info.name, &call_info.call, &call_info.input);
-
-                    let response = match value {
-                        Ok(value) => PluginResponse::Value(Box::new(value)),
-                        Err(err) => PluginResponse::Error(err),
-                    };
-                    encoder
-                        .encode_response(&response, &mut std::io::stdout())
-                        .expect("Error encoding response");
+
+                // Whatever the plugin didn't read from its input is skipped so the
+                // next call can be read
+                if let Some(input_stream) = input_stream {
+                    input_stream.for_each(drop);
+                }
+            }
+            // Nushell asks for the base value of a custom value created by the plugin
+            PluginCall::CollapseCustomValue(mut value) => {
+                let base_value = deserialize_custom_values(&mut value).and_then(|_| match *value {
+                    Value::CustomValue { val, span } => {
+                        let mut base_value = val.to_base_value(span)?;
+                        serialize_custom_values(&mut base_value)?;
+                        Ok(base_value)
+                    }
+                    value => Ok(value),
+                });
+
+                match base_value {
+                    Ok(value) => respond(&PluginResponse::Value(Box::new(value))),
+                    Err(err) => respond(&PluginResponse::Error(err.into())),
                 }
             }
+            // Chunks of an input stream that already finished, or the answer to an
+            // engine call that is not waited for anymore
+            PluginCall::StreamData(..)
+            | PluginCall::StreamEnd
+            | PluginCall::EngineCallResponse(..) => {}
+            PluginCall::Goodbye => break,
         }
     }
 }
@@ -0,0 +1,716 @@
+use super::context::PluginContext;
+use super::stream::Chunks;
+use super::{create_command, OUTPUT_BUFFER_SIZE};
+use crate::protocol::{
+    EngineCallResponse, LabeledError, PluginCall, PluginCallId, PluginResponse, ProtocolInfo,
+    StreamKind,
+};
+use crate::EncodingType;
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, ChildStderr, ChildStdin, ChildStdout};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use nu_protocol::{ShellError, Value};
+
+// A plugin process is identified by the plugin's file, the encoding used to talk to it
+// and the shell used to run it
+type PluginKey = (PathBuf, &'static str, Option<PathBuf>);
+
+lazy_static! {
+    // Plugin processes that are kept alive waiting for their next call. A process is
+    // taken out of the pool while it serves a call, including its streams, and it is put
+    // back once the call is done. That way a plugin that is used twice in the same
+    // pipeline runs in two processes
+    static ref IDLE_PLUGINS: Mutex<HashMap<PluginKey, Vec<PersistentPlugin>>> =
+        Mutex::new(HashMap::new());
+}
+
+type StdinMessage = (PluginCallId, PluginCall, Option<Sender<()>>);
+
+// The plugin's stdin is written by a thread of its own. Calls, answers to engine calls
+// and the chunks of input streams are queued in the order they are sent, so none of them
+// waits on a lock held by a writer that is blocked on the pipe. A message can carry a
+// sender that is told once the message is written
+#[derive(Clone)]
+struct PluginStdin {
+    messages: Sender<StdinMessage>,
+    // Set when a message couldn't be written completely
+    failed: Arc<AtomicBool>,
+}
+
+impl PluginStdin {
+    fn spawn(stdin: ChildStdin, encoding: EncodingType) -> Self {
+        let (messages, receiver) = channel::<StdinMessage>();
+        let failed = Arc::new(AtomicBool::new(false));
+
+        let thread_failed = failed.clone();
+        std::thread::spawn(move || {
+            let mut stdin = BufWriter::with_capacity(OUTPUT_BUFFER_SIZE, stdin);
+
+            // Every message is flushed right away because the plugin may be waiting for it
+            for (id, plugin_call, written) in receiver {
+                let result = encoding
+                    .encode_call(id, &plugin_call, &mut stdin)
+                    .and_then(|_| {
+                        stdin
+                            .flush()
+                            .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))
+                    });
+
+                if result.is_err() {
+                    thread_failed.store(true, Ordering::SeqCst);
+                    break;
+                }
+
+                if let Some(written) = written {
+                    let _ = written.send(());
+                }
+            }
+        });
+
+        Self { messages, failed }
+    }
+
+    fn send(
+        &self,
+        id: PluginCallId,
+        plugin_call: PluginCall,
+        written: Option<Sender<()>>,
+    ) -> Result<(), ShellError> {
+        if self.has_failed() {
+            return Err(ShellError::PluginFailedToEncode(
+                "Unable to write to the plugin's stdin".into(),
+            ));
+        }
+
+        self.messages
+            .send((id, plugin_call, written))
+            .map_err(|_| ShellError::PluginFailedToEncode("Plugin stdin is closed".into()))
+    }
+
+    fn has_failed(&self) -> bool {
+        self.failed.load(Ordering::SeqCst)
+    }
+}
+
+// Number of lines of the plugin's stderr that are kept
+const STDERR_LINES: usize = 20;
+
+// The plugin's stderr is read from its own thread, so the plugin never blocks writing
+// to it. Only the last lines are kept. They are shown with the error when a call to
+// the plugin fails, e.g. the message of a plugin that panicked
+struct PluginStderr {
+    lines: Arc<Mutex<VecDeque<String>>>,
+    // Receives a message once the plugin closes its stderr
+    closed: Receiver<()>,
+}
+
+impl PluginStderr {
+    fn capture(stderr: ChildStderr) -> Self {
+        let lines = Arc::new(Mutex::new(VecDeque::new()));
+        let (sender, closed) = channel();
+
+        let thread_lines = lines.clone();
+        std::thread::spawn(move || {
+            let mut reader = BufReader::new(stderr);
+            let mut line = Vec::new();
+
+            while matches!(reader.read_until(b'\n', &mut line), Ok(read) if read > 0) {
+                if let Ok(mut lines) = thread_lines.lock() {
+                    if lines.len() == STDERR_LINES {
+                        lines.pop_front();
+                    }
+                    lines.push_back(String::from_utf8_lossy(&line).trim_end().to_string());
+                }
+                line.clear();
+            }
+
+            let _ = sender.send(());
+        });
+
+        Self { lines, closed }
+    }
+
+    // The process may still be writing when a call fails. A plugin that exited closes
+    // its stderr, the wait is short in case something else keeps it open
+    fn collect(&self) -> String {
+        let _ = self.closed.recv_timeout(Duration::from_millis(100));
+
+        self.lines
+            .lock()
+            .map(|lines| lines.iter().cloned().collect::<Vec<String>>().join("\n"))
+            .unwrap_or_default()
+    }
+}
+
+// Closed while nushell answers an engine call. The plugin keeps the chunks that arrive
+// while it waits for the answer, so the input writer holds the next chunks back until
+// the answer is sent
+#[derive(Clone, Default)]
+struct InputGate(Arc<(Mutex<bool>, Condvar)>);
+
+impl InputGate {
+    fn set_closed(&self, closed: bool) {
+        let (lock, condvar) = &*self.0;
+        if let Ok(mut is_closed) = lock.lock() {
+            *is_closed = closed;
+            condvar.notify_all();
+        }
+    }
+
+    fn wait_open(&self) {
+        let (lock, condvar) = &*self.0;
+        if let Ok(mut is_closed) = lock.lock() {
+            while *is_closed {
+                is_closed = match condvar.wait(is_closed) {
+                    Ok(is_closed) => is_closed,
+                    Err(_) => return,
+                };
+            }
+        }
+    }
+}
+
+// Thread that sends the input stream of a call to the plugin. It isn't joined, as it
+// may be waiting for a chunk that never comes. It tells whether the whole stream was
+// written once it is done
+struct InputWriter {
+    finished: Receiver<bool>,
+    stop: Arc<AtomicBool>,
+}
+
+// Time given to the input writer to finish once the plugin answered the call
+const INPUT_FINISH_TIMEOUT: Duration = Duration::from_millis(100);
+
+// Time given to a plugin to exit after it was told goodbye, before it is killed
+const GOODBYE_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Time given to a killed plugin to exit. A process that is still around after that is
+// left behind rather than blocking nushell
+const KILL_TIMEOUT: Duration = Duration::from_millis(500);
+
+// Waits for the process to exit, for at most the given time. Tells whether it exited
+fn wait_for_exit(child: &mut Child, timeout: Duration) -> bool {
+    let start = Instant::now();
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) | Err(_) => return true,
+            Ok(None) if start.elapsed() >= timeout => return false,
+            Ok(None) => std::thread::sleep(Duration::from_millis(10)),
+        }
+    }
+}
+
+// A long lived plugin process. Calls are written to the plugin's stdin one after the
+// other and each response is read back from its stdout before the next call is sent
+pub(crate) struct PersistentPlugin {
+    key: PluginKey,
+    child: Child,
+    stdin: PluginStdin,
+    // Set when the plugin may have received part of a message or stream
+    broken: bool,
+    input: Option<InputWriter>,
+    input_gate: InputGate,
+    stdout: BufReader<ChildStdout>,
+    stderr: PluginStderr,
+    encoding: EncodingType,
+    next_id: PluginCallId,
+}
+
+impl PersistentPlugin {
+    fn spawn(
+        path: &Path,
+        encoding: &EncodingType,
+        shell: &Option<PathBuf>,
+    ) -> Result<Self, ShellError> {
+        let mut plugin_cmd = create_command(path, shell);
+
+        let mut child = plugin_cmd.spawn().map_err(|err| {
+            ShellError::PluginFailedToLoad(format!("Error spawning child process: {}", err))
+        })?;
+
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| ShellError::PluginFailedToLoad("Plugin missing stdin writer".into()))?;
+
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| ShellError::PluginFailedToLoad("Plugin missing stdout reader".into()))?;
+
+        let stderr = child
+            .stderr
+            .take()
+            .ok_or_else(|| ShellError::PluginFailedToLoad("Plugin missing stderr reader".into()))?;
+
+        let mut plugin = Self {
+            key: (path.to_path_buf(), encoding.to_str(), shell.clone()),
+            child,
+            stdin: PluginStdin::spawn(stdin, encoding.clone()),
+            broken: false,
+            input: None,
+            input_gate: InputGate::default(),
+            stdout: BufReader::with_capacity(OUTPUT_BUFFER_SIZE, stdout),
+            stderr: PluginStderr::capture(stderr),
+            encoding: encoding.clone(),
+            next_id: 0,
+        };
+
+        if let Err(err) = plugin.handshake() {
+            return Err(plugin.fail(err));
+        }
+
+        Ok(plugin)
+    }
+
+    // Both sides say which protocol they speak before any other call is sent. A plugin
+    // that was built for another version of nushell can't be used
+    fn handshake(&mut self) -> Result<(), ShellError> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let info = ProtocolInfo::default();
+        self.send(id, PluginCall::Hello(info.clone()))?;
+
+        let name = self.key.0.display().to_string();
+        let no_hello = |reason: String| {
+            ShellError::PluginIncompatible(
+                format!("{} did not answer the protocol handshake: {}", name, reason),
+                "The plugin may have been built for an older version of nushell".into(),
+            )
+        };
+
+        match self.receive(id, &mut None) {
+            Ok(PluginResponse::Hello(plugin_info)) if info.is_compatible(&plugin_info) => Ok(()),
+            Ok(PluginResponse::Hello(plugin_info)) => Err(ShellError::PluginIncompatible(
+                format!(
+                    "{} uses {} protocol version {} but nushell uses {} protocol version {}",
+                    name, plugin_info.protocol, plugin_info.version, info.protocol, info.version
+                ),
+                "Rebuild the plugin with the nu-plugin crate of this version of nushell".into(),
+            )),
+            Ok(PluginResponse::Error(err)) => Err(no_hello(err.msg)),
+            Ok(_) => Err(no_hello("unexpected response".into())),
+            Err(err) => Err(no_hello(err.to_string())),
+        }
+    }
+
+    fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    // After a failed exchange there is no way to know where the next message starts,
+    // so the process is stopped and the next call will start a new one
+    fn kill(&mut self) {
+        let _ = self.child.kill();
+        wait_for_exit(&mut self.child, KILL_TIMEOUT);
+    }
+
+    // Stops the process after a failed exchange. What the plugin wrote to its stderr
+    // is attached to the error
+    fn fail(&mut self, err: ShellError) -> ShellError {
+        self.kill();
+
+        let stderr = self.stderr.collect();
+        if stderr.is_empty() {
+            err
+        } else {
+            ShellError::PluginFailed(err.to_string(), stderr)
+        }
+    }
+
+    fn send(&mut self, id: PluginCallId, plugin_call: PluginCall) -> Result<(), ShellError> {
+        let sent = self.stdin.send(id, plugin_call, None);
+        if sent.is_err() {
+            self.broken = true;
+        }
+
+        sent
+    }
+
+    // Engine calls made by the plugin are answered until the plugin sends a response
+    // for the call. Without a context the engine calls are answered with an error.
+    // Ctrl-c is checked before each message is read, the plugin is stopped by the caller
+    // once the error is returned
+    fn receive(
+        &mut self,
+        id: PluginCallId,
+        context: &mut Option<PluginContext>,
+    ) -> Result<PluginResponse, ShellError> {
+        loop {
+            if context
+                .as_ref()
+                .map_or(false, PluginContext::is_interrupted)
+            {
+                return Err(ShellError::PluginInterrupted);
+            }
+
+            let (response_id, response) = self.encoding.decode_response(&mut self.stdout)?;
+
+            if response_id != id {
+                return Err(ShellError::PluginFailedToDecode(format!(
+                    "Plugin answered call {} while waiting for call {}",
+                    response_id, id
+                )));
+            }
+
+            let engine_call = match response {
+                PluginResponse::EngineCall(engine_call) => engine_call,
+                response => return Ok(response),
+            };
+
+            self.input_gate.set_closed(true);
+
+            let engine_response = match context {
+                Some(context) => context.engine_call(*engine_call),
+                None => EngineCallResponse::Error(LabeledError {
+                    label: "Engine call not available".into(),
+                    msg: "the plugin can only call nushell while it runs a command".into(),
+                    span: None,
+                    labels: vec![],
+                    help: None,
+                }),
+            };
+
+            let sent = self.send(
+                id,
+                PluginCall::EngineCallResponse(Box::new(engine_response)),
+            );
+
+            self.input_gate.set_closed(false);
+            sent?;
+        }
+    }
+
+    // The chunks are sent from their own thread so the plugin can answer while it is
+    // still reading its input. Each chunk is written before the next one is taken, so
+    // the stream isn't read faster than the plugin reads it
+    fn send_input(&mut self, id: PluginCallId, mut chunks: Chunks) {
+        let stdin = self.stdin.clone();
+        let gate = self.input_gate.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let (finished_sender, finished) = channel();
+
+        let thread_stop = stop.clone();
+        std::thread::spawn(move || {
+            // The sender is dropped with the message if the plugin's stdin is closed
+            let send = |plugin_call| {
+                let (written_sender, written) = channel();
+                stdin.send(id, plugin_call, Some(written_sender))?;
+                written
+                    .recv()
+                    .map_err(|_| ShellError::PluginFailedToEncode("Plugin stdin is closed".into()))
+            };
+
+            loop {
+                // No chunk is taken while the plugin waits for an engine call
+                gate.wait_open();
+                if thread_stop.load(Ordering::SeqCst) {
+                    break;
+                }
+
+                let chunk = match chunks.next() {
+                    Some(chunk) => chunk,
+                    None => break,
+                };
+
+                // Errors in the input are sent to the plugin as error values
+                let chunk = chunk.unwrap_or_else(|error| Value::Error { error });
+                if send(PluginCall::StreamData(Box::new(chunk))).is_err() {
+                    let _ = finished_sender.send(false);
+                    return;
+                }
+            }
+
+            let _ = finished_sender.send(send(PluginCall::StreamEnd).is_ok());
+        });
+
+        self.input = Some(InputWriter { finished, stop });
+    }
+
+    // Once the plugin has answered there is no need to keep sending the input stream.
+    // A process whose input stream didn't end can't tell where the next call starts,
+    // so it isn't used again
+    fn finish_input(&mut self) {
+        if let Some(input) = self.input.take() {
+            input.stop.store(true, Ordering::SeqCst);
+            if !matches!(input.finished.recv_timeout(INPUT_FINISH_TIMEOUT), Ok(true)) {
+                self.broken = true;
+            }
+        }
+    }
+
+    // Puts the process back in the pool once the call is done
+    fn release(mut self) {
+        self.finish_input();
+
+        if !self.broken && !self.stdin.has_failed() && self.is_running() {
+            if let Ok(mut plugins) = IDLE_PLUGINS.lock() {
+                plugins.entry(self.key.clone()).or_default().push(self);
+            }
+        }
+    }
+
+    fn shutdown(&mut self) {
+        if self.is_running() {
+            if self.broken || self.input.is_some() || self.stdin.has_failed() {
+                self.kill()
+            } else {
+                let _ = self.stdin.send(self.next_id, PluginCall::Goodbye, None);
+                if !wait_for_exit(&mut self.child, GOODBYE_TIMEOUT) {
+                    self.kill();
+                }
+            }
+        }
+    }
+}
+
+impl Drop for PersistentPlugin {
+    fn drop(&mut self) {
+        self.shutdown()
+    }
+}
+
+// Takes an idle process for the plugin out of the pool. A new process is started if
+// there is no idle process running with the same encoding and shell
+fn checkout(
+    path: &Path,
+    encoding: &EncodingType,
+    shell: &Option<PathBuf>,
+) -> Result<PersistentPlugin, ShellError> {
+    let idle = IDLE_PLUGINS
+        .lock()
+        .map_err(|_| ShellError::PluginFailedToLoad("Running plugins lock poisoned".into()))?
+        .get_mut(&(path.to_path_buf(), encoding.to_str(), shell.clone()))
+        .and_then(|plugins| {
+            while let Some(mut plugin) = plugins.pop() {
+                if plugin.is_running() {
+                    return Some(plugin);
+                }
+            }
+
+            None
+        });
+
+    match idle {
+        Some(plugin) => Ok(plugin),
+        None => PersistentPlugin::spawn(path, encoding, shell),
+    }
+}
+
+pub(crate) enum PluginOutput {
+    Response(PluginResponse),
+    Stream(StreamKind, Box<PluginStream>),
+}
+
+// Stream of chunks answered by the plugin. The process serving the call is owned by
+// the stream and it goes back to the pool when the stream ends
+pub(crate) struct PluginStream {
+    id: PluginCallId,
+    plugin: Option<PersistentPlugin>,
+    context: Option<PluginContext>,
+}
+
+impl PluginStream {
+    fn finish(&mut self) {
+        if let Some(plugin) = self.plugin.take() {
+            plugin.release();
+        }
+    }
+
+    fn kill(&mut self) {
+        if let Some(mut plugin) = self.plugin.take() {
+            plugin.kill();
+        }
+    }
+
+    fn fail(&mut self, err: ShellError) -> ShellError {
+        match self.plugin.take() {
+            Some(mut plugin) => plugin.fail(err),
+            None => err,
+        }
+    }
+}
+
+impl Iterator for PluginStream {
+    type Item = Result<Value, ShellError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let plugin = self.plugin.as_mut()?;
+
+        match plugin.receive(self.id, &mut self.context) {
+            Ok(PluginResponse::StreamData(value)) => Some(Ok(*value)),
+            Ok(PluginResponse::StreamEnd) => {
+                self.finish();
+                None
+            }
+            Ok(PluginResponse::Error(err)) => {
+                self.finish();
+                Some(Err(err.into()))
+            }
+            Ok(_) => Some(Err(self.fail(ShellError::PluginFailedToDecode(
+                "Plugin sent a response in the middle of a stream".into(),
+            )))),
+            Err(err) => Some(Err(self.fail(err))),
+        }
+    }
+}
+
+impl Drop for PluginStream {
+    fn drop(&mut self) {
+        // The stream was not read until its end and the rest of its chunks are still
+        // waiting in the plugin's stdout
+        self.kill()
+    }
+}
+
+// Sends the call to an idle process of the plugin, followed by the chunks of its
+// input stream if there is one. The context answers the engine calls made by the
+// plugin, until the end of its output stream
+pub(crate) fn call_plugin(
+    path: &Path,
+    encoding: &EncodingType,
+    shell: &Option<PathBuf>,
+    plugin_call: PluginCall,
+    input: Option<Chunks>,
+    mut context: Option<PluginContext>,
+) -> Result<PluginOutput, ShellError> {
+    let mut plugin = checkout(path, encoding, shell)?;
+
+    let id = plugin.next_id;
+    plugin.next_id += 1;
+
+    if let Err(err) = plugin.send(id, plugin_call) {
+        return Err(plugin.fail(err));
+    }
+
+    if let Some(input) = input {
+        plugin.send_input(id, input);
+    }
+
+    match plugin.receive(id, &mut context) {
+        Ok(PluginResponse::StreamStart(kind)) => Ok(PluginOutput::Stream(
+            kind,
+            Box::new(PluginStream {
+                id,
+                plugin: Some(plugin),
+                context,
+            }),
+        )),
+        Ok(response) => {
+            plugin.release();
+            Ok(PluginOutput::Response(response))
+        }
+        Err(err) => Err(plugin.fail(err)),
+    }
+}
+
+// Stops the idle processes of the plugin, whatever their encoding. The next call to the
+// plugin starts a new process
+pub(crate) fn stop_plugin(path: &Path, shell: &Option<PathBuf>) {
+    if let Ok(mut plugins) = IDLE_PLUGINS.lock() {
+        plugins.retain(|(plugin_path, _, plugin_shell), _| {
+            plugin_path != path || plugin_shell != shell
+        });
+    }
+}
+
+// Says goodbye to every idle plugin and waits a bit for their processes to exit.
+// Used by nushell before exiting
+pub fn shutdown_plugins() {
+    if let Ok(mut plugins) = IDLE_PLUGINS.lock() {
+        plugins.clear();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::protocol::PluginSource;
+    use nu_protocol::engine::{EngineState, Stack};
+    use nu_protocol::Span;
+    use std::process::{Command, Stdio};
+
+    // A process that never answers, standing in for a plugin that hangs
+    fn unresponsive_plugin(path: &str) -> PersistentPlugin {
+        let mut child = Command::new("sleep")
+            .arg("30")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .expect("unable to spawn sleep");
+
+        let encoding = EncodingType::try_from_bytes(b"json").expect("json encoding");
+        let stdin = child.stdin.take().expect("sleep stdin");
+        let stdout = child.stdout.take().expect("sleep stdout");
+        let stderr = child.stderr.take().expect("sleep stderr");
+
+        PersistentPlugin {
+            key: (PathBuf::from(path), encoding.to_str(), None),
+            child,
+            stdin: PluginStdin::spawn(stdin, encoding.clone()),
+            broken: false,
+            input: None,
+            input_gate: InputGate::default(),
+            stdout: BufReader::new(stdout),
+            stderr: PluginStderr::capture(stderr),
+            encoding,
+            next_id: 0,
+        }
+    }
+
+    #[test]
+    fn idle_plugin_is_kept_for_its_own_encoding() {
+        let path = Path::new("/nonexistent/nu_plugin_encoding_test");
+        unresponsive_plugin(&path.to_string_lossy()).release();
+
+        let msgpack = EncodingType::try_from_bytes(b"msgpack").expect("msgpack encoding");
+        assert!(checkout(path, &msgpack, &None).is_err());
+
+        let json = EncodingType::try_from_bytes(b"json").expect("json encoding");
+        let mut plugin = checkout(path, &json, &None).expect("idle json plugin");
+        assert!(plugin.is_running());
+        plugin.kill();
+    }
+
+    #[test]
+    fn receive_stops_on_ctrlc() {
+        let mut plugin = unresponsive_plugin("/nonexistent/nu_plugin_ctrlc_test");
+
+        let mut engine_state = EngineState::new();
+        engine_state.ctrlc = Some(Arc::new(AtomicBool::new(true)));
+        let source = PluginSource {
+            filename: plugin.key.0.clone(),
+            shell: None,
+            encoding: plugin.encoding.clone(),
+        };
+        let context = PluginContext::new(&engine_state, &Stack::new(), source, Span::test_data());
+
+        assert!(matches!(
+            plugin.receive(0, &mut Some(context)),
+            Err(ShellError::PluginInterrupted)
+        ));
+        plugin.kill();
+    }
+
+    #[test]
+    fn shutdown_kills_a_plugin_that_ignores_goodbye() {
+        let mut plugin = unresponsive_plugin("/nonexistent/nu_plugin_goodbye_test");
+
+        let start = Instant::now();
+        plugin.shutdown();
+
+        assert!(!plugin.is_running());
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+}
@@ -0,0 +1,154 @@
+use super::interface::PluginInput;
+use super::PluginEncoder;
+use crate::protocol::{PluginCall, PluginCallId, StreamKind};
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use nu_protocol::{ByteStream, PipelineData, ShellError, Span, StringStream, Value, ValueStream};
+
+pub(crate) type Chunks = Box<dyn Iterator<Item = Result<Value, ShellError>> + Send + 'static>;
+
+// Pipeline data as it is sent through the plugin protocol. Streams are sent one chunk
+// at a time: string streams as string values and byte streams as binary values
+pub(crate) enum PipelineChunks {
+    Value(Value),
+    Stream(StreamKind, Chunks),
+}
+
+pub(crate) fn into_chunks(data: PipelineData) -> PipelineChunks {
+    match data {
+        PipelineData::Value(value, ..) => PipelineChunks::Value(value),
+        PipelineData::ListStream(stream, ..) => {
+            PipelineChunks::Stream(StreamKind::List, Box::new(stream.map(Ok)))
+        }
+        PipelineData::StringStream(stream, span, ..) => PipelineChunks::Stream(
+            StreamKind::String,
+            Box::new(stream.map(move |chunk| chunk.map(|val| Value::String { val, span }))),
+        ),
+        PipelineData::ByteStream(stream, span, ..) => PipelineChunks::Stream(
+            StreamKind::Binary,
+            Box::new(stream.map(move |chunk| chunk.map(|val| Value::Binary { val, span }))),
+        ),
+        PipelineData::ExternalStream(stream, span, metadata) => into_chunks(
+            PipelineData::ByteStream(stream.into_stdout(span), span, metadata),
+        ),
+    }
+}
+
+// Builds lazy pipeline data out of the chunks of a stream
+pub(crate) fn from_chunks(
+    kind: StreamKind,
+    chunks: impl Iterator<Item = Result<Value, ShellError>> + Send + 'static,
+    span: Span,
+    ctrlc: Option<Arc<AtomicBool>>,
+) -> PipelineData {
+    match kind {
+        StreamKind::List => PipelineData::ListStream(
+            ValueStream::from_stream(
+                chunks.map(|chunk| chunk.unwrap_or_else(|error| Value::Error { error })),
+                ctrlc,
+            ),
+            None,
+        ),
+        StreamKind::String => PipelineData::StringStream(
+            StringStream::from_stream(
+                chunks.map(|chunk| match chunk? {
+                    Value::Error { error } => Err(error),
+                    value => value.as_string(),
+                }),
+                ctrlc,
+            ),
+            span,
+            None,
+        ),
+        StreamKind::Binary => PipelineData::ByteStream(
+            ByteStream {
+                stream: Box::new(chunks.map(move |chunk| match chunk? {
+                    Value::Binary { val, .. } => Ok(val),
+                    Value::Error { error } => Err(error),
+                    value => Err(ShellError::CantConvert(
+                        "binary".into(),
+                        value.get_type().to_string(),
+                        value.span().unwrap_or(span),
+                    )),
+                })),
+                ctrlc,
+            },
+            span,
+            None,
+        ),
+    }
+}
+
+// Input stream of a call as seen by the plugin. Its chunks are read from stdin while
+// the plugin consumes the stream, sharing the reader with the main plugin loop
+pub(crate) struct InputStream<E> {
+    id: PluginCallId,
+    input: Arc<Mutex<PluginInput<E>>>,
+    finished: Arc<AtomicBool>,
+}
+
+impl<E> Clone for InputStream<E> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id,
+            input: self.input.clone(),
+            finished: self.finished.clone(),
+        }
+    }
+}
+
+impl<E: PluginEncoder> InputStream<E> {
+    pub fn new(id: PluginCallId, input: Arc<Mutex<PluginInput<E>>>) -> Self {
+        Self {
+            id,
+            input,
+            finished: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    fn read_chunk(&self) -> Result<Option<Value>, ShellError> {
+        let mut input = self
+            .input
+            .lock()
+            .map_err(|_| ShellError::PluginFailedToDecode("Plugin input lock poisoned".into()))?;
+
+        // Nushell closed the plugin's stdin before ending the stream
+        let call = match input.read_call() {
+            Some(call) => call?,
+            None => return Ok(None),
+        };
+
+        match call {
+            (id, PluginCall::StreamData(value)) if id == self.id => Ok(Some(*value)),
+            (id, PluginCall::StreamEnd) if id == self.id => Ok(None),
+            (id, _) => Err(ShellError::PluginFailedToDecode(format!(
+                "Received call {} while reading the input stream of call {}",
+                id, self.id
+            ))),
+        }
+    }
+}
+
+impl<E: PluginEncoder> Iterator for InputStream<E> {
+    type Item = Result<Value, ShellError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        match self.read_chunk() {
+            Ok(Some(value)) => Some(Ok(value)),
+            Ok(None) => {
+                self.finished.store(true, Ordering::SeqCst);
+                None
+            }
+            Err(err) => {
+                self.finished.store(true, Ordering::SeqCst);
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -46,6 +46,15 @@ impl EvaluatedCall {
         })
     }
 
+    // Values of the positional and named arguments
+    pub(crate) fn values_mut(&mut self) -> impl Iterator<Item = &mut Value> {
+        self.positional.iter_mut().chain(
+            self.named
+                .iter_mut()
+                .filter_map(|(_, value)| value.as_mut()),
+        )
+    }
+
     pub fn has_flag(&self, flag_name: &str) -> bool {
         for name in &self.named {
             if flag_name == name.0.item {
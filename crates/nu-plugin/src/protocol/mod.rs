@@ -1,35 +1,201 @@
 mod evaluated_call;
+mod plugin_custom_value;
 
 pub use evaluated_call::EvaluatedCall;
 use nu_protocol::{ShellError, Signature, Span, Value};
+pub(crate) use plugin_custom_value::{
+    attach_source, check_source, deserialize_custom_values, serialize_custom_values,
+};
+pub use plugin_custom_value::{PluginCustomValue, PluginSource};
 use serde::{Deserialize, Serialize};
 
+// Id attached to every call sent to a plugin. The plugin answers each call with
+// the same id so a single plugin process can serve many calls one after the other
+pub type PluginCallId = usize;
+
+// Version of the plugin protocol. It changes whenever a message changes in a way that
+// an older nushell or an older plugin can't read
+pub const PROTOCOL_VERSION: u32 = 1;
+
+const PROTOCOL_NAME: &str = "nu-plugin";
+
+// Sent by both sides before any other message. Nushell only talks to plugins that use
+// the same protocol version. Features are optional parts of the protocol that can be
+// added without changing its version, a side ignores the features it doesn't know
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct ProtocolInfo {
+    pub protocol: String,
+    pub version: u32,
+    pub features: Vec<String>,
+}
+
+impl Default for ProtocolInfo {
+    fn default() -> Self {
+        Self {
+            protocol: PROTOCOL_NAME.into(),
+            version: PROTOCOL_VERSION,
+            features: vec![
+                "streams".into(),
+                "custom-values".into(),
+                "engine-calls".into(),
+            ],
+        }
+    }
+}
+
+impl ProtocolInfo {
+    pub fn is_compatible(&self, other: &ProtocolInfo) -> bool {
+        self.protocol == other.protocol && self.version == other.version
+    }
+
+    pub fn supports(&self, feature: &str) -> bool {
+        self.features.iter().any(|supported| supported == feature)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct CallInfo {
     pub name: String,
     pub call: EvaluatedCall,
-    pub input: Value,
+    pub input: CallInput,
+}
+
+// The kind of pipeline data that is sent as a stream. Every chunk of the stream is
+// sent as a Value: any value for a list stream, strings for a string stream and
+// binary values for a byte stream
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize, Debug)]
+pub enum StreamKind {
+    List,
+    String,
+    Binary,
+}
+
+// Input of a plugin call. When the input is a stream, its chunks are sent after
+// the call using StreamData calls with the same id, followed by a StreamEnd call
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub enum CallInput {
+    Value(Value),
+    Stream(StreamKind),
 }
 
 // Information sent to the plugin
 #[derive(Serialize, Deserialize, Debug)]
 pub enum PluginCall {
+    // Sent before any other call. The plugin answers with its own ProtocolInfo
+    Hello(ProtocolInfo),
     Signature,
     CallInfo(Box<CallInfo>),
+    // Sent when nushell is done with the plugin. The plugin process should exit
+    Goodbye,
+    // A chunk of the input stream of a call
+    StreamData(Box<Value>),
+    // The input stream of a call has no more chunks
+    StreamEnd,
+    // Asks the plugin for the base value of one of its custom values
+    CollapseCustomValue(Box<Value>),
+    // Nushell's answer to an engine call made by the plugin
+    EngineCallResponse(Box<EngineCallResponse>),
+}
+
+// Requests that a plugin can send to nushell while it serves a call. They are sent
+// with the id of the call and nushell answers each one with an EngineCallResponse
+#[derive(Serialize, Deserialize, Debug)]
+pub enum EngineCall {
+    EvalBlock(Box<EvalBlock>),
+    GetEnvVar(String),
+    GetEnvVars,
+    GetConfig,
+    GetCurrentDir,
 }
 
+// Evaluates a block value received by the plugin. The positional values are bound to
+// the block's parameters and the input is used as the block's pipeline input
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EvalBlock {
+    pub block: Value,
+    pub positional: Vec<Value>,
+    pub input: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum EngineCallResponse {
+    Value(Box<Value>),
+    Error(LabeledError),
+}
+
+// Error returned by a plugin. The message is shown next to the span. Other spans that
+// are part of the error can be labelled too, and the help text is shown below them
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
 pub struct LabeledError {
     pub label: String,
     pub msg: String,
     pub span: Option<Span>,
+    #[serde(default)]
+    pub labels: Vec<ErrorLabel>,
+    #[serde(default)]
+    pub help: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+pub struct ErrorLabel {
+    pub text: String,
+    pub span: Span,
+}
+
+impl LabeledError {
+    pub fn new(label: impl Into<String>, msg: impl Into<String>, span: Option<Span>) -> Self {
+        Self {
+            label: label.into(),
+            msg: msg.into(),
+            span,
+            labels: vec![],
+            help: None,
+        }
+    }
+
+    pub fn with_label(mut self, text: impl Into<String>, span: Span) -> Self {
+        self.labels.push(ErrorLabel {
+            text: text.into(),
+            span,
+        });
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
 }
 
 impl From<LabeledError> for ShellError {
     fn from(error: LabeledError) -> Self {
-        match error.span {
-            Some(span) => ShellError::SpannedLabeledError(error.label, error.msg, span),
-            None => ShellError::LabeledError(error.label, error.msg),
+        if !error.labels.is_empty() {
+            // The other labels are shown as related errors pointing at their spans
+            let label = &error.label;
+            let related = error
+                .labels
+                .into_iter()
+                .map(|other| ShellError::SpannedLabeledError(label.clone(), other.text, other.span))
+                .collect();
+
+            return ShellError::GenericError(
+                error.label,
+                error.msg,
+                error.span,
+                error.help,
+                related,
+            );
+        }
+
+        match (error.span, error.help) {
+            (Some(span), None) => ShellError::SpannedLabeledError(error.label, error.msg, span),
+            (None, None) => ShellError::LabeledError(error.label, error.msg),
+            (Some(span), Some(help)) => {
+                ShellError::SpannedLabeledErrorHelp(error.label, error.msg, span, help)
+            }
+            (None, Some(help)) => {
+                ShellError::GenericError(error.label, error.msg, None, Some(help), vec![])
+            }
         }
     }
 }
@@ -37,54 +203,77 @@ impl From<LabeledError> for ShellError {
 impl From<ShellError> for LabeledError {
     fn from(error: ShellError) -> Self {
         match error {
-            ShellError::SpannedLabeledError(label, msg, span) => LabeledError {
-                label,
-                msg,
-                span: Some(span),
-            },
-            ShellError::LabeledError(label, msg) => LabeledError {
-                label,
-                msg,
-                span: None,
-            },
-            ShellError::CantConvert(expected, input, span) => LabeledError {
-                label: format!("Can't convert to {}", expected),
-                msg: format!("can't convert {} to {}", expected, input),
-                span: Some(span),
-            },
-            ShellError::DidYouMean(suggestion, span) => LabeledError {
-                label: "Name not found".into(),
-                msg: format!("did you mean '{}'", suggestion),
-                span: Some(span),
-            },
-            ShellError::PluginFailedToLoad(msg) => LabeledError {
-                label: "Plugin failed to load".into(),
-                msg,
-                span: None,
-            },
-            ShellError::PluginFailedToEncode(msg) => LabeledError {
-                label: "Plugin failed to encode".into(),
-                msg,
-                span: None,
-            },
-            ShellError::PluginFailedToDecode(msg) => LabeledError {
-                label: "Plugin failed to decode".into(),
-                msg,
-                span: None,
-            },
-            err => LabeledError {
-                label: "Error - Add to LabeledError From<ShellError>".into(),
-                msg: err.to_string(),
-                span: None,
-            },
+            ShellError::SpannedLabeledError(label, msg, span) => {
+                LabeledError::new(label, msg, Some(span))
+            }
+            ShellError::LabeledError(label, msg) => LabeledError::new(label, msg, None),
+            ShellError::SpannedLabeledErrorHelp(label, msg, span, help) => {
+                LabeledError::new(label, msg, Some(span)).with_help(help)
+            }
+            ShellError::GenericError(label, msg, span, help, related) => {
+                let mut error = LabeledError::new(label, msg, span);
+                error.help = help;
+
+                // Related errors that point at a span become labels of the error
+                for related in related {
+                    match related {
+                        ShellError::SpannedLabeledError(_, text, span) => {
+                            error = error.with_label(text, span)
+                        }
+                        related => {
+                            error.msg = format!("{}\n{}", error.msg, related);
+                        }
+                    }
+                }
+
+                error
+            }
+            ShellError::CantConvert(expected, input, span) => LabeledError::new(
+                format!("Can't convert to {}", expected),
+                format!("can't convert {} to {}", expected, input),
+                Some(span),
+            ),
+            ShellError::DidYouMean(suggestion, span) => LabeledError::new(
+                "Name not found",
+                format!("did you mean '{}'", suggestion),
+                Some(span),
+            ),
+            ShellError::PluginFailedToLoad(msg) => {
+                LabeledError::new("Plugin failed to load", msg, None)
+            }
+            ShellError::PluginFailedToEncode(msg) => {
+                LabeledError::new("Plugin failed to encode", msg, None)
+            }
+            ShellError::PluginFailedToDecode(msg) => {
+                LabeledError::new("Plugin failed to decode", msg, None)
+            }
+            ShellError::PluginIncompatible(msg, help) => {
+                LabeledError::new("Plugin is incompatible", msg, None).with_help(help)
+            }
+            ShellError::PluginFailed(msg, stderr) => LabeledError::new("Plugin failed", msg, None)
+                .with_help(format!("The plugin wrote to its stderr:\n{}", stderr)),
+            err => LabeledError::new(
+                "Error - Add to LabeledError From<ShellError>",
+                err.to_string(),
+                None,
+            ),
         }
     }
 }
 
 // Information received from the plugin
+// A call can be answered with a single value or with a stream. A stream starts with
+// StreamStart, continues with one StreamData response per chunk and finishes with
+// StreamEnd. An Error response in the middle of a stream also finishes it
 #[derive(Serialize, Deserialize)]
 pub enum PluginResponse {
+    Hello(ProtocolInfo),
     Error(LabeledError),
     Signature(Vec<Signature>),
     Value(Box<Value>),
+    StreamStart(StreamKind),
+    StreamData(Box<Value>),
+    StreamEnd,
+    // The plugin needs something from nushell before it can answer the call
+    EngineCall(Box<EngineCall>),
 }
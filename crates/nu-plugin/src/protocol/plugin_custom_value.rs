@@ -0,0 +1,250 @@
+use std::path::PathBuf;
+
+use nu_protocol::{CustomValue, ShellError, Span, Value};
+use serde::{Deserialize, Serialize};
+
+use crate::plugin::{call_plugin, PluginOutput};
+use crate::protocol::{PluginCall, PluginResponse};
+use crate::EncodingType;
+
+// The plugin that created a custom value. Used by nushell to call the plugin back
+#[derive(Clone, Debug)]
+pub struct PluginSource {
+    pub filename: PathBuf,
+    pub shell: Option<PathBuf>,
+    pub encoding: EncodingType,
+}
+
+impl PluginSource {
+    fn is_same_plugin(&self, other: &PluginSource) -> bool {
+        self.filename == other.filename && self.shell == other.shell
+    }
+}
+
+// Custom value created by a plugin as it is seen by nushell. Nushell can't know the
+// plugin's types, so the custom value is kept serialized and is only deserialized
+// again by the plugin that created it, when it is sent back to the plugin
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PluginCustomValue {
+    // Name of the custom value, taken from its value_string
+    pub name: String,
+    pub data: Vec<u8>,
+    // Only known by nushell. It is set when the value is received from the plugin
+    #[serde(skip)]
+    pub source: Option<PluginSource>,
+}
+
+#[typetag::serde]
+impl CustomValue for PluginCustomValue {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::CustomValue {
+            val: Box::new(self.clone()),
+            span,
+        }
+    }
+
+    fn value_string(&self) -> String {
+        self.name.clone()
+    }
+
+    // The plugin that created the value is asked for its base value
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        let source = self.source.as_ref().ok_or_else(|| {
+            ShellError::SpannedLabeledError(
+                format!("Unable to collapse {}", self.name),
+                "the plugin that created the custom value is unknown".into(),
+                span,
+            )
+        })?;
+
+        let plugin_call = PluginCall::CollapseCustomValue(Box::new(self.clone_value(span)));
+        let output = call_plugin(
+            &source.filename,
+            &source.encoding,
+            &source.shell,
+            plugin_call,
+            None,
+            None,
+        )
+        .map_err(|err| {
+            ShellError::SpannedLabeledError(
+                format!("Unable to collapse {}", self.name),
+                err.to_string(),
+                span,
+            )
+        })?;
+
+        match output {
+            PluginOutput::Response(PluginResponse::Value(mut value)) => {
+                attach_source(&mut value, source);
+                Ok(*value)
+            }
+            PluginOutput::Response(PluginResponse::Error(err)) => Err(err.into()),
+            _ => Err(ShellError::SpannedLabeledError(
+                "Plugin missing value".into(),
+                "Received an unexpected response from plugin instead of value".into(),
+                span,
+            )),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+fn for_each_custom_value(
+    value: &mut Value,
+    f: &mut impl FnMut(&mut Box<dyn CustomValue>, Span) -> Result<(), ShellError>,
+) -> Result<(), ShellError> {
+    match value {
+        Value::CustomValue { val, span } => f(val, *span),
+        Value::List { vals, .. } | Value::Record { vals, .. } => vals
+            .iter_mut()
+            .try_for_each(|value| for_each_custom_value(value, f)),
+        _ => Ok(()),
+    }
+}
+
+// Nushell side. Custom values received from a plugin remember the plugin they come from
+pub(crate) fn attach_source(value: &mut Value, source: &PluginSource) {
+    let _ = for_each_custom_value(value, &mut |val, _| {
+        if let Some(custom_value) = val.as_any().downcast_ref::<PluginCustomValue>() {
+            let mut custom_value = custom_value.clone();
+            custom_value.source = Some(source.clone());
+            *val = Box::new(custom_value);
+        }
+
+        Ok(())
+    });
+}
+
+// Nushell side. Only the plugin that created a custom value can read it
+pub(crate) fn check_source(value: &mut Value, source: &PluginSource) -> Result<(), ShellError> {
+    for_each_custom_value(value, &mut |val, span| {
+        let value_source = val
+            .as_any()
+            .downcast_ref::<PluginCustomValue>()
+            .and_then(|custom_value| custom_value.source.as_ref());
+
+        match value_source {
+            Some(value_source) if value_source.is_same_plugin(source) => Ok(()),
+            _ => Err(ShellError::SpannedLabeledError(
+                format!("Can't send {} to plugin", val.value_string()),
+                "custom value was not created by this plugin".into(),
+                span,
+            )),
+        }
+    })
+}
+
+// Plugin side. The plugin's custom values are serialized before they are sent to nushell
+pub(crate) fn serialize_custom_values(value: &mut Value) -> Result<(), ShellError> {
+    for_each_custom_value(value, &mut |val, _| {
+        if val.as_any().is::<PluginCustomValue>() {
+            return Ok(());
+        }
+
+        let data = serde_json::to_vec(&*val)
+            .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))?;
+
+        *val = Box::new(PluginCustomValue {
+            name: val.value_string(),
+            data,
+            source: None,
+        });
+
+        Ok(())
+    })
+}
+
+// Plugin side. Custom values sent back by nushell are turned into the plugin's types
+pub(crate) fn deserialize_custom_values(value: &mut Value) -> Result<(), ShellError> {
+    for_each_custom_value(value, &mut |val, _| {
+        if let Some(custom_value) = val.as_any().downcast_ref::<PluginCustomValue>() {
+            *val = serde_json::from_slice(&custom_value.data)
+                .map_err(|err| ShellError::PluginFailedToDecode(err.to_string()))?;
+        }
+
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::JsonSerializer;
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    struct Handle {
+        id: i64,
+    }
+
+    #[typetag::serde]
+    impl CustomValue for Handle {
+        fn clone_value(&self, span: Span) -> Value {
+            Value::CustomValue {
+                val: Box::new(self.clone()),
+                span,
+            }
+        }
+
+        fn value_string(&self) -> String {
+            format!("handle {}", self.id)
+        }
+
+        fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+            Ok(Value::Int { val: self.id, span })
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn source(filename: &str) -> PluginSource {
+        PluginSource {
+            filename: filename.into(),
+            shell: None,
+            encoding: EncodingType::Json(JsonSerializer {}),
+        }
+    }
+
+    #[test]
+    fn custom_value_round_trip() {
+        let span = Span { start: 0, end: 5 };
+        let mut value = Value::List {
+            vals: vec![Handle { id: 7 }.clone_value(span)],
+            span,
+        };
+
+        serialize_custom_values(&mut value).expect("unable to serialize custom value");
+        attach_source(&mut value, &source("plugin"));
+
+        match &value {
+            Value::List { vals, .. } => {
+                assert_eq!(
+                    vals[0].clone().into_string("", &Default::default()),
+                    "handle 7"
+                )
+            }
+            _ => panic!("expected a list"),
+        }
+
+        check_source(&mut value, &source("plugin")).expect("custom value from the same plugin");
+        assert!(check_source(&mut value, &source("other")).is_err());
+
+        deserialize_custom_values(&mut value).expect("unable to deserialize custom value");
+
+        match value {
+            Value::List { vals, .. } => match &vals[0] {
+                Value::CustomValue { val, .. } => {
+                    let handle = val.as_any().downcast_ref::<Handle>().expect("a handle");
+                    assert_eq!(handle.id, 7)
+                }
+                _ => panic!("expected a custom value"),
+            },
+            _ => panic!("expected a list"),
+        }
+    }
+}
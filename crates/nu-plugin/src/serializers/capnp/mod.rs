@@ -5,39 +5,44 @@ mod value;
 
 use nu_protocol::ShellError;
 
-use crate::{plugin::PluginEncoder, protocol::PluginResponse};
+use crate::{
+    plugin::PluginEncoder,
+    protocol::{PluginCallId, PluginResponse},
+};
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct CapnpSerializer;
 
 impl PluginEncoder for CapnpSerializer {
     fn encode_call(
         &self,
+        id: PluginCallId,
         plugin_call: &crate::protocol::PluginCall,
         writer: &mut impl std::io::Write,
     ) -> Result<(), nu_protocol::ShellError> {
-        plugin_call::encode_call(plugin_call, writer)
+        plugin_call::encode_call(id, plugin_call, writer)
     }
 
     fn decode_call(
         &self,
         reader: &mut impl std::io::BufRead,
-    ) -> Result<crate::protocol::PluginCall, nu_protocol::ShellError> {
+    ) -> Result<(PluginCallId, crate::protocol::PluginCall), nu_protocol::ShellError> {
         plugin_call::decode_call(reader)
     }
 
     fn encode_response(
         &self,
+        id: PluginCallId,
         plugin_response: &PluginResponse,
         writer: &mut impl std::io::Write,
     ) -> Result<(), ShellError> {
-        plugin_call::encode_response(plugin_response, writer)
+        plugin_call::encode_response(id, plugin_response, writer)
     }
 
     fn decode_response(
         &self,
         reader: &mut impl std::io::BufRead,
-    ) -> Result<PluginResponse, ShellError> {
+    ) -> Result<(PluginCallId, PluginResponse), ShellError> {
         plugin_call::decode_response(reader)
     }
 }
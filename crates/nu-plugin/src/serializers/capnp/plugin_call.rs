@@ -1,20 +1,30 @@
 use super::signature::deserialize_signature;
 use super::{call, signature, value};
-use crate::plugin_capnp::{plugin_call, plugin_response};
-use crate::protocol::{CallInfo, LabeledError, PluginCall, PluginResponse};
+use crate::plugin_capnp::{
+    engine_call, engine_call_response, labeled_error, plugin_call, plugin_response, protocol_info,
+    StreamKind as PluginStreamKind,
+};
+use crate::protocol::{
+    CallInfo, CallInput, EngineCall, EngineCallResponse, ErrorLabel, EvalBlock, LabeledError,
+    PluginCall, PluginCallId, PluginResponse, ProtocolInfo, StreamKind,
+};
 use capnp::serialize;
-use nu_protocol::{ShellError, Signature, Span};
+use nu_protocol::{ShellError, Signature, Span, Value};
 
 pub fn encode_call(
+    id: PluginCallId,
     plugin_call: &PluginCall,
     writer: &mut impl std::io::Write,
 ) -> Result<(), ShellError> {
     let mut message = ::capnp::message::Builder::new_default();
 
     let mut builder = message.init_root::<plugin_call::Builder>();
+    builder.set_id(id as u64);
 
     match &plugin_call {
+        PluginCall::Hello(info) => serialize_protocol_info(info, builder.reborrow().init_hello()),
         PluginCall::Signature => builder.set_signature(()),
+        PluginCall::Goodbye => builder.set_goodbye(()),
         PluginCall::CallInfo(call_info) => {
             let mut call_info_builder = builder.reborrow().init_call_info();
 
@@ -30,13 +40,34 @@ pub fn encode_call(
             call::serialize_call(&call_info.call, call_builder)
                 .map_err(|e| ShellError::PluginFailedToEncode(e.to_string()))?;
 
-            // Serializing the input value from the call info
-            let value_builder = call_info_builder
-                .reborrow()
-                .get_input()
-                .map_err(|e| ShellError::PluginFailedToEncode(e.to_string()))?;
-
-            value::serialize_value(&call_info.input, value_builder);
+            // Serializing the input from the call info. For a stream only its kind is
+            // sent, the chunks follow the call
+            match &call_info.input {
+                CallInput::Value(input) => {
+                    let value_builder = call_info_builder
+                        .reborrow()
+                        .get_input()
+                        .map_err(|e| ShellError::PluginFailedToEncode(e.to_string()))?;
+
+                    value::serialize_value(input, value_builder);
+                }
+                CallInput::Stream(kind) => {
+                    call_info_builder.set_input_stream(serialize_stream_kind(*kind))
+                }
+            }
+        }
+        PluginCall::StreamData(val) => {
+            let value_builder = builder.reborrow().init_stream_data();
+            value::serialize_value(val, value_builder);
+        }
+        PluginCall::StreamEnd => builder.set_stream_end(()),
+        PluginCall::CollapseCustomValue(val) => {
+            let value_builder = builder.reborrow().init_collapse_custom_value();
+            value::serialize_value(val, value_builder);
+        }
+        PluginCall::EngineCallResponse(response) => {
+            let response_builder = builder.reborrow().init_engine_call_response();
+            serialize_engine_call_response(response, response_builder);
         }
     };
 
@@ -44,7 +75,9 @@ pub fn encode_call(
         .map_err(|e| ShellError::PluginFailedToEncode(e.to_string()))
 }
 
-pub fn decode_call(reader: &mut impl std::io::BufRead) -> Result<PluginCall, ShellError> {
+pub fn decode_call(
+    reader: &mut impl std::io::BufRead,
+) -> Result<(PluginCallId, PluginCall), ShellError> {
     let message_reader = serialize::read_message(reader, ::capnp::message::ReaderOptions::new())
         .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
@@ -52,11 +85,19 @@ pub fn decode_call(reader: &mut impl std::io::BufRead) -> Result<PluginCall, She
         .get_root::<plugin_call::Reader>()
         .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
-    match reader.which() {
+    let id = reader.get_id() as PluginCallId;
+
+    let plugin_call = match reader.which() {
         Err(capnp::NotInSchema(_)) => Err(ShellError::PluginFailedToDecode(
             "value not in schema".into(),
         )),
+        Ok(plugin_call::Hello(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+            deserialize_protocol_info(reader).map(PluginCall::Hello)
+        }
         Ok(plugin_call::Signature(())) => Ok(PluginCall::Signature),
+        Ok(plugin_call::Goodbye(())) => Ok(PluginCall::Goodbye),
         Ok(plugin_call::CallInfo(reader)) => {
             let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
@@ -70,11 +111,20 @@ pub fn decode_call(reader: &mut impl std::io::BufRead) -> Result<PluginCall, She
 
             let call = call::deserialize_call(call_reader)?;
 
-            let input_reader = reader
-                .get_input()
+            let input_stream = reader
+                .get_input_stream()
                 .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
-            let input = value::deserialize_value(input_reader, call.head)?;
+            let input = match deserialize_stream_kind(input_stream) {
+                Some(kind) => CallInput::Stream(kind),
+                None => {
+                    let input_reader = reader
+                        .get_input()
+                        .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+                    CallInput::Value(value::deserialize_value(input_reader, call.head)?)
+                }
+            };
 
             Ok(PluginCall::CallInfo(Box::new(CallInfo {
                 name: name.to_string(),
@@ -82,28 +132,47 @@ pub fn decode_call(reader: &mut impl std::io::BufRead) -> Result<PluginCall, She
                 input,
             })))
         }
-    }
+        Ok(plugin_call::StreamData(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let val = deserialize_value_with_span(reader)?;
+
+            Ok(PluginCall::StreamData(Box::new(val)))
+        }
+        Ok(plugin_call::StreamEnd(())) => Ok(PluginCall::StreamEnd),
+        Ok(plugin_call::CollapseCustomValue(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let val = deserialize_value_with_span(reader)?;
+
+            Ok(PluginCall::CollapseCustomValue(Box::new(val)))
+        }
+        Ok(plugin_call::EngineCallResponse(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let response = deserialize_engine_call_response(reader)?;
+
+            Ok(PluginCall::EngineCallResponse(Box::new(response)))
+        }
+    }?;
+
+    Ok((id, plugin_call))
 }
 
 pub fn encode_response(
+    id: PluginCallId,
     plugin_response: &PluginResponse,
     writer: &mut impl std::io::Write,
 ) -> Result<(), ShellError> {
     let mut message = ::capnp::message::Builder::new_default();
 
     let mut builder = message.init_root::<plugin_response::Builder>();
+    builder.set_id(id as u64);
 
     match &plugin_response {
+        PluginResponse::Hello(info) => {
+            serialize_protocol_info(info, builder.reborrow().init_hello())
+        }
         PluginResponse::Error(msg) => {
-            let mut error_builder = builder.reborrow().init_error();
-            error_builder.set_label(&msg.label);
-            error_builder.set_msg(&msg.msg);
-
-            if let Some(span) = msg.span {
-                let mut span_builder = error_builder.reborrow().init_span();
-                span_builder.set_start(span.start as u64);
-                span_builder.set_end(span.end as u64);
-            }
+            let error_builder = builder.reborrow().init_error();
+            serialize_labeled_error(msg, error_builder);
         }
         PluginResponse::Signature(signatures) => {
             let mut signature_list_builder =
@@ -118,13 +187,25 @@ pub fn encode_response(
             let value_builder = builder.reborrow().init_value();
             value::serialize_value(val, value_builder);
         }
+        PluginResponse::StreamStart(kind) => builder.set_stream_start(serialize_stream_kind(*kind)),
+        PluginResponse::StreamData(val) => {
+            let value_builder = builder.reborrow().init_stream_data();
+            value::serialize_value(val, value_builder);
+        }
+        PluginResponse::StreamEnd => builder.set_stream_end(()),
+        PluginResponse::EngineCall(engine_call) => {
+            let engine_call_builder = builder.reborrow().init_engine_call();
+            serialize_engine_call(engine_call, engine_call_builder);
+        }
     };
 
     serialize::write_message(writer, &message)
         .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))
 }
 
-pub fn decode_response(reader: &mut impl std::io::BufRead) -> Result<PluginResponse, ShellError> {
+pub fn decode_response(
+    reader: &mut impl std::io::BufRead,
+) -> Result<(PluginCallId, PluginResponse), ShellError> {
     let message_reader = serialize::read_message(reader, ::capnp::message::ReaderOptions::new())
         .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
@@ -132,39 +213,20 @@ pub fn decode_response(reader: &mut impl std::io::BufRead) -> Result<PluginRespo
         .get_root::<plugin_response::Reader>()
         .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
-    match reader.which() {
+    let id = reader.get_id() as PluginCallId;
+
+    let plugin_response = match reader.which() {
         Err(capnp::NotInSchema(_)) => Err(ShellError::PluginFailedToDecode(
             "value not in schema".into(),
         )),
-        Ok(plugin_response::Error(reader)) => {
+        Ok(plugin_response::Hello(reader)) => {
             let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
-            let msg = reader
-                .get_msg()
-                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
-
-            let label = reader
-                .get_label()
-                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
-
-            let span = if reader.has_span() {
-                let span = reader
-                    .get_span()
-                    .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
-
-                Some(Span {
-                    start: span.get_start() as usize,
-                    end: span.get_end() as usize,
-                })
-            } else {
-                None
-            };
-
-            let error = LabeledError {
-                label: label.into(),
-                msg: msg.into(),
-                span,
-            };
+            deserialize_protocol_info(reader).map(PluginResponse::Hello)
+        }
+        Ok(plugin_response::Error(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let error = deserialize_labeled_error(reader)?;
 
             Ok(PluginResponse::Error(error))
         }
@@ -180,42 +242,310 @@ pub fn decode_response(reader: &mut impl std::io::BufRead) -> Result<PluginRespo
         }
         Ok(plugin_response::Value(reader)) => {
             let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let val = deserialize_value_with_span(reader)?;
+
+            Ok(PluginResponse::Value(Box::new(val)))
+        }
+        Ok(plugin_response::StreamStart(kind)) => {
+            let kind = kind.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+            deserialize_stream_kind(kind)
+                .map(PluginResponse::StreamStart)
+                .ok_or_else(|| ShellError::PluginFailedToDecode("Missing stream kind".into()))
+        }
+        Ok(plugin_response::StreamData(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let val = deserialize_value_with_span(reader)?;
+
+            Ok(PluginResponse::StreamData(Box::new(val)))
+        }
+        Ok(plugin_response::StreamEnd(())) => Ok(PluginResponse::StreamEnd),
+        Ok(plugin_response::EngineCall(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let engine_call = deserialize_engine_call(reader)?;
 
-            let span = reader
+            Ok(PluginResponse::EngineCall(Box::new(engine_call)))
+        }
+    }?;
+
+    Ok((id, plugin_response))
+}
+
+fn deserialize_value_with_span(
+    reader: crate::plugin_capnp::value::Reader,
+) -> Result<Value, ShellError> {
+    let span = reader
+        .get_span()
+        .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+    value::deserialize_value(
+        reader,
+        Span {
+            start: span.get_start() as usize,
+            end: span.get_end() as usize,
+        },
+    )
+    .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))
+}
+
+fn serialize_protocol_info(info: &ProtocolInfo, mut builder: protocol_info::Builder) {
+    builder.set_protocol(&info.protocol);
+    builder.set_version(info.version);
+
+    let mut features_builder = builder.init_features(info.features.len() as u32);
+    for (index, feature) in info.features.iter().enumerate() {
+        features_builder.set(index as u32, feature.as_str());
+    }
+}
+
+fn deserialize_protocol_info(reader: protocol_info::Reader) -> Result<ProtocolInfo, ShellError> {
+    let protocol = reader
+        .get_protocol()
+        .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+    let features = reader
+        .get_features()
+        .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?
+        .iter()
+        .map(|feature| {
+            feature
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))
+                .map(|feature| feature.to_string())
+        })
+        .collect::<Result<Vec<String>, ShellError>>()?;
+
+    Ok(ProtocolInfo {
+        protocol: protocol.into(),
+        version: reader.get_version(),
+        features,
+    })
+}
+
+fn serialize_labeled_error(error: &LabeledError, mut builder: labeled_error::Builder) {
+    builder.set_label(&error.label);
+    builder.set_msg(&error.msg);
+
+    if let Some(span) = error.span {
+        let mut span_builder = builder.reborrow().init_span();
+        span_builder.set_start(span.start as u64);
+        span_builder.set_end(span.end as u64);
+    }
+
+    if let Some(help) = &error.help {
+        builder.set_help(help);
+    }
+
+    let mut labels_builder = builder.init_labels(error.labels.len() as u32);
+    for (index, label) in error.labels.iter().enumerate() {
+        let mut label_builder = labels_builder.reborrow().get(index as u32);
+        label_builder.set_text(&label.text);
+
+        let mut span_builder = label_builder.init_span();
+        span_builder.set_start(label.span.start as u64);
+        span_builder.set_end(label.span.end as u64);
+    }
+}
+
+fn deserialize_labeled_error(reader: labeled_error::Reader) -> Result<LabeledError, ShellError> {
+    let msg = reader
+        .get_msg()
+        .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+    let label = reader
+        .get_label()
+        .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+    let span = if reader.has_span() {
+        let span = reader
+            .get_span()
+            .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+        Some(Span {
+            start: span.get_start() as usize,
+            end: span.get_end() as usize,
+        })
+    } else {
+        None
+    };
+
+    let help = if reader.has_help() {
+        let help = reader
+            .get_help()
+            .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+        Some(help.to_string())
+    } else {
+        None
+    };
+
+    let labels = reader
+        .get_labels()
+        .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?
+        .iter()
+        .map(|label| {
+            let text = label
+                .get_text()
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let span = label
                 .get_span()
                 .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
-            let val = value::deserialize_value(
-                reader,
-                Span {
+            Ok(ErrorLabel {
+                text: text.to_string(),
+                span: Span {
                     start: span.get_start() as usize,
                     end: span.get_end() as usize,
                 },
-            )
-            .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            })
+        })
+        .collect::<Result<Vec<ErrorLabel>, ShellError>>()?;
+
+    Ok(LabeledError {
+        label: label.into(),
+        msg: msg.into(),
+        span,
+        labels,
+        help,
+    })
+}
 
-            Ok(PluginResponse::Value(Box::new(val)))
+fn serialize_engine_call(call: &EngineCall, mut builder: engine_call::Builder) {
+    match call {
+        EngineCall::EvalBlock(eval) => {
+            let mut eval_builder = builder.reborrow().init_eval_block();
+            value::serialize_value(&eval.block, eval_builder.reborrow().init_block());
+
+            let mut positional_builder = eval_builder
+                .reborrow()
+                .init_positional(eval.positional.len() as u32);
+            for (index, value) in eval.positional.iter().enumerate() {
+                let inner_builder = positional_builder.reborrow().get(index as u32);
+                value::serialize_value(value, inner_builder);
+            }
+
+            value::serialize_value(&eval.input, eval_builder.init_input());
+        }
+        EngineCall::GetEnvVar(name) => builder.set_get_env_var(name),
+        EngineCall::GetEnvVars => builder.set_get_env_vars(()),
+        EngineCall::GetConfig => builder.set_get_config(()),
+        EngineCall::GetCurrentDir => builder.set_get_current_dir(()),
+    }
+}
+
+fn deserialize_engine_call(reader: engine_call::Reader) -> Result<EngineCall, ShellError> {
+    match reader.which() {
+        Err(capnp::NotInSchema(_)) => Err(ShellError::PluginFailedToDecode(
+            "engine call not in schema".into(),
+        )),
+        Ok(engine_call::EvalBlock(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+            let block = reader
+                .get_block()
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let block = deserialize_value_with_span(block)?;
+
+            let positional = reader
+                .get_positional()
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?
+                .iter()
+                .map(deserialize_value_with_span)
+                .collect::<Result<Vec<Value>, ShellError>>()?;
+
+            let input = reader
+                .get_input()
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let input = deserialize_value_with_span(input)?;
+
+            Ok(EngineCall::EvalBlock(Box::new(EvalBlock {
+                block,
+                positional,
+                input,
+            })))
+        }
+        Ok(engine_call::GetEnvVar(name)) => {
+            let name = name.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+            Ok(EngineCall::GetEnvVar(name.to_string()))
+        }
+        Ok(engine_call::GetEnvVars(())) => Ok(EngineCall::GetEnvVars),
+        Ok(engine_call::GetConfig(())) => Ok(EngineCall::GetConfig),
+        Ok(engine_call::GetCurrentDir(())) => Ok(EngineCall::GetCurrentDir),
+    }
+}
+
+fn serialize_engine_call_response(
+    response: &EngineCallResponse,
+    builder: engine_call_response::Builder,
+) {
+    match response {
+        EngineCallResponse::Value(val) => value::serialize_value(val, builder.init_value()),
+        EngineCallResponse::Error(error) => serialize_labeled_error(error, builder.init_error()),
+    }
+}
+
+fn deserialize_engine_call_response(
+    reader: engine_call_response::Reader,
+) -> Result<EngineCallResponse, ShellError> {
+    match reader.which() {
+        Err(capnp::NotInSchema(_)) => Err(ShellError::PluginFailedToDecode(
+            "engine call response not in schema".into(),
+        )),
+        Ok(engine_call_response::Value(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+            let val = deserialize_value_with_span(reader)?;
+
+            Ok(EngineCallResponse::Value(Box::new(val)))
+        }
+        Ok(engine_call_response::Error(reader)) => {
+            let reader = reader.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+            deserialize_labeled_error(reader).map(EngineCallResponse::Error)
         }
     }
 }
 
+fn serialize_stream_kind(kind: StreamKind) -> PluginStreamKind {
+    match kind {
+        StreamKind::List => PluginStreamKind::List,
+        StreamKind::String => PluginStreamKind::String,
+        StreamKind::Binary => PluginStreamKind::Binary,
+    }
+}
+
+fn deserialize_stream_kind(kind: PluginStreamKind) -> Option<StreamKind> {
+    match kind {
+        PluginStreamKind::None => None,
+        PluginStreamKind::List => Some(StreamKind::List),
+        PluginStreamKind::String => Some(StreamKind::String),
+        PluginStreamKind::Binary => Some(StreamKind::Binary),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::protocol::{EvaluatedCall, LabeledError, PluginCall, PluginResponse};
     use nu_protocol::{Signature, Span, Spanned, SyntaxShape, Value};
+    use std::collections::HashMap;
 
     #[test]
     fn callinfo_round_trip_signature() {
         let plugin_call = PluginCall::Signature;
 
         let mut buffer: Vec<u8> = Vec::new();
-        encode_call(&plugin_call, &mut buffer).expect("unable to serialize message");
-        let returned = decode_call(&mut buffer.as_slice()).expect("unable to deserialize message");
+        encode_call(0, &plugin_call, &mut buffer).expect("unable to serialize message");
+        let (_, returned) =
+            decode_call(&mut buffer.as_slice()).expect("unable to deserialize message");
 
         match returned {
             PluginCall::Signature => {}
             PluginCall::CallInfo(_) => panic!("decoded into wrong value"),
+            PluginCall::StreamData(_) | PluginCall::StreamEnd => panic!("decoded into wrong value"),
+            PluginCall::Goodbye
+            | PluginCall::CollapseCustomValue(_)
+            | PluginCall::EngineCallResponse(_)
+            | PluginCall::Hello(_) => panic!("decoded into wrong value"),
         }
     }
 
@@ -255,18 +585,24 @@ mod tests {
         let plugin_call = PluginCall::CallInfo(Box::new(CallInfo {
             name: name.clone(),
             call: call.clone(),
-            input: input.clone(),
+            input: CallInput::Value(input.clone()),
         }));
 
         let mut buffer: Vec<u8> = Vec::new();
-        encode_call(&plugin_call, &mut buffer).expect("unable to serialize message");
-        let returned = decode_call(&mut buffer.as_slice()).expect("unable to deserialize message");
+        encode_call(0, &plugin_call, &mut buffer).expect("unable to serialize message");
+        let (_, returned) =
+            decode_call(&mut buffer.as_slice()).expect("unable to deserialize message");
 
         match returned {
             PluginCall::Signature => panic!("returned wrong call type"),
+            PluginCall::StreamData(_) | PluginCall::StreamEnd => panic!("returned wrong call type"),
+            PluginCall::Goodbye
+            | PluginCall::CollapseCustomValue(_)
+            | PluginCall::EngineCallResponse(_)
+            | PluginCall::Hello(_) => panic!("returned wrong call type"),
             PluginCall::CallInfo(call_info) => {
                 assert_eq!(name, call_info.name);
-                assert_eq!(input, call_info.input);
+                assert_eq!(CallInput::Value(input), call_info.input);
                 assert_eq!(call.head, call_info.call.head);
                 assert_eq!(call.positional.len(), call_info.call.positional.len());
 
@@ -309,12 +645,17 @@ mod tests {
         let response = PluginResponse::Signature(vec![signature.clone()]);
 
         let mut buffer: Vec<u8> = Vec::new();
-        encode_response(&response, &mut buffer).expect("unable to serialize message");
-        let returned =
+        encode_response(0, &response, &mut buffer).expect("unable to serialize message");
+        let (_, returned) =
             decode_response(&mut buffer.as_slice()).expect("unable to deserialize message");
 
         match returned {
             PluginResponse::Error(_) => panic!("returned wrong call type"),
+            PluginResponse::StreamStart(_)
+            | PluginResponse::StreamData(_)
+            | PluginResponse::StreamEnd
+            | PluginResponse::EngineCall(_)
+            | PluginResponse::Hello(_) => panic!("returned wrong call type"),
             PluginResponse::Value(_) => panic!("returned wrong call type"),
             PluginResponse::Signature(returned_signature) => {
                 assert!(returned_signature.len() == 1);
@@ -359,12 +700,17 @@ mod tests {
         let response = PluginResponse::Value(Box::new(value.clone()));
 
         let mut buffer: Vec<u8> = Vec::new();
-        encode_response(&response, &mut buffer).expect("unable to serialize message");
-        let returned =
+        encode_response(0, &response, &mut buffer).expect("unable to serialize message");
+        let (_, returned) =
             decode_response(&mut buffer.as_slice()).expect("unable to deserialize message");
 
         match returned {
             PluginResponse::Error(_) => panic!("returned wrong call type"),
+            PluginResponse::StreamStart(_)
+            | PluginResponse::StreamData(_)
+            | PluginResponse::StreamEnd
+            | PluginResponse::EngineCall(_)
+            | PluginResponse::Hello(_) => panic!("returned wrong call type"),
             PluginResponse::Signature(_) => panic!("returned wrong call type"),
             PluginResponse::Value(returned_value) => {
                 assert_eq!(&value, returned_value.as_ref())
@@ -378,17 +724,24 @@ mod tests {
             label: "label".into(),
             msg: "msg".into(),
             span: Some(Span { start: 2, end: 30 }),
+            labels: vec![],
+            help: None,
         };
         let response = PluginResponse::Error(error.clone());
 
         let mut buffer: Vec<u8> = Vec::new();
-        encode_response(&response, &mut buffer).expect("unable to serialize message");
-        let returned =
+        encode_response(0, &response, &mut buffer).expect("unable to serialize message");
+        let (_, returned) =
             decode_response(&mut buffer.as_slice()).expect("unable to deserialize message");
 
         match returned {
             PluginResponse::Error(msg) => assert_eq!(error, msg),
             PluginResponse::Signature(_) => panic!("returned wrong call type"),
+            PluginResponse::StreamStart(_)
+            | PluginResponse::StreamData(_)
+            | PluginResponse::StreamEnd
+            | PluginResponse::EngineCall(_)
+            | PluginResponse::Hello(_) => panic!("returned wrong call type"),
             PluginResponse::Value(_) => panic!("returned wrong call type"),
         }
     }
@@ -399,18 +752,273 @@ mod tests {
             label: "label".into(),
             msg: "msg".into(),
             span: None,
+            labels: vec![],
+            help: None,
         };
         let response = PluginResponse::Error(error.clone());
 
         let mut buffer: Vec<u8> = Vec::new();
-        encode_response(&response, &mut buffer).expect("unable to serialize message");
-        let returned =
+        encode_response(0, &response, &mut buffer).expect("unable to serialize message");
+        let (_, returned) =
             decode_response(&mut buffer.as_slice()).expect("unable to deserialize message");
 
         match returned {
             PluginResponse::Error(msg) => assert_eq!(error, msg),
             PluginResponse::Signature(_) => panic!("returned wrong call type"),
+            PluginResponse::StreamStart(_)
+            | PluginResponse::StreamData(_)
+            | PluginResponse::StreamEnd
+            | PluginResponse::EngineCall(_)
+            | PluginResponse::Hello(_) => panic!("returned wrong call type"),
             PluginResponse::Value(_) => panic!("returned wrong call type"),
         }
     }
+
+    #[test]
+    fn calls_share_a_stream() {
+        let mut buffer: Vec<u8> = Vec::new();
+        encode_call(1, &PluginCall::Signature, &mut buffer).expect("unable to serialize message");
+        encode_call(2, &PluginCall::Goodbye, &mut buffer).expect("unable to serialize message");
+
+        let mut reader = buffer.as_slice();
+
+        let (id, returned) = decode_call(&mut reader).expect("unable to deserialize message");
+        assert_eq!(1, id);
+        assert!(matches!(returned, PluginCall::Signature));
+
+        let (id, returned) = decode_call(&mut reader).expect("unable to deserialize message");
+        assert_eq!(2, id);
+        assert!(matches!(returned, PluginCall::Goodbye));
+    }
+
+    #[test]
+    fn callinfo_round_trip_stream_input() {
+        let call = EvaluatedCall {
+            head: Span { start: 0, end: 10 },
+            positional: vec![],
+            named: vec![],
+        };
+
+        let plugin_call = PluginCall::CallInfo(Box::new(CallInfo {
+            name: "test".into(),
+            call,
+            input: CallInput::Stream(StreamKind::String),
+        }));
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encode_call(0, &plugin_call, &mut buffer).expect("unable to serialize message");
+        let (_, returned) =
+            decode_call(&mut buffer.as_slice()).expect("unable to deserialize message");
+
+        match returned {
+            PluginCall::CallInfo(call_info) => {
+                assert_eq!(CallInput::Stream(StreamKind::String), call_info.input)
+            }
+            _ => panic!("returned wrong call type"),
+        }
+    }
+
+    #[test]
+    fn stream_data_round_trip() {
+        let value = Value::String {
+            val: "chunk".into(),
+            span: Span { start: 2, end: 30 },
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encode_call(
+            1,
+            &PluginCall::StreamData(Box::new(value.clone())),
+            &mut buffer,
+        )
+        .expect("unable to serialize message");
+        encode_call(1, &PluginCall::StreamEnd, &mut buffer).expect("unable to serialize message");
+
+        let mut reader = buffer.as_slice();
+
+        match decode_call(&mut reader).expect("unable to deserialize message") {
+            (1, PluginCall::StreamData(returned_value)) => {
+                assert_eq!(&value, returned_value.as_ref())
+            }
+            _ => panic!("returned wrong call type"),
+        }
+
+        let (_, returned) = decode_call(&mut reader).expect("unable to deserialize message");
+        assert!(matches!(returned, PluginCall::StreamEnd));
+    }
+
+    #[test]
+    fn response_stream_round_trip() {
+        let value = Value::Binary {
+            val: vec![1, 2, 3],
+            span: Span { start: 2, end: 30 },
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        for response in [
+            PluginResponse::StreamStart(StreamKind::Binary),
+            PluginResponse::StreamData(Box::new(value.clone())),
+            PluginResponse::StreamEnd,
+        ] {
+            encode_response(3, &response, &mut buffer).expect("unable to serialize message");
+        }
+
+        let mut reader = buffer.as_slice();
+
+        let (id, returned) = decode_response(&mut reader).expect("unable to deserialize message");
+        assert_eq!(3, id);
+        assert!(matches!(
+            returned,
+            PluginResponse::StreamStart(StreamKind::Binary)
+        ));
+
+        match decode_response(&mut reader) {
+            Ok((3, PluginResponse::StreamData(returned_value))) => {
+                assert_eq!(&value, returned_value.as_ref())
+            }
+            _ => panic!("returned wrong response type"),
+        }
+
+        let (_, returned) = decode_response(&mut reader).expect("unable to deserialize message");
+        assert!(matches!(returned, PluginResponse::StreamEnd));
+    }
+
+    #[test]
+    fn engine_call_round_trip() {
+        let span = Span { start: 2, end: 30 };
+        let engine_call = EngineCall::EvalBlock(Box::new(EvalBlock {
+            block: Value::Block {
+                val: 4,
+                captures: HashMap::new(),
+                span,
+            },
+            positional: vec![Value::Int { val: 1, span }],
+            input: Value::String {
+                val: "input".into(),
+                span,
+            },
+        }));
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encode_response(
+            5,
+            &PluginResponse::EngineCall(Box::new(engine_call)),
+            &mut buffer,
+        )
+        .expect("unable to serialize message");
+        encode_response(
+            5,
+            &PluginResponse::EngineCall(Box::new(EngineCall::GetEnvVar("PATH".into()))),
+            &mut buffer,
+        )
+        .expect("unable to serialize message");
+
+        let mut reader = buffer.as_slice();
+
+        match decode_response(&mut reader).expect("unable to deserialize message") {
+            (5, PluginResponse::EngineCall(engine_call)) => match *engine_call {
+                EngineCall::EvalBlock(eval) => {
+                    assert_eq!(
+                        Value::Block {
+                            val: 4,
+                            captures: HashMap::new(),
+                            span
+                        },
+                        eval.block
+                    );
+                    assert_eq!(vec![Value::Int { val: 1, span }], eval.positional);
+                    assert_eq!(
+                        Value::String {
+                            val: "input".into(),
+                            span
+                        },
+                        eval.input
+                    );
+                }
+                _ => panic!("returned wrong engine call"),
+            },
+            _ => panic!("returned wrong response type"),
+        }
+
+        match decode_response(&mut reader).expect("unable to deserialize message") {
+            (5, PluginResponse::EngineCall(engine_call)) => {
+                assert!(matches!(*engine_call, EngineCall::GetEnvVar(name) if name == "PATH"))
+            }
+            _ => panic!("returned wrong response type"),
+        }
+    }
+
+    #[test]
+    fn engine_call_response_round_trip() {
+        let error = LabeledError {
+            label: "label".into(),
+            msg: "msg".into(),
+            span: None,
+            labels: vec![],
+            help: None,
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encode_call(
+            5,
+            &PluginCall::EngineCallResponse(Box::new(EngineCallResponse::Error(error.clone()))),
+            &mut buffer,
+        )
+        .expect("unable to serialize message");
+
+        match decode_call(&mut buffer.as_slice()).expect("unable to deserialize message") {
+            (5, PluginCall::EngineCallResponse(response)) => match *response {
+                EngineCallResponse::Error(returned) => assert_eq!(error, returned),
+                EngineCallResponse::Value(_) => panic!("returned wrong engine call response"),
+            },
+            _ => panic!("returned wrong call type"),
+        }
+    }
+
+    #[test]
+    fn hello_round_trip() {
+        let info = ProtocolInfo {
+            protocol: "nu-plugin".into(),
+            version: 3,
+            features: vec!["streams".into(), "future-feature".into()],
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encode_call(0, &PluginCall::Hello(info.clone()), &mut buffer)
+            .expect("unable to serialize message");
+        encode_response(0, &PluginResponse::Hello(info.clone()), &mut buffer)
+            .expect("unable to serialize message");
+
+        let mut reader = buffer.as_slice();
+
+        match decode_call(&mut reader).expect("unable to deserialize message") {
+            (0, PluginCall::Hello(returned)) => assert_eq!(info, returned),
+            _ => panic!("returned wrong call type"),
+        }
+
+        match decode_response(&mut reader).expect("unable to deserialize message") {
+            (0, PluginResponse::Hello(returned)) => {
+                assert_eq!(info, returned);
+                assert!(!ProtocolInfo::default().is_compatible(&returned));
+            }
+            _ => panic!("returned wrong response type"),
+        }
+    }
+
+    #[test]
+    fn response_round_trip_error_labels() {
+        let error = LabeledError::new("label", "msg", Some(Span { start: 2, end: 30 }))
+            .with_label("first", Span { start: 4, end: 6 })
+            .with_label("second", Span { start: 10, end: 12 })
+            .with_help("help");
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encode_response(0, &PluginResponse::Error(error.clone()), &mut buffer)
+            .expect("unable to serialize message");
+
+        match decode_response(&mut buffer.as_slice()).expect("unable to deserialize message") {
+            (0, PluginResponse::Error(returned)) => assert_eq!(error, returned),
+            _ => panic!("returned wrong response type"),
+        }
+    }
 }
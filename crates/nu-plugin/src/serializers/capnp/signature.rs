@@ -67,6 +67,7 @@ fn serialize_argument(arg: &PositionalArg, mut builder: argument::Builder) {
         SyntaxShape::String => builder.set_shape(Shape::String),
         SyntaxShape::Int => builder.set_shape(Shape::Int),
         SyntaxShape::Number => builder.set_shape(Shape::Number),
+        SyntaxShape::Block(_) => builder.set_shape(Shape::Block),
         _ => builder.set_shape(Shape::Any),
     }
 }
@@ -88,6 +89,7 @@ fn serialize_flag(arg: &Flag, mut builder: flag::Builder) {
             SyntaxShape::String => builder.set_arg(Shape::String),
             SyntaxShape::Int => builder.set_arg(Shape::Int),
             SyntaxShape::Number => builder.set_arg(Shape::Number),
+            SyntaxShape::Block(_) => builder.set_arg(Shape::Block),
             _ => builder.set_arg(Shape::Any),
         },
     }
@@ -175,6 +177,9 @@ pub(crate) fn deserialize_signature(reader: signature::Reader) -> Result<Signatu
         named,
         is_filter,
         creates_scope: false,
+        is_loop: false,
+        runs_in_place: false,
+        input_output_types: vec![],
         category,
     })
 }
@@ -197,6 +202,8 @@ fn deserialize_argument(reader: argument::Reader) -> Result<PositionalArg, Shell
         Shape::Int => SyntaxShape::Int,
         Shape::Number => SyntaxShape::Number,
         Shape::Boolean => SyntaxShape::Boolean,
+        // Blocks given to plugins take the value they are run with as parameter
+        Shape::Block => SyntaxShape::Block(Some(vec![SyntaxShape::Any])),
         Shape::Any => SyntaxShape::Any,
         Shape::None => SyntaxShape::Any,
     };
@@ -206,6 +213,7 @@ fn deserialize_argument(reader: argument::Reader) -> Result<PositionalArg, Shell
         desc: desc.to_string(),
         shape,
         var_id: None,
+        default_value: None,
     })
 }
 
@@ -241,6 +249,7 @@ fn deserialize_flag(reader: flag::Reader) -> Result<Flag, ShellError> {
         Shape::Int => Some(SyntaxShape::Int),
         Shape::Number => Some(SyntaxShape::Number),
         Shape::Boolean => Some(SyntaxShape::Boolean),
+        Shape::Block => Some(SyntaxShape::Block(Some(vec![SyntaxShape::Any]))),
     };
 
     Ok(Flag {
@@ -250,6 +259,7 @@ fn deserialize_flag(reader: flag::Reader) -> Result<Flag, ShellError> {
         required,
         desc: desc.to_string(),
         var_id: None,
+        default_value: None,
     })
 }
 
@@ -1,5 +1,7 @@
 use crate::plugin_capnp::value;
+use crate::protocol::PluginCustomValue;
 use nu_protocol::{ShellError, Span, Value};
+use std::collections::HashMap;
 
 pub(crate) fn serialize_value(value: &Value, mut builder: value::Builder) {
     let value_span = match value {
@@ -23,6 +25,10 @@ pub(crate) fn serialize_value(value: &Value, mut builder: value::Builder) {
             builder.set_string(val);
             *span
         }
+        Value::Binary { val, span } => {
+            builder.set_binary(val);
+            *span
+        }
         Value::Record { cols, vals, span } => {
             let mut record_builder = builder.reborrow().init_record();
 
@@ -48,6 +54,21 @@ pub(crate) fn serialize_value(value: &Value, mut builder: value::Builder) {
 
             *span
         }
+        Value::Block { val, span, .. } => {
+            builder.set_block(*val as u64);
+            *span
+        }
+        Value::CustomValue { val, span } => {
+            // Only custom values created by plugins have an encoding. Nushell keeps
+            // them serialized
+            if let Some(custom_value) = val.as_any().downcast_ref::<PluginCustomValue>() {
+                let mut custom_builder = builder.reborrow().init_custom_value();
+                custom_builder.set_name(&custom_value.name);
+                custom_builder.set_data(&custom_value.data);
+            }
+
+            *span
+        }
         _ => {
             // If there is the need to pass other type of value to the plugin
             // we have to define the encoding for that object in this match
@@ -83,6 +104,12 @@ pub(crate) fn deserialize_value(reader: value::Reader, head: Span) -> Result<Val
                 .to_string();
             Ok(Value::String { val: string, span })
         }
+        Ok(value::Binary(val)) => {
+            let val = val
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?
+                .to_vec();
+            Ok(Value::Binary { val, span })
+        }
         Ok(value::Record(record)) => {
             let record = record.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
 
@@ -118,6 +145,34 @@ pub(crate) fn deserialize_value(reader: value::Reader, head: Span) -> Result<Val
                 span,
             })
         }
+        Ok(value::Block(val)) => Ok(Value::Block {
+            val: val as usize,
+            captures: HashMap::new(),
+            span,
+        }),
+        Ok(value::CustomValue(custom_value)) => {
+            let custom_value =
+                custom_value.map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?;
+
+            let name = custom_value
+                .get_name()
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?
+                .to_string();
+
+            let data = custom_value
+                .get_data()
+                .map_err(|e| ShellError::PluginFailedToDecode(e.to_string()))?
+                .to_vec();
+
+            Ok(Value::CustomValue {
+                val: Box::new(PluginCustomValue {
+                    name,
+                    data,
+                    source: None,
+                }),
+                span,
+            })
+        }
         Err(capnp::NotInSchema(_)) => Ok(Value::Nothing { span: head }),
     }
 }
@@ -179,6 +234,10 @@ mod tests {
                 val: "a string".into(),
                 span: Span { start: 4, end: 50 },
             },
+            Value::Binary {
+                val: vec![0, 1, 255],
+                span: Span { start: 5, end: 60 },
+            },
         ];
 
         for value in values {
@@ -191,6 +250,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn custom_value_round_trip() {
+        let value = Value::CustomValue {
+            val: Box::new(PluginCustomValue {
+                name: "custom".into(),
+                data: vec![1, 2, 3],
+                source: None,
+            }),
+            span: Span { start: 0, end: 10 },
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        write_buffer(&value, &mut buffer).expect("unable to serialize message");
+        let returned_value =
+            read_buffer(&mut buffer.as_slice()).expect("unable to deserialize message");
+
+        match returned_value {
+            Value::CustomValue { val, span } => {
+                let custom_value = val
+                    .as_any()
+                    .downcast_ref::<PluginCustomValue>()
+                    .expect("a plugin custom value");
+
+                assert_eq!(custom_value.name, "custom");
+                assert_eq!(custom_value.data, vec![1, 2, 3]);
+                assert_eq!(span, Span { start: 0, end: 10 });
+            }
+            _ => panic!("decoded into wrong value"),
+        }
+    }
+
     #[test]
     fn value_nothing_round_trip() {
         // Since nothing doesn't implement PartialOrd, we only compare that the
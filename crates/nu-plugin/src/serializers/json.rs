@@ -1,50 +1,74 @@
 use nu_protocol::ShellError;
+use serde::Deserialize;
 
-use crate::{plugin::PluginEncoder, protocol::PluginResponse};
+use crate::{
+    plugin::PluginEncoder,
+    protocol::{PluginCallId, PluginResponse},
+};
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct JsonSerializer;
 
+// Every message is written as a `[id, message]` pair followed by a new line so
+// several messages can be sent through the same stream
 impl PluginEncoder for JsonSerializer {
     fn encode_call(
         &self,
+        id: PluginCallId,
         plugin_call: &crate::protocol::PluginCall,
         writer: &mut impl std::io::Write,
     ) -> Result<(), nu_protocol::ShellError> {
-        serde_json::to_writer(writer, plugin_call)
+        serde_json::to_writer(&mut *writer, &(id, plugin_call))
+            .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))?;
+
+        writer
+            .write_all(b"\n")
             .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))
     }
 
     fn decode_call(
         &self,
         reader: &mut impl std::io::BufRead,
-    ) -> Result<crate::protocol::PluginCall, nu_protocol::ShellError> {
-        serde_json::from_reader(reader)
-            .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))
+    ) -> Result<(PluginCallId, crate::protocol::PluginCall), nu_protocol::ShellError> {
+        // Only one message is read from the stream. The rest is left for the next call
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+
+        Deserialize::deserialize(&mut deserializer)
+            .map_err(|err| ShellError::PluginFailedToDecode(err.to_string()))
     }
 
     fn encode_response(
         &self,
+        id: PluginCallId,
         plugin_response: &PluginResponse,
         writer: &mut impl std::io::Write,
     ) -> Result<(), ShellError> {
-        serde_json::to_writer(writer, plugin_response)
+        serde_json::to_writer(&mut *writer, &(id, plugin_response))
+            .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))?;
+
+        writer
+            .write_all(b"\n")
             .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))
     }
 
     fn decode_response(
         &self,
         reader: &mut impl std::io::BufRead,
-    ) -> Result<PluginResponse, ShellError> {
-        serde_json::from_reader(reader)
-            .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))
+    ) -> Result<(PluginCallId, PluginResponse), ShellError> {
+        let mut deserializer = serde_json::Deserializer::from_reader(reader);
+
+        Deserialize::deserialize(&mut deserializer)
+            .map_err(|err| ShellError::PluginFailedToDecode(err.to_string()))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::protocol::{CallInfo, EvaluatedCall, LabeledError, PluginCall, PluginResponse};
+    use crate::protocol::{
+        CallInfo, CallInput, EngineCall, EngineCallResponse, EvaluatedCall, LabeledError,
+        PluginCall, PluginCustomValue, PluginResponse, ProtocolInfo, StreamKind,
+    };
     use nu_protocol::{Signature, Span, Spanned, SyntaxShape, Value};
 
     #[test]
@@ -54,15 +78,20 @@ mod tests {
 
         let mut buffer: Vec<u8> = Vec::new();
         encoder
-            .encode_call(&plugin_call, &mut buffer)
+            .encode_call(0, &plugin_call, &mut buffer)
             .expect("unable to serialize message");
-        let returned = encoder
+        let (_, returned) = encoder
             .decode_call(&mut buffer.as_slice())
             .expect("unable to deserialize message");
 
         match returned {
             PluginCall::Signature => {}
             PluginCall::CallInfo(_) => panic!("decoded into wrong value"),
+            PluginCall::StreamData(_) | PluginCall::StreamEnd => panic!("decoded into wrong value"),
+            PluginCall::Goodbye
+            | PluginCall::CollapseCustomValue(_)
+            | PluginCall::EngineCallResponse(_)
+            | PluginCall::Hello(_) => panic!("decoded into wrong value"),
         }
     }
 
@@ -102,23 +131,28 @@ mod tests {
         let plugin_call = PluginCall::CallInfo(Box::new(CallInfo {
             name: name.clone(),
             call: call.clone(),
-            input: input.clone(),
+            input: CallInput::Value(input.clone()),
         }));
 
         let encoder = JsonSerializer {};
         let mut buffer: Vec<u8> = Vec::new();
         encoder
-            .encode_call(&plugin_call, &mut buffer)
+            .encode_call(0, &plugin_call, &mut buffer)
             .expect("unable to serialize message");
-        let returned = encoder
+        let (_, returned) = encoder
             .decode_call(&mut buffer.as_slice())
             .expect("unable to deserialize message");
 
         match returned {
             PluginCall::Signature => panic!("returned wrong call type"),
+            PluginCall::StreamData(_) | PluginCall::StreamEnd => panic!("returned wrong call type"),
+            PluginCall::Goodbye
+            | PluginCall::CollapseCustomValue(_)
+            | PluginCall::EngineCallResponse(_)
+            | PluginCall::Hello(_) => panic!("returned wrong call type"),
             PluginCall::CallInfo(call_info) => {
                 assert_eq!(name, call_info.name);
-                assert_eq!(input, call_info.input);
+                assert_eq!(CallInput::Value(input), call_info.input);
                 assert_eq!(call.head, call_info.call.head);
                 assert_eq!(call.positional.len(), call_info.call.positional.len());
 
@@ -163,14 +197,19 @@ mod tests {
         let encoder = JsonSerializer {};
         let mut buffer: Vec<u8> = Vec::new();
         encoder
-            .encode_response(&response, &mut buffer)
+            .encode_response(0, &response, &mut buffer)
             .expect("unable to serialize message");
-        let returned = encoder
+        let (_, returned) = encoder
             .decode_response(&mut buffer.as_slice())
             .expect("unable to deserialize message");
 
         match returned {
             PluginResponse::Error(_) => panic!("returned wrong call type"),
+            PluginResponse::StreamStart(_)
+            | PluginResponse::StreamData(_)
+            | PluginResponse::StreamEnd
+            | PluginResponse::EngineCall(_)
+            | PluginResponse::Hello(_) => panic!("returned wrong call type"),
             PluginResponse::Value(_) => panic!("returned wrong call type"),
             PluginResponse::Signature(returned_signature) => {
                 assert!(returned_signature.len() == 1);
@@ -217,14 +256,19 @@ mod tests {
         let encoder = JsonSerializer {};
         let mut buffer: Vec<u8> = Vec::new();
         encoder
-            .encode_response(&response, &mut buffer)
+            .encode_response(0, &response, &mut buffer)
             .expect("unable to serialize message");
-        let returned = encoder
+        let (_, returned) = encoder
             .decode_response(&mut buffer.as_slice())
             .expect("unable to deserialize message");
 
         match returned {
             PluginResponse::Error(_) => panic!("returned wrong call type"),
+            PluginResponse::StreamStart(_)
+            | PluginResponse::StreamData(_)
+            | PluginResponse::StreamEnd
+            | PluginResponse::EngineCall(_)
+            | PluginResponse::Hello(_) => panic!("returned wrong call type"),
             PluginResponse::Signature(_) => panic!("returned wrong call type"),
             PluginResponse::Value(returned_value) => {
                 assert_eq!(&value, returned_value.as_ref())
@@ -238,21 +282,28 @@ mod tests {
             label: "label".into(),
             msg: "msg".into(),
             span: Some(Span { start: 2, end: 30 }),
+            labels: vec![],
+            help: None,
         };
         let response = PluginResponse::Error(error.clone());
 
         let encoder = JsonSerializer {};
         let mut buffer: Vec<u8> = Vec::new();
         encoder
-            .encode_response(&response, &mut buffer)
+            .encode_response(0, &response, &mut buffer)
             .expect("unable to serialize message");
-        let returned = encoder
+        let (_, returned) = encoder
             .decode_response(&mut buffer.as_slice())
             .expect("unable to deserialize message");
 
         match returned {
             PluginResponse::Error(msg) => assert_eq!(error, msg),
             PluginResponse::Signature(_) => panic!("returned wrong call type"),
+            PluginResponse::StreamStart(_)
+            | PluginResponse::StreamData(_)
+            | PluginResponse::StreamEnd
+            | PluginResponse::EngineCall(_)
+            | PluginResponse::Hello(_) => panic!("returned wrong call type"),
             PluginResponse::Value(_) => panic!("returned wrong call type"),
         }
     }
@@ -263,22 +314,255 @@ mod tests {
             label: "label".into(),
             msg: "msg".into(),
             span: None,
+            labels: vec![],
+            help: None,
         };
         let response = PluginResponse::Error(error.clone());
 
         let encoder = JsonSerializer {};
         let mut buffer: Vec<u8> = Vec::new();
         encoder
-            .encode_response(&response, &mut buffer)
+            .encode_response(0, &response, &mut buffer)
             .expect("unable to serialize message");
-        let returned = encoder
+        let (_, returned) = encoder
             .decode_response(&mut buffer.as_slice())
             .expect("unable to deserialize message");
 
         match returned {
             PluginResponse::Error(msg) => assert_eq!(error, msg),
             PluginResponse::Signature(_) => panic!("returned wrong call type"),
+            PluginResponse::StreamStart(_)
+            | PluginResponse::StreamData(_)
+            | PluginResponse::StreamEnd
+            | PluginResponse::EngineCall(_)
+            | PluginResponse::Hello(_) => panic!("returned wrong call type"),
             PluginResponse::Value(_) => panic!("returned wrong call type"),
         }
     }
+
+    #[test]
+    fn calls_share_a_stream() {
+        let encoder = JsonSerializer {};
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encoder
+            .encode_call(1, &PluginCall::Signature, &mut buffer)
+            .expect("unable to serialize message");
+        encoder
+            .encode_call(2, &PluginCall::Goodbye, &mut buffer)
+            .expect("unable to serialize message");
+
+        let mut reader = buffer.as_slice();
+
+        let (id, returned) = encoder
+            .decode_call(&mut reader)
+            .expect("unable to deserialize message");
+        assert_eq!(1, id);
+        assert!(matches!(returned, PluginCall::Signature));
+
+        let (id, returned) = encoder
+            .decode_call(&mut reader)
+            .expect("unable to deserialize message");
+        assert_eq!(2, id);
+        assert!(matches!(returned, PluginCall::Goodbye));
+    }
+
+    #[test]
+    fn callinfo_round_trip_stream_input() {
+        let call = EvaluatedCall {
+            head: Span { start: 0, end: 10 },
+            positional: vec![],
+            named: vec![],
+        };
+
+        let plugin_call = PluginCall::CallInfo(Box::new(CallInfo {
+            name: "test".into(),
+            call,
+            input: CallInput::Stream(StreamKind::String),
+        }));
+
+        let encoder = JsonSerializer {};
+        let mut buffer: Vec<u8> = Vec::new();
+        encoder
+            .encode_call(0, &plugin_call, &mut buffer)
+            .expect("unable to serialize message");
+        let (_, returned) = encoder
+            .decode_call(&mut buffer.as_slice())
+            .expect("unable to deserialize message");
+
+        match returned {
+            PluginCall::CallInfo(call_info) => {
+                assert_eq!(CallInput::Stream(StreamKind::String), call_info.input)
+            }
+            _ => panic!("returned wrong call type"),
+        }
+    }
+
+    #[test]
+    fn response_stream_round_trip() {
+        let value = Value::Binary {
+            val: vec![1, 2, 3],
+            span: Span { start: 2, end: 30 },
+        };
+
+        let encoder = JsonSerializer {};
+        let mut buffer: Vec<u8> = Vec::new();
+        for response in [
+            PluginResponse::StreamStart(StreamKind::Binary),
+            PluginResponse::StreamData(Box::new(value.clone())),
+            PluginResponse::StreamEnd,
+        ] {
+            encoder
+                .encode_response(3, &response, &mut buffer)
+                .expect("unable to serialize message");
+        }
+
+        let mut reader = buffer.as_slice();
+
+        let (id, returned) = encoder
+            .decode_response(&mut reader)
+            .expect("unable to deserialize message");
+        assert_eq!(3, id);
+        assert!(matches!(
+            returned,
+            PluginResponse::StreamStart(StreamKind::Binary)
+        ));
+
+        match encoder.decode_response(&mut reader) {
+            Ok((3, PluginResponse::StreamData(returned_value))) => {
+                assert_eq!(&value, returned_value.as_ref())
+            }
+            _ => panic!("returned wrong response type"),
+        }
+
+        let (_, returned) = encoder
+            .decode_response(&mut reader)
+            .expect("unable to deserialize message");
+        assert!(matches!(returned, PluginResponse::StreamEnd));
+    }
+
+    #[test]
+    fn collapse_custom_value_round_trip() {
+        let value = Value::CustomValue {
+            val: Box::new(PluginCustomValue {
+                name: "custom".into(),
+                data: vec![1, 2, 3],
+                source: None,
+            }),
+            span: Span { start: 0, end: 10 },
+        };
+
+        let encoder = JsonSerializer {};
+        let mut buffer: Vec<u8> = Vec::new();
+        encoder
+            .encode_call(
+                4,
+                &PluginCall::CollapseCustomValue(Box::new(value)),
+                &mut buffer,
+            )
+            .expect("unable to serialize message");
+
+        match encoder.decode_call(&mut buffer.as_slice()) {
+            Ok((4, PluginCall::CollapseCustomValue(returned_value))) => match *returned_value {
+                Value::CustomValue { val, .. } => {
+                    let custom_value = val
+                        .as_any()
+                        .downcast_ref::<PluginCustomValue>()
+                        .expect("a plugin custom value");
+
+                    assert_eq!(custom_value.name, "custom");
+                    assert_eq!(custom_value.data, vec![1, 2, 3]);
+                }
+                _ => panic!("decoded into wrong value"),
+            },
+            _ => panic!("returned wrong call type"),
+        }
+    }
+
+    #[test]
+    fn engine_call_round_trip() {
+        let encoder = JsonSerializer {};
+        let mut buffer: Vec<u8> = Vec::new();
+        encoder
+            .encode_response(
+                2,
+                &PluginResponse::EngineCall(Box::new(EngineCall::GetEnvVar("PATH".into()))),
+                &mut buffer,
+            )
+            .expect("unable to serialize message");
+
+        match encoder.decode_response(&mut buffer.as_slice()) {
+            Ok((2, PluginResponse::EngineCall(engine_call))) => {
+                assert!(matches!(*engine_call, EngineCall::GetEnvVar(name) if name == "PATH"))
+            }
+            _ => panic!("returned wrong response type"),
+        }
+
+        let value = Value::String {
+            val: "/bin".into(),
+            span: Span { start: 0, end: 10 },
+        };
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encoder
+            .encode_call(
+                2,
+                &PluginCall::EngineCallResponse(Box::new(EngineCallResponse::Value(Box::new(
+                    value.clone(),
+                )))),
+                &mut buffer,
+            )
+            .expect("unable to serialize message");
+
+        match encoder.decode_call(&mut buffer.as_slice()) {
+            Ok((2, PluginCall::EngineCallResponse(response))) => match *response {
+                EngineCallResponse::Value(returned_value) => assert_eq!(value, *returned_value),
+                EngineCallResponse::Error(_) => panic!("returned wrong engine call response"),
+            },
+            _ => panic!("returned wrong call type"),
+        }
+    }
+
+    #[test]
+    fn hello_round_trip() {
+        let info = ProtocolInfo::default();
+        let encoder = JsonSerializer {};
+
+        let mut buffer: Vec<u8> = Vec::new();
+        encoder
+            .encode_call(0, &PluginCall::Hello(info.clone()), &mut buffer)
+            .expect("unable to serialize message");
+        encoder
+            .encode_response(0, &PluginResponse::Hello(info.clone()), &mut buffer)
+            .expect("unable to serialize message");
+
+        let mut reader = buffer.as_slice();
+
+        match encoder.decode_call(&mut reader) {
+            Ok((0, PluginCall::Hello(returned))) => assert_eq!(info, returned),
+            _ => panic!("returned wrong call type"),
+        }
+
+        match encoder.decode_response(&mut reader) {
+            Ok((0, PluginResponse::Hello(returned))) => {
+                assert!(info.is_compatible(&returned));
+                assert!(returned.supports("engine-calls"));
+            }
+            _ => panic!("returned wrong response type"),
+        }
+    }
+
+    #[test]
+    fn error_without_labels_or_help() {
+        // Plugins written before errors had labels and help still send errors like this
+        let message = br#"[3,{"Error":{"label":"label","msg":"msg","span":null}}]"#;
+        let encoder = JsonSerializer {};
+
+        match encoder.decode_response(&mut message.as_ref()) {
+            Ok((3, PluginResponse::Error(returned))) => {
+                assert_eq!(LabeledError::new("label", "msg", None), returned)
+            }
+            _ => panic!("returned wrong response type"),
+        }
+    }
 }
@@ -2,16 +2,18 @@ use nu_protocol::ShellError;
 
 use crate::{
     plugin::PluginEncoder,
-    protocol::{PluginCall, PluginResponse},
+    protocol::{PluginCall, PluginCallId, PluginResponse},
 };
 
 pub mod capnp;
 pub mod json;
+pub mod msgpack;
 
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub enum EncodingType {
     Capnp(capnp::CapnpSerializer),
     Json(json::JsonSerializer),
+    MsgPack(msgpack::MsgPackSerializer),
 }
 
 impl EncodingType {
@@ -19,49 +21,56 @@ impl EncodingType {
         match bytes {
             b"capnp" => Some(Self::Capnp(capnp::CapnpSerializer {})),
             b"json" => Some(Self::Json(json::JsonSerializer {})),
+            b"msgpack" => Some(Self::MsgPack(msgpack::MsgPackSerializer {})),
             _ => None,
         }
     }
 
     pub fn encode_call(
         &self,
+        id: PluginCallId,
         plugin_call: &PluginCall,
         writer: &mut impl std::io::Write,
     ) -> Result<(), ShellError> {
         match self {
-            EncodingType::Capnp(encoder) => encoder.encode_call(plugin_call, writer),
-            EncodingType::Json(encoder) => encoder.encode_call(plugin_call, writer),
+            EncodingType::Capnp(encoder) => encoder.encode_call(id, plugin_call, writer),
+            EncodingType::Json(encoder) => encoder.encode_call(id, plugin_call, writer),
+            EncodingType::MsgPack(encoder) => encoder.encode_call(id, plugin_call, writer),
         }
     }
 
     pub fn decode_call(
         &self,
         reader: &mut impl std::io::BufRead,
-    ) -> Result<PluginCall, ShellError> {
+    ) -> Result<(PluginCallId, PluginCall), ShellError> {
         match self {
             EncodingType::Capnp(encoder) => encoder.decode_call(reader),
             EncodingType::Json(encoder) => encoder.decode_call(reader),
+            EncodingType::MsgPack(encoder) => encoder.decode_call(reader),
         }
     }
 
     pub fn encode_response(
         &self,
+        id: PluginCallId,
         plugin_response: &PluginResponse,
         writer: &mut impl std::io::Write,
     ) -> Result<(), ShellError> {
         match self {
-            EncodingType::Capnp(encoder) => encoder.encode_response(plugin_response, writer),
-            EncodingType::Json(encoder) => encoder.encode_response(plugin_response, writer),
+            EncodingType::Capnp(encoder) => encoder.encode_response(id, plugin_response, writer),
+            EncodingType::Json(encoder) => encoder.encode_response(id, plugin_response, writer),
+            EncodingType::MsgPack(encoder) => encoder.encode_response(id, plugin_response, writer),
         }
     }
 
     pub fn decode_response(
         &self,
         reader: &mut impl std::io::BufRead,
-    ) -> Result<PluginResponse, ShellError> {
+    ) -> Result<(PluginCallId, PluginResponse), ShellError> {
         match self {
             EncodingType::Capnp(encoder) => encoder.decode_response(reader),
             EncodingType::Json(encoder) => encoder.decode_response(reader),
+            EncodingType::MsgPack(encoder) => encoder.decode_response(reader),
         }
     }
 
@@ -69,6 +78,7 @@ impl EncodingType {
         match self {
             Self::Capnp(_) => "capnp",
             Self::Json(_) => "json",
+            Self::MsgPack(_) => "msgpack",
         }
     }
 }
@@ -0,0 +1,176 @@
+use nu_protocol::ShellError;
+
+use crate::{
+    plugin::PluginEncoder,
+    protocol::{PluginCall, PluginCallId, PluginResponse},
+};
+
+#[derive(Clone, Debug)]
+pub struct MsgPackSerializer;
+
+// Every message is written as a `[id, message]` array. MessagePack values know their
+// own length, so several messages can follow each other in the same stream. Structs are
+// written as maps with their field names, the same shape as the JSON messages
+impl PluginEncoder for MsgPackSerializer {
+    fn encode_call(
+        &self,
+        id: PluginCallId,
+        plugin_call: &PluginCall,
+        writer: &mut impl std::io::Write,
+    ) -> Result<(), ShellError> {
+        rmp_serde::encode::write_named(writer, &(id, plugin_call))
+            .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))
+    }
+
+    fn decode_call(
+        &self,
+        reader: &mut impl std::io::BufRead,
+    ) -> Result<(PluginCallId, PluginCall), ShellError> {
+        // Only one message is read from the stream. The rest is left for the next call
+        rmp_serde::from_read(reader)
+            .map_err(|err| ShellError::PluginFailedToDecode(err.to_string()))
+    }
+
+    fn encode_response(
+        &self,
+        id: PluginCallId,
+        plugin_response: &PluginResponse,
+        writer: &mut impl std::io::Write,
+    ) -> Result<(), ShellError> {
+        rmp_serde::encode::write_named(writer, &(id, plugin_response))
+            .map_err(|err| ShellError::PluginFailedToEncode(err.to_string()))
+    }
+
+    fn decode_response(
+        &self,
+        reader: &mut impl std::io::BufRead,
+    ) -> Result<(PluginCallId, PluginResponse), ShellError> {
+        rmp_serde::from_read(reader)
+            .map_err(|err| ShellError::PluginFailedToDecode(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::{CallInfo, CallInput, EvaluatedCall, LabeledError, StreamKind};
+    use chrono::DateTime;
+    use nu_protocol::{Signature, Span, SyntaxShape, Value};
+
+    #[test]
+    fn callinfo_round_trip_all_values() {
+        let span = Span { start: 0, end: 10 };
+        let input = Value::Record {
+            cols: vec![
+                "binary".into(),
+                "date".into(),
+                "duration".into(),
+                "filesize".into(),
+                "list".into(),
+            ],
+            vals: vec![
+                Value::Binary {
+                    val: vec![0, 1, 255],
+                    span,
+                },
+                Value::Date {
+                    val: DateTime::parse_from_rfc3339("2021-12-01T10:30:00+02:00")
+                        .expect("valid date"),
+                    span,
+                },
+                Value::Duration {
+                    val: 1_000_000_000,
+                    span,
+                },
+                Value::Filesize { val: 1024, span },
+                Value::List {
+                    vals: vec![
+                        Value::Float { val: 1.5, span },
+                        Value::Nothing { span },
+                        Value::Bool { val: true, span },
+                    ],
+                    span,
+                },
+            ],
+            span,
+        };
+
+        let plugin_call = PluginCall::CallInfo(Box::new(CallInfo {
+            name: "test".into(),
+            call: EvaluatedCall {
+                head: span,
+                positional: vec![Value::String {
+                    val: "something".into(),
+                    span,
+                }],
+                named: vec![],
+            },
+            input: CallInput::Value(input.clone()),
+        }));
+
+        let encoder = MsgPackSerializer {};
+        let mut buffer: Vec<u8> = Vec::new();
+        encoder
+            .encode_call(7, &plugin_call, &mut buffer)
+            .expect("unable to serialize message");
+
+        match encoder.decode_call(&mut buffer.as_slice()) {
+            Ok((7, PluginCall::CallInfo(call_info))) => {
+                assert_eq!("test", call_info.name);
+                assert_eq!(CallInput::Value(input), call_info.input);
+                assert_eq!(1, call_info.call.positional.len());
+            }
+            _ => panic!("returned wrong call type"),
+        }
+    }
+
+    #[test]
+    fn responses_share_a_stream() {
+        let signature = Signature::build("nu-plugin")
+            .required("first", SyntaxShape::String, "first required")
+            .rest("remaining", SyntaxShape::Int, "remaining");
+        let error = LabeledError {
+            label: "label".into(),
+            msg: "msg".into(),
+            span: None,
+            labels: vec![],
+            help: None,
+        };
+
+        let encoder = MsgPackSerializer {};
+        let mut buffer: Vec<u8> = Vec::new();
+        for response in [
+            PluginResponse::Signature(vec![signature.clone()]),
+            PluginResponse::StreamStart(StreamKind::Binary),
+            PluginResponse::Error(error.clone()),
+        ] {
+            encoder
+                .encode_response(1, &response, &mut buffer)
+                .expect("unable to serialize message");
+        }
+
+        let mut reader = buffer.as_slice();
+
+        match encoder.decode_response(&mut reader) {
+            Ok((1, PluginResponse::Signature(returned))) => {
+                assert_eq!(signature.name, returned[0].name);
+                assert_eq!(
+                    signature.required_positional,
+                    returned[0].required_positional
+                );
+                assert_eq!(signature.rest_positional, returned[0].rest_positional);
+            }
+            _ => panic!("returned wrong response type"),
+        }
+
+        assert!(matches!(
+            encoder.decode_response(&mut reader),
+            Ok((1, PluginResponse::StreamStart(StreamKind::Binary)))
+        ));
+
+        match encoder.decode_response(&mut reader) {
+            Ok((1, PluginResponse::Error(returned))) => assert_eq!(error, returned),
+            _ => panic!("returned wrong response type"),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use super::{Call, CellPath, Expression, FullCellPath, Operator, RangeOperator};
+use super::{
+    Call, CellPath, Expression, FullCellPath, MatchPattern, Operator, RangeOperator, Redirection,
+};
 use crate::{ast::ImportPattern, BlockId, Signature, Span, Spanned, Unit, VarId};
 
 #[derive(Debug, Clone)]
@@ -15,15 +17,23 @@ pub enum Expr {
     Var(VarId),
     VarDecl(VarId),
     Call(Box<Call>),
-    ExternalCall(String, Span, Vec<Expression>),
+    ExternalCall(
+        String,
+        Span,
+        Vec<Expression>,
+        Vec<(Redirection, Expression)>,
+    ), // name, name span, args, redirections
     Operator(Operator),
     RowCondition(BlockId),
     BinaryOp(Box<Expression>, Box<Expression>, Box<Expression>), //lhs, op, rhs
+    Assignment(Box<Expression>, Box<Expression>, Box<Expression>), //lhs, op, rhs
     Subexpression(BlockId),
     Block(BlockId),
     List(Vec<Expression>),
     Table(Vec<Expression>, Vec<Vec<Expression>>),
     Record(Vec<(Expression, Expression)>),
+    MatchBlock(Vec<(MatchPattern, Expression)>),
+    MatchPattern(Box<MatchPattern>),
     Keyword(Vec<u8>, Span, Box<Expression>),
     ValueWithUnit(Box<Expression>, Spanned<Unit>),
     Filepath(String),
@@ -26,10 +26,16 @@ impl Expression {
 
                 match operator {
                     Operator::Pow => 100,
-                    Operator::Multiply | Operator::Divide | Operator::Modulo => 95,
+                    Operator::Multiply
+                    | Operator::Divide
+                    | Operator::FloorDivision
+                    | Operator::Modulo => 95,
                     Operator::Plus | Operator::Minus => 90,
-                    Operator::NotContains
-                    | Operator::Contains
+                    Operator::ShiftLeft | Operator::ShiftRight => 85,
+                    Operator::NotRegexMatch
+                    | Operator::RegexMatch
+                    | Operator::StartsWith
+                    | Operator::EndsWith
                     | Operator::LessThan
                     | Operator::LessThanOrEqual
                     | Operator::GreaterThan
@@ -38,8 +44,12 @@ impl Expression {
                     | Operator::NotEqual
                     | Operator::In
                     | Operator::NotIn => 80,
+                    Operator::BitAnd => 75,
+                    Operator::BitXor => 70,
+                    Operator::BitOr => 60,
                     Operator::And => 50,
                     Operator::Or => 40,
+                    Operator::Assign | Operator::PlusAssign | Operator::MinusAssign => 10,
                 }
             }
             _ => 0,
@@ -98,7 +108,7 @@ impl Expression {
 
     pub fn has_in_variable(&self, working_set: &StateWorkingSet) -> bool {
         match &self.expr {
-            Expr::BinaryOp(left, _, right) => {
+            Expr::BinaryOp(left, _, right) | Expr::Assignment(left, _, right) => {
                 left.has_in_variable(working_set) || right.has_in_variable(working_set)
             }
             Expr::Block(block_id) => {
@@ -130,8 +140,11 @@ impl Expression {
                 false
             }
             Expr::CellPath(_) => false,
-            Expr::ExternalCall(_, _, args) => {
-                for arg in args {
+            Expr::ExternalCall(_, _, args, redirections) => {
+                for arg in args
+                    .iter()
+                    .chain(redirections.iter().map(|(_, target)| target))
+                {
                     if arg.has_in_variable(working_set) {
                         return true;
                     }
@@ -198,6 +211,20 @@ impl Expression {
                 }
                 false
             }
+            Expr::MatchBlock(arms) => {
+                for (pattern, expr) in arms {
+                    if let Some(guard) = &pattern.guard {
+                        if guard.has_in_variable(working_set) {
+                            return true;
+                        }
+                    }
+                    if expr.has_in_variable(working_set) {
+                        return true;
+                    }
+                }
+                false
+            }
+            Expr::MatchPattern(_) => false,
             Expr::Signature(_) => false,
             Expr::String(_) => false,
             Expr::RowCondition(block_id) | Expr::Subexpression(block_id) => {
@@ -239,7 +266,7 @@ impl Expression {
 
     pub fn replace_in_variable(&mut self, working_set: &mut StateWorkingSet, new_var_id: VarId) {
         match &mut self.expr {
-            Expr::BinaryOp(left, _, right) => {
+            Expr::BinaryOp(left, _, right) | Expr::Assignment(left, _, right) => {
                 left.replace_in_variable(working_set, new_var_id);
                 right.replace_in_variable(working_set, new_var_id);
             }
@@ -286,10 +313,13 @@ impl Expression {
                 }
             }
             Expr::CellPath(_) => {}
-            Expr::ExternalCall(_, _, args) => {
+            Expr::ExternalCall(_, _, args, redirections) => {
                 for arg in args {
                     arg.replace_in_variable(working_set, new_var_id)
                 }
+                for (_, target) in redirections {
+                    target.replace_in_variable(working_set, new_var_id)
+                }
             }
             Expr::Filepath(_) => {}
             Expr::Float(_) => {}
@@ -327,6 +357,15 @@ impl Expression {
                     field_value.replace_in_variable(working_set, new_var_id);
                 }
             }
+            Expr::MatchBlock(arms) => {
+                for (pattern, expr) in arms {
+                    if let Some(guard) = &mut pattern.guard {
+                        guard.replace_in_variable(working_set, new_var_id)
+                    }
+                    expr.replace_in_variable(working_set, new_var_id)
+                }
+            }
+            Expr::MatchPattern(_) => {}
             Expr::Signature(_) => {}
             Expr::String(_) => {}
             Expr::StringInterpolation(items) => {
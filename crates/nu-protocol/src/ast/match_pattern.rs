@@ -0,0 +1,52 @@
+use super::Expression;
+use crate::{Span, VarId};
+
+/// One arm of a `match`: a pattern with an optional guard, eg) `[$a, $b] if $a > $b`
+#[derive(Debug, Clone)]
+pub struct MatchPattern {
+    pub pattern: Pattern,
+    pub guard: Option<Box<Expression>>,
+    pub span: Span,
+}
+
+impl MatchPattern {
+    /// The variables bound when the pattern matches
+    pub fn variables(&self) -> Vec<VarId> {
+        self.pattern.variables()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// A literal value or a range of values, eg) `1`, `'foo'` or `1..10`
+    Value(Box<Expression>),
+    /// Binds the value to a variable, eg) `$x`
+    Variable(VarId),
+    /// A list, possibly ending with a rest pattern, eg) `[$first, ..$rest]`
+    List(Vec<MatchPattern>),
+    /// A record with at least the given fields, eg) `{name: $n}`
+    Record(Vec<(String, MatchPattern)>),
+    /// Binds the remaining items of a list, eg) `..$rest`
+    Rest(VarId),
+    /// Ignores the remaining items of a list, eg) `..`
+    IgnoreRest,
+    /// Matches any value, eg) `_`
+    IgnoreValue,
+    Garbage,
+}
+
+impl Pattern {
+    pub fn variables(&self) -> Vec<VarId> {
+        match self {
+            Pattern::Variable(var_id) | Pattern::Rest(var_id) => vec![*var_id],
+            Pattern::List(items) => items.iter().flat_map(|item| item.variables()).collect(),
+            Pattern::Record(fields) => fields
+                .iter()
+                .flat_map(|(_, field)| field.variables())
+                .collect(),
+            Pattern::Value(_) | Pattern::IgnoreRest | Pattern::IgnoreValue | Pattern::Garbage => {
+                vec![]
+            }
+        }
+    }
+}
@@ -4,8 +4,10 @@ mod cell_path;
 mod expr;
 mod expression;
 mod import_pattern;
+mod match_pattern;
 mod operator;
 mod pipeline;
+mod redirection;
 mod statement;
 
 pub use block::*;
@@ -14,6 +16,8 @@ pub use cell_path::*;
 pub use expr::*;
 pub use expression::*;
 pub use import_pattern::*;
+pub use match_pattern::*;
 pub use operator::*;
 pub use pipeline::*;
+pub use redirection::*;
 pub use statement::*;
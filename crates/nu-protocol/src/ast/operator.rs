@@ -11,18 +11,29 @@ pub enum Operator {
     GreaterThan,
     LessThanOrEqual,
     GreaterThanOrEqual,
-    Contains,
-    NotContains,
+    RegexMatch,
+    NotRegexMatch,
     Plus,
     Minus,
     Multiply,
     Divide,
+    FloorDivision,
     In,
     NotIn,
+    StartsWith,
+    EndsWith,
     Modulo,
     And,
     Or,
     Pow,
+    BitOr,
+    BitXor,
+    BitAnd,
+    ShiftLeft,
+    ShiftRight,
+    Assign,
+    PlusAssign,
+    MinusAssign,
 }
 
 impl Display for Operator {
@@ -32,20 +43,31 @@ impl Display for Operator {
             Operator::NotEqual => write!(f, "!="),
             Operator::LessThan => write!(f, "<"),
             Operator::GreaterThan => write!(f, ">"),
-            Operator::Contains => write!(f, "=~"),
-            Operator::NotContains => write!(f, "!~"),
+            Operator::RegexMatch => write!(f, "=~"),
+            Operator::NotRegexMatch => write!(f, "!~"),
             Operator::Plus => write!(f, "+"),
             Operator::Minus => write!(f, "-"),
             Operator::Multiply => write!(f, "*"),
             Operator::Divide => write!(f, "/"),
+            Operator::FloorDivision => write!(f, "//"),
             Operator::In => write!(f, "in"),
             Operator::NotIn => write!(f, "not-in"),
+            Operator::StartsWith => write!(f, "starts-with"),
+            Operator::EndsWith => write!(f, "ends-with"),
             Operator::Modulo => write!(f, "mod"),
             Operator::And => write!(f, "&&"),
             Operator::Or => write!(f, "||"),
             Operator::Pow => write!(f, "**"),
+            Operator::BitOr => write!(f, "bit-or"),
+            Operator::BitXor => write!(f, "bit-xor"),
+            Operator::BitAnd => write!(f, "bit-and"),
+            Operator::ShiftLeft => write!(f, "bit-shl"),
+            Operator::ShiftRight => write!(f, "bit-shr"),
             Operator::LessThanOrEqual => write!(f, "<="),
             Operator::GreaterThanOrEqual => write!(f, ">="),
+            Operator::Assign => write!(f, "="),
+            Operator::PlusAssign => write!(f, "+="),
+            Operator::MinusAssign => write!(f, "-="),
         }
     }
 }
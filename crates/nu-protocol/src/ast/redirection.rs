@@ -0,0 +1,10 @@
+/// Which output of an external is sent to a file, eg) `err> log.txt`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Redirection {
+    /// `out>`
+    Stdout,
+    /// `err>`
+    Stderr,
+    /// `out+err>`
+    StdoutAndStderr,
+}
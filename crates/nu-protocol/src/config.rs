@@ -53,6 +53,7 @@ pub struct Config {
     pub env_conversions: HashMap<String, EnvConversion>,
     pub edit_mode: String,
     pub max_history_size: i64,
+    pub external_shell: bool,
 }
 
 impl Default for Config {
@@ -71,6 +72,7 @@ impl Default for Config {
             env_conversions: HashMap::new(), // TODO: Add default conversoins
             edit_mode: "emacs".into(),
             max_history_size: 1000,
+            external_shell: false,
         }
     }
 }
@@ -186,6 +188,9 @@ impl Value {
                 "max_history_size" => {
                     config.max_history_size = value.as_i64()?;
                 }
+                "external_shell" => {
+                    config.external_shell = value.as_bool()?;
+                }
                 _ => {}
             }
         }
@@ -0,0 +1,10 @@
+use std::collections::HashMap;
+
+use crate::{BlockId, Value, VarId};
+
+/// A block together with the values of the variables it captured when it was created
+#[derive(Clone, Debug)]
+pub struct CaptureBlock {
+    pub block_id: BlockId,
+    pub captures: HashMap<VarId, Value>,
+}
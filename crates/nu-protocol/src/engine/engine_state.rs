@@ -1,17 +1,16 @@
 use super::Command;
 use crate::{
     ast::Block, BlockId, DeclId, Example, Overlay, OverlayId, ShellError, Signature, Span, Type,
-    VarId,
+    VarId, Variable,
 };
 use core::panic;
+use regex::Regex;
 use std::{
     collections::HashMap,
-    sync::{atomic::AtomicBool, Arc},
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc, Mutex},
 };
 
-#[cfg(feature = "plugin")]
-use std::path::PathBuf;
-
 // Tells whether a decl etc. is visible or not
 #[derive(Debug, Clone)]
 struct Visibility {
@@ -134,16 +133,24 @@ impl Default for ScopeFrame {
 pub struct EngineState {
     files: im::Vector<(String, usize, usize)>,
     file_contents: im::Vector<(Vec<u8>, usize, usize)>,
-    vars: im::Vector<Type>,
+    vars: im::Vector<Variable>,
     decls: im::Vector<Box<dyn Command + 'static>>,
     blocks: im::Vector<Block>,
     overlays: im::Vector<Overlay>,
     pub scope: im::Vector<ScopeFrame>,
     pub ctrlc: Option<Arc<AtomicBool>>,
+    // Compiled patterns of the regex operators, so a `where` over a table compiles each pattern once
+    pub regex_cache: Arc<Mutex<HashMap<String, Regex>>>,
+    // The `PWD` of the stack being run. The parser has no stack, so it resolves the paths of
+    // `source`, `use` and `register` against this
+    pub cwd: Option<PathBuf>,
     #[cfg(feature = "plugin")]
     pub plugin_signatures: Option<PathBuf>,
 }
 
+// The regex cache is cleared once it holds this many patterns
+const REGEX_CACHE_SIZE: usize = 100;
+
 pub const NU_VARIABLE_ID: usize = 0;
 pub const SCOPE_VARIABLE_ID: usize = 1;
 pub const IN_VARIABLE_ID: usize = 2;
@@ -154,12 +161,19 @@ impl EngineState {
         Self {
             files: im::vector![],
             file_contents: im::vector![],
-            vars: im::vector![Type::Unknown, Type::Unknown, Type::Unknown, Type::Unknown],
+            vars: im::vector![
+                Variable::new(Span::new(0, 0), Type::Unknown, false),
+                Variable::new(Span::new(0, 0), Type::Unknown, false),
+                Variable::new(Span::new(0, 0), Type::Unknown, false),
+                Variable::new(Span::new(0, 0), Type::Unknown, false)
+            ],
             decls: im::vector![],
             blocks: im::vector![],
             overlays: im::vector![],
             scope: im::vector![ScopeFrame::new()],
             ctrlc: None,
+            regex_cache: Arc::new(Mutex::new(HashMap::new())),
+            cwd: None,
             #[cfg(feature = "plugin")]
             plugin_signatures: None,
         }
@@ -226,48 +240,43 @@ impl EngineState {
                     .map_err(|err| ShellError::PluginFailedToLoad(err.to_string()))
             })
             .and_then(|mut plugin_file| {
-                // Plugin definitions with parsed signature
-                self.plugin_decls().try_for_each(|decl| {
-                    // A successful plugin registration already includes the plugin filename
-                    // No need to check the None option
-                    let (path, encoding, shell) =
-                        decl.is_plugin().expect("plugin should have file name");
-                    let file_name = path
-                        .to_str()
-                        .expect("path was checked during registration as a str");
-
-                    serde_json::to_string_pretty(&decl.signature())
-                        .map(|signature| {
-                            // Extracting the possible path to the shell used to load the plugin
-                            let shell_str = match shell {
-                                Some(path) => format!(
-                                    "-s {}",
-                                    path.to_str().expect(
-                                        "shell path was checked during registration as a str"
-                                    )
-                                ),
-                                None => "".into(),
-                            };
-
-                            // Each signature is stored in the plugin file with the required
-                            // encoding, shell and signature
-                            // This information will be used when loading the plugin
-                            // information when nushell starts
-                            format!(
-                                "register {} -e {} {} {}\n\n",
-                                file_name, encoding, shell_str, signature
-                            )
-                        })
-                        .map_err(|err| ShellError::PluginFailedToLoad(err.to_string()))
-                        .and_then(|line| {
-                            plugin_file
-                                .write_all(line.as_bytes())
-                                .map_err(|err| ShellError::PluginFailedToLoad(err.to_string()))
-                        })
-                })
+                // Each plugin is registered once, with the encoding and shell used to
+                // load it when nushell starts. Its signatures are taken from the
+                // signature cache, unless the plugin changed since they were cached
+                self.plugins()
+                    .into_iter()
+                    .try_for_each(|(path, encoding, shell)| {
+                        // A successful plugin registration already includes the plugin filename
+                        let file_name = path
+                            .to_str()
+                            .expect("path was checked during registration as a str");
+
+                        // Extracting the possible path to the shell used to load the plugin
+                        let shell_str = match shell {
+                            Some(path) => format!(
+                                " -s {}",
+                                path.to_str()
+                                    .expect("shell path was checked during registration as a str")
+                            ),
+                            None => "".into(),
+                        };
+
+                        let line = format!("register {} -e {}{}\n", file_name, encoding, shell_str);
+                        plugin_file
+                            .write_all(line.as_bytes())
+                            .map_err(|err| ShellError::PluginFailedToLoad(err.to_string()))
+                    })
             })
     }
 
+    // The signature cache lives next to the plugin file
+    #[cfg(feature = "plugin")]
+    pub fn plugin_cache_file(&self) -> Option<PathBuf> {
+        self.plugin_signatures
+            .as_ref()
+            .map(|plugin_path| plugin_path.with_file_name("plugin-cache.json"))
+    }
+
     pub fn num_files(&self) -> usize {
         self.files.len()
     }
@@ -346,6 +355,23 @@ impl EngineState {
         plugin_decls.into_iter().map(|(_, decl)| decl)
     }
 
+    // Registered plugins with their encoding and shell, in the order of their first
+    // command
+    #[cfg(feature = "plugin")]
+    pub fn plugins(&self) -> Vec<(&PathBuf, &str, &Option<PathBuf>)> {
+        let mut plugins: Vec<(&PathBuf, &str, &Option<PathBuf>)> = vec![];
+
+        for decl in self.plugin_decls() {
+            if let Some(plugin) = decl.is_plugin() {
+                if !plugins.iter().any(|(path, ..)| *path == plugin.0) {
+                    plugins.push(plugin);
+                }
+            }
+        }
+
+        plugins
+    }
+
     pub fn find_overlay(&self, name: &[u8]) -> Option<OverlayId> {
         for scope in self.scope.iter().rev() {
             if let Some(overlay_id) = scope.overlays.get(name) {
@@ -380,7 +406,7 @@ impl EngineState {
         panic!("internal error: span missing in file contents cache")
     }
 
-    pub fn get_var(&self, var_id: VarId) -> &Type {
+    pub fn get_var(&self, var_id: VarId) -> &Variable {
         self.vars
             .get(var_id)
             .expect("internal error: missing variable")
@@ -472,6 +498,26 @@ impl EngineState {
             .expect("internal error: missing overlay")
     }
 
+    /// Compile a regex, reusing the compiled form if the pattern was seen before
+    pub fn get_regex(&self, pattern: &str) -> Result<Regex, regex::Error> {
+        let mut cache = match self.regex_cache.lock() {
+            Ok(cache) => cache,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if let Some(regex) = cache.get(pattern) {
+            return Ok(regex.clone());
+        }
+
+        let regex = Regex::new(pattern)?;
+        if cache.len() >= REGEX_CACHE_SIZE {
+            cache.clear();
+        }
+        cache.insert(pattern.to_string(), regex.clone());
+
+        Ok(regex)
+    }
+
     pub fn next_span_start(&self) -> usize {
         if let Some((_, _, last)) = self.file_contents.last() {
             *last
@@ -538,6 +584,10 @@ impl Default for EngineState {
 pub struct StateWorkingSet<'a> {
     pub permanent_state: &'a EngineState,
     pub delta: StateDelta,
+    // How many loop bodies enclose the code currently being parsed
+    pub loop_depth: usize,
+    // Whether the blocks being parsed are arguments of a command that runs them in place
+    pub in_place_blocks: bool,
 }
 
 /// A delta (or change set) between the current global state and a possible future global state. Deltas
@@ -546,7 +596,7 @@ pub struct StateWorkingSet<'a> {
 pub struct StateDelta {
     files: Vec<(String, usize, usize)>,
     pub(crate) file_contents: Vec<(Vec<u8>, usize, usize)>,
-    vars: Vec<Type>,              // indexed by VarId
+    vars: Vec<Variable>,          // indexed by VarId
     decls: Vec<Box<dyn Command>>, // indexed by DeclId
     blocks: Vec<Block>,           // indexed by BlockId
     overlays: Vec<Overlay>,       // indexed by OverlayId
@@ -596,6 +646,8 @@ impl<'a> StateWorkingSet<'a> {
                 plugins_changed: false,
             },
             permanent_state,
+            loop_depth: 0,
+            in_place_blocks: false,
         }
     }
 
@@ -821,6 +873,15 @@ impl<'a> StateWorkingSet<'a> {
         self.num_files() - 1
     }
 
+    /// The directory relative paths are resolved against: the `PWD` of the engine state, or the
+    /// process's current directory if there is none
+    pub fn get_cwd(&self) -> PathBuf {
+        match &self.permanent_state.cwd {
+            Some(cwd) => cwd.clone(),
+            None => std::env::current_dir().unwrap_or_default(),
+        }
+    }
+
     pub fn get_span_contents(&self, span: Span) -> &[u8] {
         let permanent_end = self.permanent_state.next_span_start();
         if permanent_end <= span.start {
@@ -950,7 +1011,13 @@ impl<'a> StateWorkingSet<'a> {
         None
     }
 
-    pub fn add_variable(&mut self, mut name: Vec<u8>, ty: Type) -> VarId {
+    pub fn add_variable(
+        &mut self,
+        mut name: Vec<u8>,
+        span: Span,
+        ty: Type,
+        mutable: bool,
+    ) -> VarId {
         let next_id = self.next_var_id();
 
         // correct name if necessary
@@ -966,7 +1033,7 @@ impl<'a> StateWorkingSet<'a> {
 
         last.vars.insert(name, next_id);
 
-        self.delta.vars.push(ty);
+        self.delta.vars.push(Variable::new(span, ty, mutable));
 
         next_id
     }
@@ -986,11 +1053,11 @@ impl<'a> StateWorkingSet<'a> {
         if var_id < num_permanent_vars {
             panic!("Internal error: attempted to set into permanent state from working set")
         } else {
-            self.delta.vars[var_id - num_permanent_vars] = ty;
+            self.delta.vars[var_id - num_permanent_vars].ty = ty;
         }
     }
 
-    pub fn get_variable(&self, var_id: VarId) -> &Type {
+    pub fn get_variable(&self, var_id: VarId) -> &Variable {
         let num_permanent_vars = self.permanent_state.num_vars();
         if var_id < num_permanent_vars {
             self.permanent_state.get_var(var_id)
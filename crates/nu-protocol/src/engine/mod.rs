@@ -1,9 +1,11 @@
 mod call_info;
+mod capture_block;
 mod command;
 mod engine_state;
 mod stack;
 
 pub use call_info::*;
+pub use capture_block::*;
 pub use command::*;
 pub use engine_state::*;
 pub use stack::*;
@@ -1,5 +1,10 @@
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use super::EngineState;
 use crate::{Config, ShellError, Value, VarId, CONFIG_VARIABLE_ID};
 
 /// A runtime value stack used during evaluation
@@ -25,6 +30,21 @@ pub struct Stack {
     pub vars: HashMap<VarId, Value>,
     /// Environment variables arranged as a stack to be able to recover values from parent scopes
     pub env_vars: Vec<HashMap<String, Value>>,
+    /// Exit codes of externals whose output was piped into another command. They become
+    /// `LAST_EXIT_CODE` once the pipeline that read the output is done
+    pub pending_exit_codes: Vec<PendingExitCode>,
+}
+
+/// Time an external is given to exit once the pipeline it was piped into is done. One that still
+/// runs after that was left behind by a command that stopped reading early, like `first`
+const EXIT_GRACE_PERIOD: Duration = Duration::from_millis(500);
+
+/// Exit code of an external whose output was piped into another command
+#[derive(Debug, Clone)]
+pub struct PendingExitCode {
+    pub exit_code: Arc<Mutex<Receiver<Value>>>,
+    /// Set to have the external killed, as nothing reads its output anymore
+    pub stop: Arc<AtomicBool>,
 }
 
 impl Default for Stack {
@@ -38,6 +58,7 @@ impl Stack {
         Stack {
             vars: HashMap::new(),
             env_vars: vec![],
+            pending_exit_codes: vec![],
         }
     }
 
@@ -61,6 +82,24 @@ impl Stack {
         }
     }
 
+    /// Create a stack for running a closure from the values it captured when it was created
+    pub fn captures_to_stack(&self, captures: &HashMap<VarId, Value>) -> Stack {
+        let mut output = Stack::new();
+
+        output.vars = captures.clone();
+
+        // FIXME: this is probably slow
+        output.env_vars = self.env_vars.clone();
+        output.env_vars.push(HashMap::new());
+
+        let config = self
+            .get_var(CONFIG_VARIABLE_ID)
+            .expect("internal error: config is missing");
+        output.vars.insert(CONFIG_VARIABLE_ID, config);
+
+        output
+    }
+
     pub fn collect_captures(&self, captures: &[VarId]) -> Stack {
         let mut output = Stack::new();
 
@@ -115,6 +154,55 @@ impl Stack {
         None
     }
 
+    /// Wait for the externals of finished pipelines and record the last exit code. Externals
+    /// that don't exit on their own are stopped, rather than waited for forever
+    pub fn record_exit_codes(&mut self) {
+        for pending in std::mem::take(&mut self.pending_exit_codes) {
+            let exit_code = match pending.exit_code.lock() {
+                Ok(receiver) => match receiver.recv_timeout(EXIT_GRACE_PERIOD) {
+                    Err(RecvTimeoutError::Timeout) => {
+                        pending.stop.store(true, Ordering::SeqCst);
+                        receiver.recv().ok()
+                    }
+                    exit_code => exit_code.ok(),
+                },
+                Err(_) => continue,
+            };
+
+            if let Some(exit_code @ Value::Int { .. }) = exit_code {
+                self.add_env_var("LAST_EXIT_CODE".into(), exit_code);
+            }
+        }
+    }
+
+    /// Give the exit code of the last external a block ran to the stack of its caller
+    pub fn pass_exit_code_to(&mut self, caller: &mut Stack) {
+        if let Some(exit_code) = self.get_env_var("LAST_EXIT_CODE") {
+            caller.add_env_var("LAST_EXIT_CODE".into(), exit_code);
+        }
+
+        caller
+            .pending_exit_codes
+            .append(&mut self.pending_exit_codes);
+    }
+
+    /// Give the mutable variables a block captured back to the stack of its caller. Blocks that
+    /// run in place, like the branches of `if`, use this so their assignments are kept
+    pub fn pass_mutable_captures_to(
+        &self,
+        engine_state: &EngineState,
+        caller: &mut Stack,
+        captures: &[VarId],
+    ) {
+        for var_id in captures {
+            if engine_state.get_var(*var_id).mutable {
+                if let Some(value) = self.vars.get(var_id) {
+                    caller.add_var(*var_id, value.clone());
+                }
+            }
+        }
+    }
+
     pub fn get_config(&self) -> Result<Config, ShellError> {
         let config = self.get_var(CONFIG_VARIABLE_ID);
 
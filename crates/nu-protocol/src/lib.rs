@@ -12,6 +12,7 @@ mod span;
 mod syntax_shape;
 mod ty;
 mod value;
+mod variable;
 pub use value::Value;
 
 pub use config::*;
@@ -28,3 +29,4 @@ pub use syntax_shape::*;
 pub use ty::*;
 pub use value::CustomValue;
 pub use value::*;
+pub use variable::*;
@@ -1,7 +1,8 @@
 use std::sync::{atomic::AtomicBool, Arc};
 
 use crate::{
-    ast::PathMember, ByteStream, Config, ShellError, Span, StringStream, Value, ValueStream,
+    ast::PathMember, ByteStream, Config, ExternalStream, ShellError, Span, StringStream, Value,
+    ValueStream,
 };
 
 /// The foundational abstraction for input and output to commands
@@ -39,6 +40,7 @@ pub enum PipelineData {
     ListStream(ValueStream, Option<PipelineMetadata>),
     StringStream(StringStream, Span, Option<PipelineMetadata>),
     ByteStream(ByteStream, Span, Option<PipelineMetadata>),
+    ExternalStream(ExternalStream, Span, Option<PipelineMetadata>),
 }
 
 #[derive(Debug, Clone)]
@@ -60,6 +62,7 @@ impl PipelineData {
         match self {
             PipelineData::ListStream(_, x) => x.clone(),
             PipelineData::ByteStream(_, _, x) => x.clone(),
+            PipelineData::ExternalStream(_, _, x) => x.clone(),
             PipelineData::StringStream(_, _, x) => x.clone(),
             PipelineData::Value(_, x) => x.clone(),
         }
@@ -69,6 +72,7 @@ impl PipelineData {
         match &mut self {
             PipelineData::ListStream(_, x) => *x = metadata,
             PipelineData::ByteStream(_, _, x) => *x = metadata,
+            PipelineData::ExternalStream(_, _, x) => *x = metadata,
             PipelineData::StringStream(_, _, x) => *x = metadata,
             PipelineData::Value(_, x) => *x = metadata,
         }
@@ -113,6 +117,10 @@ impl PipelineData {
                     span, // FIXME?
                 }
             }
+            PipelineData::ExternalStream(s, stream_span, metadata) => {
+                PipelineData::ByteStream(s.into_stdout(stream_span), stream_span, metadata)
+                    .into_value(span)
+            }
         }
     }
 
@@ -134,6 +142,9 @@ impl PipelineData {
             PipelineData::ByteStream(s, ..) => {
                 Ok(String::from_utf8_lossy(&s.into_vec()?).to_string())
             }
+            PipelineData::ExternalStream(s, span, ..) => {
+                Ok(String::from_utf8_lossy(&s.into_stdout(span).into_vec()?).to_string())
+            }
         }
     }
 
@@ -201,11 +212,13 @@ impl PipelineData {
                 Value::Error { error } => Err(error),
                 v => Ok(v.into_pipeline_data()),
             },
-            PipelineData::ByteStream(_, span, ..) => Err(ShellError::UnsupportedInput(
-                "Binary output from this command may need to be decoded using the 'decode' command"
-                    .into(),
-                span,
-            )),
+            PipelineData::ByteStream(_, span, ..) | PipelineData::ExternalStream(_, span, ..) => {
+                Err(ShellError::UnsupportedInput(
+                    "Binary output from this command may need to be decoded using the 'decode' command"
+                        .into(),
+                    span,
+                ))
+            }
         }
     }
 
@@ -241,11 +254,13 @@ impl PipelineData {
                 Err(error) => Err(error),
             },
             PipelineData::Value(v, ..) => Ok(f(v).into_iter().into_pipeline_data(ctrlc)),
-            PipelineData::ByteStream(_, span, ..) => Err(ShellError::UnsupportedInput(
-                "Binary output from this command may need to be decoded using the 'decode' command"
-                    .into(),
-                span,
-            )),
+            PipelineData::ByteStream(_, span, ..) | PipelineData::ExternalStream(_, span, ..) => {
+                Err(ShellError::UnsupportedInput(
+                    "Binary output from this command may need to be decoded using the 'decode' command"
+                        .into(),
+                    span,
+                ))
+            }
         }
     }
 
@@ -281,11 +296,13 @@ impl PipelineData {
                     Ok(Value::Nothing { span: v.span()? }.into_pipeline_data())
                 }
             }
-            PipelineData::ByteStream(_, span, ..) => Err(ShellError::UnsupportedInput(
-                "Binary output from this command may need to be decoded using the 'decode' command"
-                    .into(),
-                span,
-            )),
+            PipelineData::ByteStream(_, span, ..) | PipelineData::ExternalStream(_, span, ..) => {
+                Err(ShellError::UnsupportedInput(
+                    "Binary output from this command may need to be decoded using the 'decode' command"
+                        .into(),
+                    span,
+                ))
+            }
         }
     }
 }
@@ -326,6 +343,9 @@ impl IntoIterator for PipelineData {
                     )),
                 }
             }
+            PipelineData::ExternalStream(stream, span, metadata) => PipelineIterator(
+                PipelineData::ByteStream(stream.into_stdout(span), span, metadata),
+            ),
             x => PipelineIterator(x),
         }
     }
@@ -353,6 +373,8 @@ impl Iterator for PipelineIterator {
                 },
                 Err(err) => Value::Error { error: err },
             }),
+            // Turned into a ByteStream when the iterator is created
+            PipelineData::ExternalStream(..) => None,
         }
     }
 }
@@ -2,7 +2,7 @@ use miette::Diagnostic;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{ast::Operator, Span, Type};
+use crate::{ast::Operator, Span, Type, Value};
 
 /// The fundamental error type for the evaluation engine. These cases represent different kinds of errors
 /// the evaluator might face, along with helpful spans to label. An error renderer will take this error value
@@ -38,6 +38,15 @@ pub enum ShellError {
     #[diagnostic(code(nu::shell::type_mismatch), url(docsrs))]
     TypeMismatch(String, #[label = "{0}"] Span),
 
+    #[error("Type mismatch during argument evaluation.")]
+    #[diagnostic(code(nu::shell::argument_type_mismatch), url(docsrs))]
+    ArgumentTypeMismatch(
+        String,
+        String,
+        #[label("expected {0}, found {1}")] Span,
+        #[label("parameter declared here")] Span,
+    ),
+
     #[error("Unsupported operator: {0}.")]
     #[diagnostic(code(nu::shell::unsupported_operator), url(docsrs))]
     UnsupportedOperator(Operator, #[label = "unsupported operator"] Span),
@@ -110,6 +119,17 @@ pub enum ShellError {
     #[diagnostic(code(nu::shell::cant_convert), url(docsrs))]
     CantConvert(String, String, #[label("can't convert {1} to {0}")] Span),
 
+    #[error("Value doesn't match the variables it is assigned to.")]
+    #[diagnostic(code(nu::shell::destructure_mismatch), url(docsrs))]
+    DestructureMismatch {
+        expected: String,
+        #[label("expected {expected}")]
+        pattern_span: Span,
+        found: String,
+        #[label("found {found}")]
+        value_span: Span,
+    },
+
     #[error("Division by zero.")]
     #[diagnostic(code(nu::shell::division_by_zero), url(docsrs))]
     DivisionByZero(#[label("division by zero")] Span),
@@ -148,6 +168,10 @@ pub enum ShellError {
     #[diagnostic(code(nu::shell::external_command), url(docsrs))]
     ExternalCommand(String, #[label("{0}")] Span),
 
+    #[error("External command failed")]
+    #[diagnostic(code(nu::shell::external_command_failed), url(docsrs))]
+    ExternalCommandFailed(String, i64, #[label("'{0}' exited with code {1}")] Span),
+
     #[error("Unsupported input")]
     #[diagnostic(code(nu::shell::unsupported_input), url(docsrs))]
     UnsupportedInput(String, #[label("{0}")] Span),
@@ -180,6 +204,22 @@ pub enum ShellError {
     #[diagnostic(code(nu::shell::plugin_failed_to_decode), url(docsrs))]
     PluginFailedToDecode(String),
 
+    #[error("Plugin is incompatible: {0}")]
+    #[diagnostic(code(nu::shell::plugin_incompatible), url(docsrs), help("{1}"))]
+    PluginIncompatible(String, String),
+
+    #[error("Plugin call was interrupted")]
+    #[diagnostic(code(nu::shell::plugin_interrupted), url(docsrs))]
+    PluginInterrupted,
+
+    #[error("{0}")]
+    #[diagnostic(
+        code(nu::shell::plugin_failed),
+        url(docsrs),
+        help("The plugin wrote to its stderr:\n{1}")
+    )]
+    PluginFailed(String, String),
+
     #[error("I/O error")]
     #[diagnostic(code(nu::shell::io_error), url(docsrs))]
     IOError(String),
@@ -242,6 +282,14 @@ pub enum ShellError {
     #[diagnostic(code(nu::shell::missing_config_value), url(docsrs))]
     MissingConfigValue(String, #[label = "missing {0}"] Span),
 
+    #[error("Break used outside of loop")]
+    #[diagnostic(code(nu::shell::break_outside_loop), url(docsrs))]
+    Break(#[label = "used outside of loop"] Span),
+
+    #[error("Continue used outside of loop")]
+    #[diagnostic(code(nu::shell::continue_outside_loop), url(docsrs))]
+    Continue(#[label = "used outside of loop"] Span),
+
     #[error("{0}")]
     #[diagnostic()]
     SpannedLabeledError(String, String, #[label("{1}")] Span),
@@ -253,6 +301,54 @@ pub enum ShellError {
     #[error("{0}")]
     #[diagnostic(help("{1}"))]
     LabeledError(String, String),
+
+    // An error with an optional span and help message. The related errors are shown
+    // after it, e.g. to point at other spans that are part of the same error
+    #[error("{0}")]
+    #[diagnostic()]
+    GenericError(
+        String,
+        String,
+        #[label("{1}")] Option<Span>,
+        #[help] Option<String>,
+        #[related] Vec<ShellError>,
+    ),
+}
+
+impl ShellError {
+    /// Turn the error into a record with its message and its first label, so scripts can
+    /// inspect it (eg, in the `catch` block of `try`)
+    pub fn into_value(self, span: Span) -> Value {
+        let label = self.labels().and_then(|mut labels| labels.next());
+
+        let (label_text, label_span) = match label {
+            Some(label) => (
+                match label.label() {
+                    Some(text) => Value::string(text, span),
+                    None => Value::nothing(span),
+                },
+                Value::Record {
+                    cols: vec!["start".into(), "end".into()],
+                    vals: vec![
+                        Value::int(label.offset() as i64, span),
+                        Value::int((label.offset() + label.len()) as i64, span),
+                    ],
+                    span,
+                },
+            ),
+            None => (Value::nothing(span), Value::nothing(span)),
+        };
+
+        Value::Record {
+            cols: vec!["msg".into(), "label".into(), "span".into()],
+            vals: vec![
+                Value::string(self.to_string(), span),
+                label_text,
+                label_span,
+            ],
+            span,
+        }
+    }
 }
 
 impl From<std::io::Error> for ShellError {
@@ -8,6 +8,7 @@ use crate::engine::Stack;
 use crate::BlockId;
 use crate::PipelineData;
 use crate::SyntaxShape;
+use crate::Type;
 use crate::VarId;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -19,6 +20,9 @@ pub struct Flag {
     pub desc: String,
     // For custom commands
     pub var_id: Option<VarId>,
+    // Block that evaluates to the value used when the argument is missing
+    #[serde(default)]
+    pub default_value: Option<BlockId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -28,6 +32,9 @@ pub struct PositionalArg {
     pub shape: SyntaxShape,
     // For custom commands
     pub var_id: Option<VarId>,
+    // Block that evaluates to the value used when the argument is missing
+    #[serde(default)]
+    pub default_value: Option<BlockId>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -51,6 +58,7 @@ pub enum Category {
     Viewers,
     Hash,
     Generators,
+    Bits,
     Custom(String),
 }
 
@@ -75,6 +83,7 @@ impl std::fmt::Display for Category {
             Category::System => "system",
             Category::Viewers => "viewers",
             Category::Hash => "hash",
+            Category::Bits => "bits",
             Category::Generators => "generators",
             Category::Custom(name) => name,
         };
@@ -94,6 +103,19 @@ pub struct Signature {
     pub named: Vec<Flag>,
     pub is_filter: bool,
     pub creates_scope: bool,
+    // Block arguments are loop bodies: they may use `break` and `continue`, and are run in
+    // the caller's stack so they can update mutable variables
+    #[serde(default)]
+    pub is_loop: bool,
+    // Block arguments run as part of the caller's control flow, like the branches of `if`, so
+    // `break` and `continue` inside of them reach the enclosing loop and they may update mutable
+    // variables
+    #[serde(default)]
+    pub runs_in_place: bool,
+    // The pipeline input types the command accepts, each with the type of output it gives.
+    // When empty, any input is accepted and the output type is unknown
+    #[serde(default)]
+    pub input_output_types: Vec<(Type, Type)>,
     // Signature category used to classify commands stored in the list of declarations
     pub category: Category,
 }
@@ -121,6 +143,7 @@ impl Signature {
             desc: "Display this help message".into(),
             required: false,
             var_id: None,
+            default_value: None,
         };
 
         Signature {
@@ -133,6 +156,9 @@ impl Signature {
             named: vec![flag],
             is_filter: false,
             creates_scope: false,
+            is_loop: false,
+            runs_in_place: false,
+            input_output_types: vec![],
             category: Category::Default,
         }
     }
@@ -158,6 +184,7 @@ impl Signature {
             desc: desc.into(),
             shape: shape.into(),
             var_id: None,
+            default_value: None,
         });
 
         self
@@ -175,6 +202,7 @@ impl Signature {
             desc: desc.into(),
             shape: shape.into(),
             var_id: None,
+            default_value: None,
         });
 
         self
@@ -191,6 +219,7 @@ impl Signature {
             desc: desc.into(),
             shape: shape.into(),
             var_id: None,
+            default_value: None,
         });
 
         self
@@ -213,6 +242,7 @@ impl Signature {
             required: false,
             desc: desc.into(),
             var_id: None,
+            default_value: None,
         });
 
         self
@@ -235,6 +265,7 @@ impl Signature {
             required: true,
             desc: desc.into(),
             var_id: None,
+            default_value: None,
         });
 
         self
@@ -256,6 +287,7 @@ impl Signature {
             required: false,
             desc: desc.into(),
             var_id: None,
+            default_value: None,
         });
 
         self
@@ -274,6 +306,24 @@ impl Signature {
         self
     }
 
+    /// Sets that the block arguments of the signature are loop bodies
+    pub fn is_loop(mut self) -> Signature {
+        self.is_loop = true;
+        self
+    }
+
+    /// Sets that the block arguments of the signature run in place, within the caller's loop
+    pub fn runs_in_place(mut self) -> Signature {
+        self.runs_in_place = true;
+        self
+    }
+
+    /// Add a type of pipeline input the command accepts, with the type of output it gives for it
+    pub fn input_output_type(mut self, input_type: Type, output_type: Type) -> Signature {
+        self.input_output_types.push((input_type, output_type));
+        self
+    }
+
     /// Get list of the short-hand flags
     pub fn get_shorts(&self) -> Vec<char> {
         self.named.iter().filter_map(|f| f.short).collect()
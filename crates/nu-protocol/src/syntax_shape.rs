@@ -49,6 +49,9 @@ pub enum SyntaxShape {
     /// A table is allowed, eg `[first second]`
     List(Box<SyntaxShape>),
 
+    /// A record is allowed, eg `{name: "nu", version: 1}`
+    Record,
+
     /// A filesize value is allowed, eg `10kb`
     Filesize,
 
@@ -80,6 +83,9 @@ pub enum SyntaxShape {
     /// A boolean value
     Boolean,
 
+    /// The arms of a match, eg `{ 1 => 'one', [$a, ..] => $a, _ => 'other' }`
+    MatchBlock,
+
     /// A custom shape with custom completion logic
     Custom(Box<SyntaxShape>, String),
 }
@@ -104,12 +110,14 @@ impl SyntaxShape {
                 Type::List(Box::new(contents))
             }
             SyntaxShape::Keyword(_, expr) => expr.to_type(),
+            SyntaxShape::Record => Type::Record(vec![]),
             SyntaxShape::MathExpression => Type::Unknown,
             SyntaxShape::Number => Type::Number,
             SyntaxShape::Operator => Type::Unknown,
             SyntaxShape::Range => Type::Unknown,
             SyntaxShape::RowCondition => Type::Bool,
             SyntaxShape::Boolean => Type::Bool,
+            SyntaxShape::MatchBlock => Type::Unknown,
             SyntaxShape::Signature => Type::Signature,
             SyntaxShape::String => Type::String,
             SyntaxShape::Table => Type::List(Box::new(Type::Unknown)), // FIXME: Tables should have better types
@@ -138,6 +146,7 @@ impl Display for SyntaxShape {
             SyntaxShape::Block(_) => write!(f, "block"),
             SyntaxShape::Table => write!(f, "table"),
             SyntaxShape::List(x) => write!(f, "list<{}>", x),
+            SyntaxShape::Record => write!(f, "record"),
             SyntaxShape::Filesize => write!(f, "filesize"),
             SyntaxShape::Duration => write!(f, "duration"),
             SyntaxShape::Operator => write!(f, "operator"),
@@ -148,6 +157,7 @@ impl Display for SyntaxShape {
             SyntaxShape::Signature => write!(f, "signature"),
             SyntaxShape::Expression => write!(f, "expression"),
             SyntaxShape::Boolean => write!(f, "bool"),
+            SyntaxShape::MatchBlock => write!(f, "match block"),
             SyntaxShape::Custom(x, _) => write!(f, "custom<{}>", x),
         }
     }
@@ -18,12 +18,13 @@ use sys_locale::get_locale;
 pub use unit::*;
 
 use std::collections::HashMap;
-use std::{cmp::Ordering, fmt::Debug};
+use std::{cmp::Ordering, convert::TryFrom, fmt::Debug};
 
 use crate::ast::{CellPath, PathMember};
-use crate::{did_you_mean, span, BlockId, Config, Span, Spanned, Type};
+use crate::{did_you_mean, span, BlockId, Config, Span, Spanned, Type, VarId};
 
 use crate::ast::Operator;
+use crate::engine::EngineState;
 pub use custom_value::CustomValue;
 
 use crate::ShellError;
@@ -74,6 +75,7 @@ pub enum Value {
     },
     Block {
         val: BlockId,
+        captures: HashMap<VarId, Value>,
         span: Span,
     },
     Nothing {
@@ -140,8 +142,13 @@ impl Clone for Value {
                 vals: vals.clone(),
                 span: *span,
             },
-            Value::Block { val, span } => Value::Block {
+            Value::Block {
+                val,
+                captures,
+                span,
+            } => Value::Block {
                 val: *val,
+                captures: captures.clone(),
                 span: *span,
             },
             Value::Nothing { span } => Value::Nothing { span: *span },
@@ -1298,16 +1305,66 @@ impl Value {
         }
     }
 
-    pub fn contains(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+    pub fn regex_match(
+        &self,
+        engine_state: &EngineState,
+        op: Span,
+        rhs: &Value,
+        invert: bool,
+    ) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (
+                Value::String { val: lhs, .. },
+                Value::String {
+                    val: rhs,
+                    span: rhs_span,
+                },
+            ) => {
+                let regex = engine_state.get_regex(rhs).map_err(|err| {
+                    // Syntax errors span several lines, with the reason on the last one
+                    let err = err.to_string();
+                    let reason = err.lines().last().unwrap_or_default();
+                    ShellError::UnsupportedInput(
+                        format!("invalid regex: {}", reason.trim_start_matches("error: ")),
+                        *rhs_span,
+                    )
+                })?;
+
+                Ok(Value::Bool {
+                    val: regex.is_match(lhs) != invert,
+                    span,
+                })
+            }
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                let operator = if invert {
+                    Operator::NotRegexMatch
+                } else {
+                    Operator::RegexMatch
+                };
+                lhs.operation(*span, operator, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    pub fn starts_with(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
         let span = span(&[self.span()?, rhs.span()?]);
 
         match (self, rhs) {
             (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::Bool {
-                val: lhs.contains(rhs),
+                val: lhs.starts_with(rhs),
                 span,
             }),
             (Value::CustomValue { val: lhs, span }, rhs) => {
-                lhs.operation(*span, Operator::Contains, op, rhs)
+                lhs.operation(*span, Operator::StartsWith, op, rhs)
             }
             _ => Err(ShellError::OperatorMismatch {
                 op_span: op,
@@ -1319,16 +1376,16 @@ impl Value {
         }
     }
 
-    pub fn not_contains(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+    pub fn ends_with(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
         let span = span(&[self.span()?, rhs.span()?]);
 
         match (self, rhs) {
             (Value::String { val: lhs, .. }, Value::String { val: rhs, .. }) => Ok(Value::Bool {
-                val: !lhs.contains(rhs),
+                val: lhs.ends_with(rhs),
                 span,
             }),
             (Value::CustomValue { val: lhs, span }, rhs) => {
-                lhs.operation(*span, Operator::NotContains, op, rhs)
+                lhs.operation(*span, Operator::EndsWith, op, rhs)
             }
             _ => Err(ShellError::OperatorMismatch {
                 op_span: op,
@@ -1340,6 +1397,8 @@ impl Value {
         }
     }
 
+    /// The remainder of a floor division, so `$a == ($a // $b) * $b + ($a mod $b)`. The result
+    /// takes the sign of the divisor, eg `-7 mod 3` is `2`
     pub fn modulo(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
         let span = span(&[self.span()?, rhs.span()?]);
 
@@ -1347,7 +1406,7 @@ impl Value {
             (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
                 if *rhs != 0 {
                     Ok(Value::Int {
-                        val: lhs % rhs,
+                        val: floor_mod_int(*lhs, *rhs),
                         span,
                     })
                 } else {
@@ -1357,7 +1416,7 @@ impl Value {
             (Value::Int { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
                 if *rhs != 0.0 {
                     Ok(Value::Float {
-                        val: *lhs as f64 % *rhs,
+                        val: floor_mod_float(*lhs as f64, *rhs),
                         span,
                     })
                 } else {
@@ -1367,7 +1426,7 @@ impl Value {
             (Value::Float { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
                 if *rhs != 0 {
                     Ok(Value::Float {
-                        val: *lhs % *rhs as f64,
+                        val: floor_mod_float(*lhs, *rhs as f64),
                         span,
                     })
                 } else {
@@ -1377,7 +1436,27 @@ impl Value {
             (Value::Float { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
                 if *rhs != 0.0 {
                     Ok(Value::Float {
-                        val: lhs % rhs,
+                        val: floor_mod_float(*lhs, *rhs),
+                        span,
+                    })
+                } else {
+                    Err(ShellError::DivisionByZero(op))
+                }
+            }
+            (Value::Filesize { val: lhs, .. }, Value::Filesize { val: rhs, .. }) => {
+                if *rhs != 0 {
+                    Ok(Value::Filesize {
+                        val: floor_mod_int(*lhs, *rhs),
+                        span,
+                    })
+                } else {
+                    Err(ShellError::DivisionByZero(op))
+                }
+            }
+            (Value::Duration { val: lhs, .. }, Value::Duration { val: rhs, .. }) => {
+                if *rhs != 0 {
+                    Ok(Value::Duration {
+                        val: floor_mod_int(*lhs, *rhs),
                         span,
                     })
                 } else {
@@ -1398,6 +1477,82 @@ impl Value {
         }
     }
 
+    /// Division rounded towards negative infinity, the counterpart of `mod`
+    pub fn floor_div(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+        let overflow = || ShellError::OperatorOverflow("floor division overflowed".into(), span);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. })
+            | (Value::Filesize { val: lhs, .. }, Value::Filesize { val: rhs, .. })
+            | (Value::Duration { val: lhs, .. }, Value::Duration { val: rhs, .. }) => {
+                if *rhs != 0 {
+                    let val = floor_div_int(*lhs, *rhs).ok_or_else(overflow)?;
+                    Ok(Value::Int { val, span })
+                } else {
+                    Err(ShellError::DivisionByZero(op))
+                }
+            }
+            (Value::Filesize { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
+                if *rhs != 0 {
+                    let val = floor_div_int(*lhs, *rhs).ok_or_else(overflow)?;
+                    Ok(Value::Filesize { val, span })
+                } else {
+                    Err(ShellError::DivisionByZero(op))
+                }
+            }
+            (Value::Duration { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
+                if *rhs != 0 {
+                    let val = floor_div_int(*lhs, *rhs).ok_or_else(overflow)?;
+                    Ok(Value::Duration { val, span })
+                } else {
+                    Err(ShellError::DivisionByZero(op))
+                }
+            }
+            (Value::Int { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
+                if *rhs != 0.0 {
+                    Ok(Value::Float {
+                        val: (*lhs as f64 / *rhs).floor(),
+                        span,
+                    })
+                } else {
+                    Err(ShellError::DivisionByZero(op))
+                }
+            }
+            (Value::Float { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
+                if *rhs != 0 {
+                    Ok(Value::Float {
+                        val: (*lhs / *rhs as f64).floor(),
+                        span,
+                    })
+                } else {
+                    Err(ShellError::DivisionByZero(op))
+                }
+            }
+            (Value::Float { val: lhs, .. }, Value::Float { val: rhs, .. }) => {
+                if *rhs != 0.0 {
+                    Ok(Value::Float {
+                        val: (lhs / rhs).floor(),
+                        span,
+                    })
+                } else {
+                    Err(ShellError::DivisionByZero(op))
+                }
+            }
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::FloorDivision, op, rhs)
+            }
+
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
     pub fn and(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
         let span = span(&[self.span()?, rhs.span()?]);
 
@@ -1479,6 +1634,130 @@ impl Value {
             }),
         }
     }
+
+    pub fn bit_or(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => Ok(Value::Int {
+                val: *lhs | rhs,
+                span,
+            }),
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::BitOr, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    pub fn bit_xor(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => Ok(Value::Int {
+                val: *lhs ^ rhs,
+                span,
+            }),
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::BitXor, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    pub fn bit_and(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => Ok(Value::Int {
+                val: *lhs & rhs,
+                span,
+            }),
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::BitAnd, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    pub fn bit_shl(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
+                // Shifting by a negative amount, past the width of an int, or shifting
+                // set bits out of the int overflows
+                match u32::try_from(*rhs)
+                    .ok()
+                    .and_then(|rhs| lhs.checked_shl(rhs).filter(|val| val >> rhs == *lhs))
+                {
+                    Some(val) => Ok(Value::Int { val, span }),
+                    None => Err(ShellError::OperatorOverflow(
+                        "shift left operation overflowed".into(),
+                        span,
+                    )),
+                }
+            }
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::ShiftLeft, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
+
+    pub fn bit_shr(&self, op: Span, rhs: &Value) -> Result<Value, ShellError> {
+        let span = span(&[self.span()?, rhs.span()?]);
+
+        match (self, rhs) {
+            (Value::Int { val: lhs, .. }, Value::Int { val: rhs, .. }) => {
+                // Shifting by a negative amount or past the width of an int overflows
+                match u32::try_from(*rhs)
+                    .ok()
+                    .and_then(|rhs| lhs.checked_shr(rhs))
+                {
+                    Some(val) => Ok(Value::Int { val, span }),
+                    None => Err(ShellError::OperatorOverflow(
+                        "shift right operation overflowed".into(),
+                        span,
+                    )),
+                }
+            }
+            (Value::CustomValue { val: lhs, span }, rhs) => {
+                lhs.operation(*span, Operator::ShiftRight, op, rhs)
+            }
+            _ => Err(ShellError::OperatorMismatch {
+                op_span: op,
+                lhs_ty: self.get_type(),
+                lhs_span: self.span()?,
+                rhs_ty: rhs.get_type(),
+                rhs_span: rhs.span()?,
+            }),
+        }
+    }
 }
 
 /// Create a Value::Record from a spanned hashmap
@@ -1515,6 +1794,35 @@ impl From<Spanned<IndexMap<String, Value>>> for Value {
     }
 }
 
+// Integer division rounded towards negative infinity, None on overflow
+fn floor_div_int(lhs: i64, rhs: i64) -> Option<i64> {
+    let quotient = lhs.checked_div(rhs)?;
+    if lhs % rhs != 0 && (lhs < 0) != (rhs < 0) {
+        Some(quotient - 1)
+    } else {
+        Some(quotient)
+    }
+}
+
+// The remainder of `floor_div_int`, which has the sign of the divisor
+fn floor_mod_int(lhs: i64, rhs: i64) -> i64 {
+    let remainder = lhs.wrapping_rem(rhs);
+    if remainder != 0 && (remainder < 0) != (rhs < 0) {
+        remainder + rhs
+    } else {
+        remainder
+    }
+}
+
+fn floor_mod_float(lhs: f64, rhs: f64) -> f64 {
+    let remainder = lhs % rhs;
+    if remainder != 0.0 && (remainder < 0.0) != (rhs < 0.0) {
+        remainder + rhs
+    } else {
+        remainder
+    }
+}
+
 /// Format a duration in nanoseconds into a string
 pub fn format_duration(duration: i64) -> String {
     let (sign, duration) = if duration >= 0 {
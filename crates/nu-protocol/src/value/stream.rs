@@ -150,3 +150,49 @@ impl Iterator for ValueStream {
         }
     }
 }
+
+/// The output of an external command. Stdout and stderr are only present when they are piped
+/// rather than sent to the terminal or a file. The exit code is produced once the external ends.
+pub struct ExternalStream {
+    pub name: String,
+    pub stdout: Option<ByteStream>,
+    pub stderr: Option<ByteStream>,
+    pub exit_code: Option<ValueStream>,
+}
+
+impl ExternalStream {
+    /// Read the output as a plain byte stream of stdout, which ends with an error if the external
+    /// exited with a non-zero code
+    pub fn into_stdout(self, span: Span) -> ByteStream {
+        let ExternalStream {
+            name,
+            stdout,
+            exit_code,
+            ..
+        } = self;
+
+        let ctrlc = stdout.as_ref().and_then(|stdout| stdout.ctrlc.clone());
+        let failure =
+            exit_code
+                .into_iter()
+                .flatten()
+                .filter_map(move |exit_code| match exit_code {
+                    Value::Int { val, .. } if val != 0 => Some(Err(
+                        ShellError::ExternalCommandFailed(name.clone(), val, span),
+                    )),
+                    Value::Error { error } => Some(Err(error)),
+                    _ => None,
+                });
+
+        ByteStream {
+            stream: Box::new(stdout.into_iter().flatten().chain(failure)),
+            ctrlc,
+        }
+    }
+}
+
+impl Debug for ExternalStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExternalStream").finish()
+    }
+}
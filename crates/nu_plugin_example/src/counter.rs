@@ -0,0 +1,34 @@
+use nu_protocol::{CustomValue, ShellError, Span, Value};
+use serde::{Deserialize, Serialize};
+
+// Custom value returned by the plugin. Nushell keeps it serialized and sends it back
+// to the plugin when it is piped into one of the plugin's commands
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Counter {
+    pub count: i64,
+}
+
+#[typetag::serde]
+impl CustomValue for Counter {
+    fn clone_value(&self, span: Span) -> Value {
+        Value::CustomValue {
+            val: Box::new(self.clone()),
+            span,
+        }
+    }
+
+    fn value_string(&self) -> String {
+        "counter".into()
+    }
+
+    fn to_base_value(&self, span: Span) -> Result<Value, ShellError> {
+        Ok(Value::Int {
+            val: self.count,
+            span,
+        })
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
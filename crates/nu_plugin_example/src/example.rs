@@ -1,5 +1,6 @@
-use nu_plugin::{EvaluatedCall, LabeledError};
-use nu_protocol::Value;
+use crate::Counter;
+use nu_plugin::{EngineInterface, EvaluatedCall, LabeledError, ProtocolInfo};
+use nu_protocol::{PipelineData, Value, ValueStream};
 pub struct Example;
 
 impl Example {
@@ -10,8 +11,9 @@ impl Example {
         input: &Value,
     ) -> Result<(), LabeledError> {
         // Note. When debugging your plugin, you may want to print something to the console
-        // Use the eprintln macro to print your messages. Trying to print to stdout will
-        // cause a decoding error for your message
+        // Use the eprintln macro to print your messages. Nushell keeps the last lines
+        // written to stderr and shows them when a call to the plugin fails. Trying to
+        // print to stdout will cause a decoding error for your message
         eprintln!("Calling test {} signature", index);
         eprintln!("value received {:?}", input);
 
@@ -85,10 +87,141 @@ impl Example {
     pub fn test3(&self, call: &EvaluatedCall, input: &Value) -> Result<Value, LabeledError> {
         self.print_values(3, call, input)?;
 
-        Err(LabeledError {
-            label: "ERROR from plugin".into(),
-            msg: "error message pointing to call head span".into(),
-            span: Some(call.head),
+        // Other spans of the call can be labelled too and a help message is shown
+        // below the labels
+        let mut error = LabeledError::new(
+            "ERROR from plugin",
+            "error message pointing to call head span",
+            Some(call.head),
+        );
+        if let Some(Ok(span)) = call.positional.first().map(|value| value.span()) {
+            error = error.with_label("the first argument", span);
+        }
+
+        Err(error.with_help("this error is returned on purpose by the example plugin"))
+    }
+
+    pub fn test4(
+        &self,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        eprintln!("Calling test 4 signature");
+
+        // The input is read from nushell while the output is consumed. Each value
+        // is sent back to nushell as soon as it is produced
+        let head = call.head;
+        let values = input
+            .into_iter()
+            .enumerate()
+            .map(move |(index, value)| Value::Record {
+                cols: vec!["index".into(), "value".into()],
+                vals: vec![
+                    Value::Int {
+                        val: index as i64,
+                        span: head,
+                    },
+                    value,
+                ],
+                span: head,
+            });
+
+        Ok(PipelineData::ListStream(
+            ValueStream::from_stream(values, None),
+            None,
+        ))
+    }
+
+    pub fn test5(&self, call: &EvaluatedCall, input: &Value) -> Result<Value, LabeledError> {
+        eprintln!("Calling test 5 signature");
+
+        // A counter created by this plugin comes back with its own type. Any other
+        // input starts a new counter
+        let count = match input {
+            Value::CustomValue { val, .. } => val
+                .as_any()
+                .downcast_ref::<Counter>()
+                .map(|counter| counter.count + 1)
+                .unwrap_or(0),
+            _ => 0,
+        };
+
+        Ok(Value::CustomValue {
+            val: Box::new(Counter { count }),
+            span: call.head,
         })
     }
+
+    pub fn test6(
+        &self,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        eprintln!("Calling test 6 signature");
+
+        // The block is evaluated by nushell. The engine interface is moved into the
+        // output stream because the block runs while the output is consumed
+        let block: Value = call.req(0)?;
+        let engine = engine.clone();
+        let values = input.into_iter().map(move |value| {
+            engine
+                .eval_block(&block, vec![value.clone()], value)
+                .unwrap_or_else(|error| Value::Error { error })
+        });
+
+        Ok(PipelineData::ListStream(
+            ValueStream::from_stream(values, None),
+            None,
+        ))
+    }
+
+    pub fn test7(
+        &self,
+        engine: &EngineInterface,
+        call: &EvaluatedCall,
+    ) -> Result<PipelineData, LabeledError> {
+        eprintln!("Calling test 7 signature");
+
+        // The handshake is done by serve_plugin before the first call. Nushell would
+        // have stopped the plugin if both sides didn't speak the same protocol version
+        let protocol_record = |info: &ProtocolInfo| Value::Record {
+            cols: vec!["protocol".into(), "version".into(), "features".into()],
+            vals: vec![
+                Value::String {
+                    val: info.protocol.clone(),
+                    span: call.head,
+                },
+                Value::Int {
+                    val: info.version as i64,
+                    span: call.head,
+                },
+                Value::List {
+                    vals: info
+                        .features
+                        .iter()
+                        .map(|feature| Value::String {
+                            val: feature.clone(),
+                            span: call.head,
+                        })
+                        .collect(),
+                    span: call.head,
+                },
+            ],
+            span: call.head,
+        };
+
+        let nushell = engine
+            .protocol_info()
+            .map(protocol_record)
+            .unwrap_or(Value::Nothing { span: call.head });
+
+        let value = Value::Record {
+            cols: vec!["nushell".into(), "plugin".into()],
+            vals: vec![nushell, protocol_record(&ProtocolInfo::default())],
+            span: call.head,
+        };
+
+        Ok(PipelineData::Value(value, None))
+    }
 }
@@ -1,4 +1,6 @@
+mod counter;
 mod example;
 mod nu;
 
+pub use counter::Counter;
 pub use example::Example;
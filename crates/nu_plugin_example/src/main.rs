@@ -4,14 +4,18 @@ use nu_plugin_example::Example;
 fn main() {
     // When defining your plugin, you can select the Serializer that could be
     // used to encode and decode the messages. The available options are
-    // CapnpSerializer and JsonSerializer. Both are defined in the serializer
-    // folder in nu-plugin.
+    // CapnpSerializer, JsonSerializer and MsgPackSerializer. They are defined in the
+    // serializer folder in nu-plugin.
     serve_plugin(&mut Example {}, CapnpSerializer {})
 
     // Note
     // When creating plugins in other languages one needs to consider how a plugin
     // is added and used in nushell.
     // The steps are:
+    // - Nushell starts the plugin with an encoded PluginCall::Hello that has the version
+    //      of the plugin protocol it speaks and the optional features it supports. The
+    //      plugin answers with a PluginResponse::Hello with its own. Nushell stops
+    //      plugins that speak another version of the protocol
     // - The plugin is register. In this stage nushell calls the binary file of
     //      the plugin sending information using the encoded PluginCall::Signature object.
     //      Use this encoded data in your plugin to design the logic that will return
@@ -25,6 +29,17 @@ fn main() {
     //      Use this data to design your plugin login and to create the value that
     //      will be sent to nushell
     //      Nushell expects an encoded PluginResponse::Value from the plugin
+    // - Input and output streams are sent in chunks. A stream answer starts with
+    //      PluginResponse::StreamStart, continues with a PluginResponse::StreamData for
+    //      every chunk and finishes with PluginResponse::StreamEnd. Input streams are
+    //      sent the same way using PluginCall::StreamData and PluginCall::StreamEnd
+    // - Custom values returned by the plugin are sent as a name and the serialized
+    //      value. Nushell asks the plugin for their base value with
+    //      PluginCall::CollapseCustomValue and the plugin answers with PluginResponse::Value
+    // - While serving a call the plugin can ask nushell to evaluate a block or to send
+    //      its environment, config or current directory. The plugin sends a
+    //      PluginResponse::EngineCall with the id of the call and nushell answers with a
+    //      PluginCall::EngineCallResponse before the plugin continues
     // - If an error needs to be sent back to nushell, one can encode PluginResponse::Error.
     //      This is a labeled error that nushell can format for pretty printing
 }
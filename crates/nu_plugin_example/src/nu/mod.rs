@@ -1,6 +1,6 @@
 use crate::Example;
-use nu_plugin::{EvaluatedCall, LabeledError, Plugin};
-use nu_protocol::{Category, Signature, SyntaxShape, Value};
+use nu_plugin::{EngineInterface, EvaluatedCall, LabeledError, Plugin};
+use nu_protocol::{Category, PipelineData, Signature, SyntaxShape, Value};
 
 impl Plugin for Example {
     fn signature(&self) -> Vec<Signature> {
@@ -35,6 +35,23 @@ impl Plugin for Example {
                 .named("named", SyntaxShape::String, "named string", Some('n'))
                 .rest("rest", SyntaxShape::String, "rest value string")
                 .category(Category::Experimental),
+            Signature::build("nu-example-4")
+                .desc("Signature test 4 for plugin. Streams the input back with its index")
+                .category(Category::Experimental),
+            Signature::build("nu-example-5")
+                .desc("Signature test 5 for plugin. Returns a custom value counting its uses")
+                .category(Category::Experimental),
+            Signature::build("nu-example-6")
+                .desc("Signature test 6 for plugin. Runs the block for every value of the input")
+                .required(
+                    "block",
+                    SyntaxShape::Block(Some(vec![SyntaxShape::Any])),
+                    "the block to run",
+                )
+                .category(Category::Experimental),
+            Signature::build("nu-example-7")
+                .desc("Signature test 7 for plugin. Returns the protocol of nushell and the plugin")
+                .category(Category::Experimental),
         ]
     }
 
@@ -49,11 +66,34 @@ impl Plugin for Example {
             "nu-example-1" => self.test1(call, input),
             "nu-example-2" => self.test2(call, input),
             "nu-example-3" => self.test3(call, input),
+            "nu-example-5" => self.test5(call, input),
             _ => Err(LabeledError {
                 label: "Plugin call with wrong name signature".into(),
                 msg: "the signature used to call the plugin does not match any name in the plugin signature vector".into(),
                 span: Some(call.head),
+                labels: vec![],
+                help: None,
             }),
         }
     }
+
+    fn run_stream(
+        &mut self,
+        engine: &EngineInterface,
+        name: &str,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        // Signatures that don't work with streams collect their input and use `run`
+        match name {
+            "nu-example-4" => self.test4(call, input),
+            "nu-example-6" => self.test6(engine, call, input),
+            "nu-example-7" => self.test7(engine, call),
+            _ => {
+                let input = input.into_value(call.head);
+                self.run(name, call, &input)
+                    .map(|value| PipelineData::Value(value, None))
+            }
+        }
+    }
 }
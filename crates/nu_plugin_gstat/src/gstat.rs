@@ -3,7 +3,7 @@ use nu_plugin::LabeledError;
 use nu_protocol::{Span, Spanned, Value};
 use std::fmt::Write;
 use std::ops::BitAnd;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 // git status
 // https://github.com/git/git/blob/9875c515535860450bafd1a177f64f0a478900fa/Documentation/git-status.txt
@@ -27,6 +27,7 @@ impl GStat {
         &self,
         value: &Value,
         path: Option<Spanned<String>>,
+        cwd: Option<&str>,
         span: &Span,
     ) -> Result<Value, LabeledError> {
         // use std::any::Any;
@@ -64,8 +65,13 @@ impl GStat {
             a_path.item = piped_value;
         }
 
+        let full_path = match cwd {
+            Some(cwd) => Path::new(cwd).join(&a_path.item),
+            None => PathBuf::from(&a_path.item),
+        };
+
         // This path has to exist
-        if !std::path::Path::new(&a_path.item).exists() {
+        if !full_path.exists() {
             return Err(LabeledError {
                 label: "error with path".to_string(),
                 msg: format!("path does not exist [{}]", &a_path.item),
@@ -74,10 +80,12 @@ impl GStat {
                 } else {
                     Some(a_path.span)
                 },
+                labels: vec![],
+                help: None,
             });
         }
 
-        let metadata = match std::fs::metadata(&a_path.item) {
+        let metadata = match std::fs::metadata(&full_path) {
             Ok(md) => md,
             Err(e) => {
                 return Err(LabeledError {
@@ -91,6 +99,8 @@ impl GStat {
                     } else {
                         Some(a_path.span)
                     },
+                    labels: vec![],
+                    help: None,
                 });
             }
         };
@@ -105,10 +115,12 @@ impl GStat {
                 } else {
                     Some(a_path.span)
                 },
+                labels: vec![],
+                help: None,
             });
         }
 
-        let repo_path = match PathBuf::from(&a_path.item).canonicalize() {
+        let repo_path = match full_path.canonicalize() {
             Ok(p) => p,
             Err(e) => {
                 return Err(LabeledError {
@@ -119,6 +131,8 @@ impl GStat {
                     } else {
                         Some(a_path.span)
                     },
+                    labels: vec![],
+                    help: None,
                 });
             }
         };
@@ -1,6 +1,6 @@
 use crate::GStat;
-use nu_plugin::{EvaluatedCall, LabeledError, Plugin};
-use nu_protocol::{Category, Signature, Spanned, SyntaxShape, Value};
+use nu_plugin::{EngineInterface, EvaluatedCall, LabeledError, Plugin};
+use nu_protocol::{Category, PipelineData, Signature, Spanned, SyntaxShape, Value};
 
 impl Plugin for GStat {
     fn signature(&self) -> Vec<Signature> {
@@ -22,6 +22,29 @@ impl Plugin for GStat {
 
         let repo_path: Option<Spanned<String>> = call.opt(0)?;
         // eprintln!("input value: {:#?}", &input);
-        self.gstat(input, repo_path, &call.head)
+        self.gstat(input, repo_path, None, &call.head)
+    }
+
+    fn run_stream(
+        &mut self,
+        engine: &EngineInterface,
+        name: &str,
+        call: &EvaluatedCall,
+        input: PipelineData,
+    ) -> Result<PipelineData, LabeledError> {
+        if name != "gstat" {
+            return Ok(PipelineData::Value(
+                Value::Nothing { span: call.head },
+                None,
+            ));
+        }
+
+        // Relative paths are resolved against the `PWD` of the caller, not the plugin's own
+        let cwd = engine.get_current_dir()?;
+        let repo_path: Option<Spanned<String>> = call.opt(0)?;
+        let input = input.into_value(call.head);
+
+        self.gstat(&input, repo_path, Some(&cwd), &call.head)
+            .map(|value| PipelineData::Value(value, None))
     }
 }
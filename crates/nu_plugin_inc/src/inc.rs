@@ -98,12 +98,16 @@ impl Inc {
                         e.to_string()
                     ),
                     span: Some(head),
+                    labels: vec![],
+                    help: None,
                 })?;
 
                 Err(LabeledError {
                     label: "Incorrect value".into(),
                     msg,
                     span: Some(head),
+                    labels: vec![],
+                    help: None,
                 })
             }
         }
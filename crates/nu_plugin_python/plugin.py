@@ -5,6 +5,10 @@
 # would be that you need to compile the schema file in order have the objects
 # that decode and encode information that is read and written to stdin and stdour
 #
+# MessagePack can be used instead of JSON with `-e msgpack`. The messages have the
+# same shape as the JSON ones and can be read and written with the msgpack package,
+# without compiling a schema
+#
 # To register the plugin use:
 # 	register <path-to-py-file> -e json
 #
@@ -22,11 +26,21 @@
 #   by using libraries like pydantic and marshmallow
 #
 # This plugin uses python3
-# Note: To debug plugins write to stderr using sys.stderr.write
+# Note: To debug plugins write to stderr using sys.stderr.write. Nushell keeps the
+#   last lines written to stderr and shows them when a call to the plugin fails
 import sys
 import json
 
 
+def hello():
+    """
+    Before any other call nushell sends a Hello with the version of the plugin
+    protocol it speaks and the optional features it supports. The plugin answers
+    with its own. Nushell stops the plugin if both versions are not the same
+    """
+    return {"Hello": {"protocol": "nu-plugin", "version": 1, "features": []}}
+
+
 def signatures():
     """
     Multiple signatures can be sent to nushell. Each signature will be registered
@@ -109,7 +123,9 @@ def process_call(plugin_call):
     It should contain:
             - The name of the call
             - The call data which includes the positional and named values
-            - The input from the pippeline
+            - The input from the pippeline. It is either {"Value": value} or
+              {"Stream": kind} when the input is a stream. The chunks of a stream
+              are sent after the call and this example ignores them
 
     Use this information to implement your plugin logic
     """
@@ -389,28 +405,60 @@ def process_call(plugin_call):
     }
 
 
+def write_response(call_id, response):
+    """
+    Every message is a JSON array with the id of the call and the message itself,
+    written in a single line. Nushell waits for the response with the same id as
+    the call it sent
+    """
+    sys.stdout.write(json.dumps([call_id, response]))
+    sys.stdout.write("\n")
+    sys.stdout.flush()
+
+
 def plugin():
-    call_str = ",".join(sys.stdin.readlines())
-    plugin_call = json.loads(call_str)
+    # The plugin process is kept alive by nushell and receives one call per line
+    # until it gets a Goodbye call or its stdin is closed.
+    # While it serves a call the plugin can also call nushell, e.g. by writing
+    # {"EngineCall": "GetCurrentDir"} with the id of the call. Nushell answers with an
+    # {"EngineCallResponse": {"Value": ...}} line carrying the same id
+    for line in sys.stdin:
+        if not line.strip():
+            continue
 
-    if plugin_call == "Signature":
-        signature = json.dumps(signatures())
-        sys.stdout.write(signature)
+        call_id, plugin_call = json.loads(line)
 
-    elif "CallInfo" in plugin_call:
-        response = process_call(plugin_call)
-        sys.stdout.write(json.dumps(response))
+        if plugin_call == "Goodbye":
+            break
 
-    else:
-        # Use this error format if you want to return an error back to nushell
-        error = {
-            "Error": {
-                "label": "ERROR from plugin",
-                "msg": "error message pointing to call head span",
-                "span": {"start": 0, "end": 1},
+        elif plugin_call == "StreamEnd" or "StreamData" in plugin_call:
+            # Chunks of an input stream. Nushell doesn't wait for an answer to them
+            continue
+
+        elif "Hello" in plugin_call:
+            write_response(call_id, hello())
+
+        elif plugin_call == "Signature":
+            write_response(call_id, signatures())
+
+        elif "CallInfo" in plugin_call:
+            write_response(call_id, process_call(plugin_call))
+
+        else:
+            # Use this error format if you want to return an error back to nushell
+            # The labels and the help are optional. Each label points to another span
+            error = {
+                "Error": {
+                    "label": "ERROR from plugin",
+                    "msg": "error message pointing to call head span",
+                    "span": {"start": 0, "end": 1},
+                    "labels": [
+                        {"text": "another label", "span": {"start": 0, "end": 1}}
+                    ],
+                    "help": "help message shown below the error",
+                }
             }
-        }
-        sys.stdout.write(json.dumps(error))
+            write_response(call_id, error)
 
 
 if __name__ == "__main__":
@@ -9,10 +9,10 @@ use miette::{IntoDiagnostic, Result};
 use nu_cli::{CliError, NuCompleter, NuHighlighter, NuValidator, NushellPrompt};
 use nu_color_config::get_color_config;
 use nu_command::create_default_context;
-use nu_engine::{convert_env_values, eval_block};
+use nu_engine::{convert_env_values, current_dir, eval_block};
 use nu_parser::{lex, parse, trim_quotes, Token, TokenContents};
 use nu_protocol::{
-    ast::Call,
+    ast::{Block, Call, Expr, Statement},
     engine::{EngineState, Stack, StateWorkingSet},
     Config, PipelineData, ShellError, Span, Value, CONFIG_VARIABLE_ID,
 };
@@ -21,7 +21,6 @@ use reedline::{
 };
 use std::{
     io::Write,
-    path::Path,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
@@ -132,6 +131,7 @@ fn main() -> Result<()> {
 
         // First, set up env vars as strings only
         gather_parent_env_vars(&mut engine_state, &mut stack);
+        reset_last_exit_code(&mut stack);
 
         // Set up our initial config to start from
         stack.vars.insert(
@@ -182,6 +182,11 @@ fn main() -> Result<()> {
                 let args: Vec<String> = std::env::args().skip(2).collect();
 
                 if args.is_empty() && engine_state.find_decl(b"main").is_none() {
+                    #[cfg(feature = "plugin")]
+                    nu_plugin::shutdown_plugins();
+
+                    exit_on_failed_external(&engine_state, &mut stack, &block);
+
                     return Ok(());
                 }
 
@@ -220,6 +225,11 @@ fn main() -> Result<()> {
                             }
                             println!("{}", item.into_string("\n", &config));
                         }
+
+                        #[cfg(feature = "plugin")]
+                        nu_plugin::shutdown_plugins();
+
+                        exit_on_failed_external(&engine_state, &mut stack, &block);
                     }
                     Err(err) => {
                         let working_set = StateWorkingSet::new(&engine_state);
@@ -251,6 +261,7 @@ fn main() -> Result<()> {
 
         // First, set up env vars as strings only
         gather_parent_env_vars(&mut engine_state, &mut stack);
+        reset_last_exit_code(&mut stack);
 
         // Set up our initial config to start from
         stack.vars.insert(
@@ -312,8 +323,9 @@ fn main() -> Result<()> {
 
         #[cfg(feature = "plugin")]
         {
-            // Reading signatures from signature file
-            // The plugin.nu file stores the parsed signature collected from each registered plugin
+            // Registering the plugins from the plugin file
+            // The plugin.nu file registers each plugin. Their signatures are read from the
+            // signature cache next to it, only plugins that changed are started
             if let Some(mut plugin_path) = nu_path::config_dir() {
                 // Path to store plugins signatures
                 plugin_path.push("nushell");
@@ -342,6 +354,9 @@ fn main() -> Result<()> {
             //Reset the ctrl-c handler
             ctrlc.store(false, Ordering::SeqCst);
 
+            // Complete paths from the `PWD` the last entry left behind
+            engine_state.cwd = current_dir(&stack).ok();
+
             let line_editor = Reedline::create()
                 .into_diagnostic()?
                 .with_completion_action_handler(Box::new(FuzzyCompletion {
@@ -415,7 +430,8 @@ fn main() -> Result<()> {
                     let orig = s.clone();
                     s = path.to_string_lossy().to_string();
 
-                    let path = Path::new(&s);
+                    let cwd = current_dir(&stack).unwrap_or_default();
+                    let path = nu_path::expand_path_with(&s, &cwd);
                     if (orig.starts_with('.')
                         || orig.starts_with('~')
                         || orig.starts_with('/')
@@ -423,14 +439,10 @@ fn main() -> Result<()> {
                         && path.is_dir()
                     {
                         // We have an auto-cd
-                        let _ = std::env::set_current_dir(&path);
-
-                        //FIXME: this only changes the current scope, but instead this environment variable
-                        //should probably be a block that loads the information from the state in the overlay
                         stack.add_env_var(
                             "PWD".into(),
                             Value::String {
-                                val: s.clone(),
+                                val: path.to_string_lossy().to_string(),
                                 span: Span { start: 0, end: 0 },
                             },
                         );
@@ -465,10 +477,66 @@ fn main() -> Result<()> {
             }
         }
 
+        // Plugins are kept running between calls, let them know we are leaving
+        #[cfg(feature = "plugin")]
+        nu_plugin::shutdown_plugins();
+
         Ok(())
     }
 }
 
+fn reset_last_exit_code(stack: &mut Stack) {
+    stack.add_env_var(
+        "LAST_EXIT_CODE".to_string(),
+        Value::Int {
+            val: 0,
+            span: Span::new(0, 0),
+        },
+    );
+}
+
+// A script whose last pipeline ends with a failed external exits with the same code, so callers
+// can see the failure. An external that failed earlier doesn't decide it
+fn exit_on_failed_external(engine_state: &EngineState, stack: &mut Stack, block: &Block) {
+    stack.record_exit_codes();
+
+    if !ends_with_external(engine_state, block) {
+        return;
+    }
+
+    if let Some(Value::Int { val, .. }) = stack.get_env_var("LAST_EXIT_CODE") {
+        if val != 0 {
+            std::process::exit(val as i32);
+        }
+    }
+}
+
+// Whether the last pipeline of the block ends with an external, directly or through the body of
+// a custom command
+fn ends_with_external<'a>(engine_state: &'a EngineState, block: &'a Block) -> bool {
+    let mut block = block;
+    let mut seen = vec![];
+
+    loop {
+        let last = match block.stmts.last() {
+            Some(Statement::Pipeline(pipeline)) => pipeline.expressions.last(),
+            _ => None,
+        };
+
+        match last.map(|expression| &expression.expr) {
+            Some(Expr::ExternalCall(..)) => return true,
+            Some(Expr::Call(call)) => match engine_state.get_decl(call.decl_id).get_block_id() {
+                Some(block_id) if !seen.contains(&block_id) => {
+                    seen.push(block_id);
+                    block = engine_state.get_block(block_id);
+                }
+                _ => return false,
+            },
+            _ => return false,
+        }
+    }
+}
+
 // This fill collect environment variables from std::env and adds them to a stack.
 //
 // In order to ensure the values have spans, it first creates a dummy file, writes the collected
@@ -606,6 +674,18 @@ fn gather_parent_env_vars(engine_state: &mut EngineState, stack: &mut Stack) {
             stack.add_env_var(name, value);
         }
     }
+
+    // The PWD inherited from the parent may be stale, so it is taken from the directory we were
+    // started in. From here on `$env.PWD` is the current directory, the process one is not changed
+    if let Ok(cwd) = std::env::current_dir() {
+        stack.add_env_var(
+            "PWD".into(),
+            Value::String {
+                val: cwd.to_string_lossy().to_string(),
+                span: Span::new(0, 0),
+            },
+        );
+    }
 }
 
 fn print_pipeline_data(
@@ -736,6 +816,9 @@ fn eval_source(
     source: &str,
     fname: &str,
 ) -> bool {
+    // `source`, `use` and `register` resolve their files against the `PWD` of the stack
+    engine_state.cwd = current_dir(stack).ok();
+
     let (block, delta) = {
         let mut working_set = StateWorkingSet::new(engine_state);
         let (output, err) = parse(
@@ -764,7 +847,12 @@ fn eval_source(
         PipelineData::new(Span::new(0, 0)), // Don't try this at home, 0 span is ignored
     ) {
         Ok(pipeline_data) => {
-            if let Err(err) = print_pipeline_data(pipeline_data, engine_state, stack) {
+            let result = print_pipeline_data(pipeline_data, engine_state, stack);
+
+            // The output has been printed, so the externals of the pipeline are done
+            stack.record_exit_codes();
+
+            if let Err(err) = result {
                 let working_set = StateWorkingSet::new(engine_state);
 
                 report_error(&working_set, &err);
@@ -3,6 +3,7 @@ mod test_converters;
 mod test_custom_commands;
 mod test_engine;
 mod test_env;
+mod test_externals;
 mod test_hiding;
 mod test_iteration;
 mod test_math;
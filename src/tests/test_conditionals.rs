@@ -1,4 +1,4 @@
-use crate::tests::{run_test, TestResult};
+use crate::tests::{fail_test, run_test, TestResult};
 
 #[test]
 fn if_test1() -> TestResult {
@@ -59,3 +59,68 @@ fn if_elseif3() -> TestResult {
 fn if_elseif4() -> TestResult {
     run_test("if 2 > 3 { 5 } else if 6 < 7 { 4 } else { 8 } ", "4")
 }
+
+#[test]
+fn match_literal() -> TestResult {
+    run_test(r#"match 2 { 1 => 'one', 2 => 'two', _ => 'other' }"#, "two")
+}
+
+#[test]
+fn match_wildcard() -> TestResult {
+    run_test(
+        r#"match 3 { 1 => 'one', 2 => 'two', _ => 'other' }"#,
+        "other",
+    )
+}
+
+#[test]
+fn match_range() -> TestResult {
+    run_test(r#"match 7 { 1..5 => 'small', 6..10 => 'big' }"#, "big")
+}
+
+#[test]
+fn match_list_with_rest() -> TestResult {
+    run_test(
+        r#"match [1 2 3] { [$first, ..$rest] => ($first + ($rest | math sum)) }"#,
+        "6",
+    )
+}
+
+#[test]
+fn match_list_length() -> TestResult {
+    run_test(
+        r#"match [1 2 3] { [$a] => 'one', [$a, $b] => 'two', [$a, ..] => 'many' }"#,
+        "many",
+    )
+}
+
+#[test]
+fn match_record() -> TestResult {
+    run_test(
+        r#"match {name: 'bob', age: 25} { {name: $n, age: 30} => 'old', {name: $n} => $n }"#,
+        "bob",
+    )
+}
+
+#[test]
+fn match_record_shorthand() -> TestResult {
+    run_test(r#"match {name: 'bob'} { {$name} => $name }"#, "bob")
+}
+
+#[test]
+fn match_guard() -> TestResult {
+    run_test(
+        r#"match [1 2] { [$a, $b] if $a > $b => 'descending', [$a, $b] => 'ascending' }"#,
+        "ascending",
+    )
+}
+
+#[test]
+fn match_block_result() -> TestResult {
+    run_test(r#"match [5] { [$x] => { $x * 2 } }"#, "10")
+}
+
+#[test]
+fn match_rest_not_last_fails() -> TestResult {
+    fail_test(r#"match [1] { [..$rest, $x] => 1 }"#, "rest pattern")
+}
@@ -117,3 +117,98 @@ fn allow_missing_optional_params() -> TestResult {
         "5",
     )
 }
+
+#[test]
+fn default_positional_value() -> TestResult {
+    run_test(
+        r#"def greet [name: string = "world"] { $"hello ($name)" }; greet"#,
+        "hello world",
+    )
+}
+
+#[test]
+fn default_positional_value_overridden() -> TestResult {
+    run_test(
+        r#"def greet [name: string = "world"] { $"hello ($name)" }; greet nu"#,
+        "hello nu",
+    )
+}
+
+#[test]
+fn default_flag_value() -> TestResult {
+    run_test(r#"def foo [--times: int = 3] { $times * 2 }; foo"#, "6")
+}
+
+#[test]
+fn default_flag_value_overridden() -> TestResult {
+    run_test(
+        r#"def foo [--times: int = 3] { $times * 2 }; foo --times 5"#,
+        "10",
+    )
+}
+
+#[test]
+fn default_value_type_mismatch() -> TestResult {
+    fail_test(r#"def foo [x: int = "bar"] { $x }"#, "expected int")
+}
+
+#[test]
+fn default_value_on_switch() -> TestResult {
+    fail_test(
+        r#"def foo [--dry-run = 1] { $dry-run }"#,
+        "flag with a type",
+    )
+}
+
+#[test]
+fn default_value_in_help() -> TestResult {
+    run_test(
+        r#"def greet [name: string = "world"] { $name }; greet --help | str contains 'default: "world"'"#,
+        "true",
+    )
+}
+
+#[test]
+fn argument_type_checked_at_runtime() -> TestResult {
+    fail_test(
+        r#"def bar [] { "bar" }; def foo [x: int] { $x }; foo (bar)"#,
+        "expected int, found string",
+    )
+}
+
+#[test]
+fn flag_type_checked_at_runtime() -> TestResult {
+    fail_test(
+        r#"def bar [] { "bar" }; def foo [--x: int] { $x }; foo --x (bar)"#,
+        "expected int, found string",
+    )
+}
+
+#[test]
+fn number_argument_accepts_float() -> TestResult {
+    run_test(
+        r#"def bar [] { 1.5 }; def foo [x: number] { $x }; foo (bar)"#,
+        "1.5",
+    )
+}
+
+#[test]
+fn record_argument_accepts_any_fields() -> TestResult {
+    run_test(
+        r#"def bar [] { {a: 1} }; def foo [x: record] { $x.a }; foo (bar)"#,
+        "1",
+    )
+}
+
+#[test]
+fn record_literal_argument() -> TestResult {
+    run_test(r#"def foo [x: record] { $x.a }; foo {a: 1}"#, "1")
+}
+
+#[test]
+fn record_argument_rejects_list() -> TestResult {
+    fail_test(
+        r#"def bar [] { [1 2] }; def foo [x: record] { $x }; foo (bar)"#,
+        "expected record",
+    )
+}
@@ -87,3 +87,199 @@ fn earlier_errors() -> TestResult {
         "int",
     )
 }
+
+#[test]
+fn mutable_variable_assign() -> TestResult {
+    run_test(r#"mut x = 1; $x = 5; $x"#, "5")
+}
+
+#[test]
+fn mutable_variable_plus_assign() -> TestResult {
+    run_test(r#"mut x = 1; $x += 2; $x"#, "3")
+}
+
+#[test]
+fn mutable_variable_minus_assign() -> TestResult {
+    run_test(r#"mut x = 10; $x -= 4; $x"#, "6")
+}
+
+#[test]
+fn assign_to_immutable_variable_fails() -> TestResult {
+    fail_test(r#"let x = 1; $x = 2"#, "mutable")
+}
+
+#[test]
+fn assign_with_wrong_type_fails() -> TestResult {
+    fail_test(r#"mut x = 1; $x = "a""#, "mismatch")
+}
+
+#[test]
+fn capture_of_mutable_variable_fails() -> TestResult {
+    fail_test(r#"mut x = 1; [1 2] | each { $x }"#, "mutable")
+}
+
+#[test]
+fn block_argument_of_in_place_command_is_captured() -> TestResult {
+    fail_test(r#"mut x = 0; do { |b| do $b } { $x += 1 }"#, "mutable")
+}
+
+#[test]
+fn if_updates_mutable_variable() -> TestResult {
+    run_test(
+        r#"mut x = 0; if $false { $x = 3 } else { $x = 4 }; $x"#,
+        "4",
+    )
+}
+
+#[test]
+fn nested_if_updates_mutable_variable() -> TestResult {
+    run_test(r#"mut x = 0; if $true { if $true { $x = 9 } }; $x"#, "9")
+}
+
+#[test]
+fn do_updates_mutable_variable() -> TestResult {
+    run_test(r#"mut x = 1; do { $x += 4 }; $x"#, "5")
+}
+
+#[test]
+fn match_updates_mutable_variable() -> TestResult {
+    run_test(r#"mut x = 0; match 2 { 2 => { $x = 7 }, _ => 0 }; $x"#, "7")
+}
+
+#[test]
+fn try_and_catch_update_mutable_variable() -> TestResult {
+    run_test(
+        r#"mut x = 0; try { $x = 1; 1 / 0 } catch { $x += 10 }; $x"#,
+        "11",
+    )
+}
+
+#[test]
+fn try_catch_internal_error() -> TestResult {
+    run_test(
+        r#"try { 1 / 0 } catch { |err| $err.msg }"#,
+        "Division by zero.",
+    )
+}
+
+#[test]
+fn try_catch_error_label() -> TestResult {
+    run_test(
+        r#"try { 1 / 0 } catch { |err| $err.label }"#,
+        "division by zero",
+    )
+}
+
+#[test]
+fn try_catch_error_span() -> TestResult {
+    run_test(
+        r#"try { 1 / 0 } catch { |err| $err.span.end - $err.span.start }"#,
+        "1",
+    )
+}
+
+#[test]
+fn try_catch_custom_command_error() -> TestResult {
+    run_test(
+        r#"def foo [] { 1 / 0 }; try { foo } catch { 'caught' }"#,
+        "caught",
+    )
+}
+
+#[test]
+fn try_catch_external_spawn_error() -> TestResult {
+    run_test(
+        r#"try { nonexistent_command_for_try_catch } catch { 'caught' }"#,
+        "caught",
+    )
+}
+
+#[test]
+fn try_without_error() -> TestResult {
+    run_test(r#"try { 5 } catch { 6 }"#, "5")
+}
+
+#[test]
+fn try_does_not_catch_break() -> TestResult {
+    run_test(
+        r#"mut i = 0; while $i < 3 { $i += 1; try { break } catch { 9 } }; $i"#,
+        "1",
+    )
+}
+
+#[test]
+fn let_destructure_list() -> TestResult {
+    run_test(
+        r#"let [a, b, ...rest] = [1 2 3 4]; $a + $b + ($rest | length)"#,
+        "5",
+    )
+}
+
+#[test]
+fn let_destructure_record() -> TestResult {
+    run_test(
+        r#"let {name, age: years} = {name: 'bob', age: 3}; $"($name) ($years)""#,
+        "bob 3",
+    )
+}
+
+#[test]
+fn let_destructure_nested() -> TestResult {
+    run_test(r#"let [_, {x}] = [0, {x: 7}]; $x"#, "7")
+}
+
+#[test]
+fn mut_destructure_list() -> TestResult {
+    run_test(r#"mut [a, b] = [1 2]; $a = 10; $a + $b"#, "12")
+}
+
+#[test]
+fn let_destructure_wrong_length_fails() -> TestResult {
+    fail_test(r#"let [a, b] = (echo [1 2 3])"#, "list of 2 items")
+}
+
+#[test]
+fn let_destructure_missing_field_fails() -> TestResult {
+    fail_test(r#"let {name} = (echo {age: 3})"#, "field named 'name'")
+}
+
+#[test]
+fn let_destructure_type_mismatch_fails() -> TestResult {
+    fail_test(r#"let [a, b] = {x: 1}"#, "expected a list")
+}
+
+#[test]
+fn do_closure_with_args() -> TestResult {
+    run_test(r#"let f = { |x, y| $x + $y }; do $f 1 2"#, "3")
+}
+
+#[test]
+fn closure_keeps_captures_after_scope_ends() -> TestResult {
+    run_test(
+        r#"def make [] { let x = 10; { |y| $x + $y } }; let g = (make); do $g 5"#,
+        "15",
+    )
+}
+
+#[test]
+fn closure_in_record() -> TestResult {
+    run_test(r#"let r = {f: { |x| $x * 2 }}; do $r.f 21"#, "42")
+}
+
+#[test]
+fn closure_in_list() -> TestResult {
+    run_test(r#"let l = [{ 1 } { 2 }]; do $l.1"#, "2")
+}
+
+#[test]
+fn closure_passed_to_custom_command() -> TestResult {
+    run_test(
+        r#"def apply [blk: block, v] { do $blk $v }; apply { |x| $x + 1 } 41"#,
+        "42",
+    )
+}
+
+#[test]
+fn closure_variable_passed_to_each() -> TestResult {
+    run_test(r#"let f = { |x| $x * 3 }; [1 2] | each $f | math sum"#, "9")
+}
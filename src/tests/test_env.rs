@@ -1,4 +1,7 @@
-use crate::tests::{run_test, TestResult};
+use crate::eval_source;
+use crate::tests::{fail_test, run_test, TestResult};
+use nu_command::create_default_context;
+use nu_protocol::engine::Stack;
 
 #[test]
 fn shorthand_env_1() -> TestResult {
@@ -14,3 +17,104 @@ fn shorthand_env_2() -> TestResult {
 fn shorthand_env_3() -> TestResult {
     run_test(r#"FOO=BAZ BAR=MOO $nu.env.FOO"#, "BAZ")
 }
+
+#[test]
+fn cd_sets_pwd() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    let dir = dir.path().display();
+
+    run_test(&format!("cd {}; $nu.env.PWD", dir), &dir.to_string())
+}
+
+#[test]
+fn cd_resolves_relative_path_against_pwd() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::create_dir(dir.path().join("sub"))?;
+
+    run_test(
+        &format!(
+            "cd {}; cd sub; cd ..; cd sub; $nu.env.PWD",
+            dir.path().display()
+        ),
+        &dir.path().join("sub").display().to_string(),
+    )
+}
+
+#[test]
+fn cd_to_missing_directory_fails() -> TestResult {
+    fail_test(r#"cd this_directory_does_not_exist"#, "directory not found")
+}
+
+#[test]
+fn cd_in_block_does_not_change_caller_pwd() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::create_dir(dir.path().join("sub"))?;
+    let dir = dir.path().display();
+
+    run_test(
+        &format!("cd {}; do {{ cd sub }}; $nu.env.PWD", dir),
+        &dir.to_string(),
+    )
+}
+
+#[test]
+fn open_and_ls_use_pwd() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a.json"), r#"{"a": 3}"#)?;
+    std::fs::write(dir.path().join("b.json"), r#"{"a": 4}"#)?;
+
+    run_test(
+        &format!(
+            "cd {}; (open a.json).a + (ls | length)",
+            dir.path().display()
+        ),
+        "5",
+    )
+}
+
+#[test]
+fn with_env_pwd_changes_working_directory() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a.json"), r#"{"a": 3}"#)?;
+
+    run_test(
+        &format!(
+            "with-env [PWD {}] {{ (open a.json).a }}",
+            dir.path().display()
+        ),
+        "3",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn externals_run_in_pwd() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    // `pwd` prints the path with symlinks resolved
+    let dir = dir.path().canonicalize()?;
+    let dir = dir.display();
+
+    run_test(&format!("cd {}; ^pwd", dir), &dir.to_string())
+}
+
+#[test]
+fn source_after_cd_resolves_against_pwd() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("spam.nu"), "def spam [] { 3 }")?;
+
+    // Each entry is parsed on its own, like in the REPL, so the `cd` has run when `source` is parsed
+    let mut engine_state = create_default_context();
+    let mut stack = Stack::new();
+    let cd = format!("cd {}", dir.path().display());
+
+    assert!(eval_source(&mut engine_state, &mut stack, &cd, "cd"));
+    assert!(eval_source(
+        &mut engine_state,
+        &mut stack,
+        "source spam.nu",
+        "source"
+    ));
+    assert!(engine_state.find_decl(b"spam").is_some());
+
+    Ok(())
+}
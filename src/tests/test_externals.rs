@@ -0,0 +1,368 @@
+use crate::tests::{fail_test, run_test, TestResult};
+use assert_cmd::prelude::*;
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_is_recorded() -> TestResult {
+    run_test(
+        r#"^sh -c 'exit 3'; let code = $env.LAST_EXIT_CODE; ^true; $code"#,
+        "3",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_is_reset_by_success() -> TestResult {
+    run_test(r#"^false; ^true; $env.LAST_EXIT_CODE"#, "0")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn failing_external_aborts_pipeline() -> TestResult {
+    fail_test(r#"^sh -c 'exit 4' | lines"#, "exited with code 4")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn failing_external_at_end_of_script_sets_exit_status() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "^sh -c 'exit 3'")?;
+
+    let output = Command::cargo_bin("engine-q")?.arg(file.path()).output()?;
+
+    assert_eq!(output.status.code(), Some(3));
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn earlier_failing_external_does_not_set_exit_status() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "^sh -c 'exit 3'; echo done")?;
+
+    let output = Command::cargo_bin("engine-q")?.arg(file.path()).output()?;
+
+    assert_eq!(output.status.code(), Some(0));
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn failing_external_at_end_of_main_sets_exit_status() -> TestResult {
+    let mut file = NamedTempFile::new()?;
+    writeln!(file, "def main [] {{ ^sh -c 'exit 3' }}")?;
+
+    let output = Command::cargo_bin("engine-q")?.arg(file.path()).output()?;
+
+    assert_eq!(output.status.code(), Some(3));
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_of_piped_external() -> TestResult {
+    run_test(
+        r#"^sh -c 'exit 3'; ^echo hi | lines; $env.LAST_EXIT_CODE"#,
+        "0",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn consumer_that_stops_early_does_not_hang() -> TestResult {
+    run_test(r#"^yes | first 1; 5"#, "5")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn silent_external_left_by_consumer_is_stopped() -> TestResult {
+    run_test(
+        r#"^sh -c 'echo a; exec sleep 60' | first 1; $env.LAST_EXIT_CODE"#,
+        "-1",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_of_external_in_subexpression() -> TestResult {
+    run_test(r#"let out = (^sh -c 'exit 3'); $env.LAST_EXIT_CODE"#, "3")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_is_from_end_of_pipeline() -> TestResult {
+    run_test(r#"^sh -c 'exit 3' | ^cat; $env.LAST_EXIT_CODE"#, "0")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_from_custom_command() -> TestResult {
+    run_test(
+        r#"def f [] { ^sh -c 'exit 4' }; f; $env.LAST_EXIT_CODE"#,
+        "4",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_from_do() -> TestResult {
+    run_test(r#"do { ^sh -c 'exit 5' }; $env.LAST_EXIT_CODE"#, "5")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn last_exit_code_from_complete() -> TestResult {
+    run_test(
+        r#"let out = (^sh -c 'exit 6' | complete); $env.LAST_EXIT_CODE"#,
+        "6",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn complete_captures_stdout() -> TestResult {
+    run_test(
+        r#"(^sh -c 'echo out; echo err >&2; exit 2' | complete).stdout | str trim"#,
+        "out",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn complete_captures_stderr() -> TestResult {
+    run_test(
+        r#"(^sh -c 'echo out; echo err >&2; exit 2' | complete).stderr | str trim"#,
+        "err",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn complete_captures_exit_code() -> TestResult {
+    run_test(r#"(^sh -c 'exit 2' | complete).exit_code"#, "2")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn complete_requires_external_input() -> TestResult {
+    fail_test(r#"3 | complete"#, "only works on the output of an external")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn stderr_redirected_to_file() -> TestResult {
+    let file = NamedTempFile::new()?;
+
+    run_test(
+        &format!("^sh -c 'echo err >&2' err> {}", file.path().display()),
+        "",
+    )?;
+
+    assert_eq!(std::fs::read_to_string(file.path())?, "err\n");
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn stdout_and_stderr_redirected_to_file() -> TestResult {
+    let file = NamedTempFile::new()?;
+
+    run_test(
+        &format!(
+            "^sh -c 'echo out; echo err >&2' out+err> {}",
+            file.path().display()
+        ),
+        "",
+    )?;
+
+    assert_eq!(std::fs::read_to_string(file.path())?, "out\nerr\n");
+
+    Ok(())
+}
+
+#[test]
+fn redirection_without_target() -> TestResult {
+    fail_test(r#"^echo hi err>"#, "redirection target")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn argument_with_spaces_is_one_argument() -> TestResult {
+    run_test(r#"^printf '%s|' 'a b' c"#, "a b|c|")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn backslashes_in_arguments_are_kept() -> TestResult {
+    run_test(r#"^echo 'a\b'"#, r"a\b")
+}
+
+#[cfg(not(windows))]
+fn executable_in(dir: &std::path::Path, name: &str, script: &str) -> TestResult {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = dir.join(name);
+    std::fs::write(&path, script)?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+
+    Ok(())
+}
+
+#[cfg(not(windows))]
+#[test]
+fn external_is_found_in_env_path() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    executable_in(dir.path(), "nu_path_test", "#!/bin/sh\necho found $1\n")?;
+
+    run_test(
+        &format!(
+            "let-env PATH = '{}'; ^nu_path_test it",
+            dir.path().display()
+        ),
+        "found it",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn quoted_glob_is_passed_literally() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a"), "")?;
+
+    run_test(
+        &format!("^echo '{}/*'", dir.path().display()),
+        &format!("{}/*", dir.path().display()),
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn external_shell_is_opt_in() -> TestResult {
+    run_test(
+        "let config = {external_shell: $true}; ^echo a && echo b",
+        "a\nb",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn shell_operators_are_plain_arguments_by_default() -> TestResult {
+    run_test("^echo a && echo b", "a && echo b")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn bare_glob_is_expanded() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a.rs"), "")?;
+    std::fs::write(dir.path().join("b.rs"), "")?;
+    std::fs::write(dir.path().join("c.txt"), "")?;
+
+    let dir = dir.path().display();
+
+    run_test(
+        &format!("^printf '%s|' {}/*.rs", dir),
+        &format!("{0}/a.rs|{0}/b.rs|", dir),
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn relative_glob_is_expanded_in_pwd() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a.rs"), "")?;
+    std::fs::write(dir.path().join("b.rs"), "")?;
+
+    run_test(
+        &format!("cd {}; ^printf '%s|' *.rs", dir.path().display()),
+        "a.rs|b.rs|",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn glob_without_matches_fails() -> TestResult {
+    let dir = tempfile::tempdir()?;
+
+    fail_test(
+        &format!("^echo {}/*.rs", dir.path().display()),
+        "no matches found",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn relative_glob_without_matches_fails() -> TestResult {
+    let dir = tempfile::tempdir()?;
+
+    fail_test(
+        &format!("cd {}; ^ls *.zz", dir.path().display()),
+        "no matches found",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn invalid_glob_fails() -> TestResult {
+    fail_test("^echo a***b", "glob pattern")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn url_is_not_a_glob() -> TestResult {
+    run_test("^echo https://example.com/[a]*", "https://example.com/[a]*")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn question_mark_is_not_a_glob() -> TestResult {
+    run_test("^echo what?", "what?")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn tilde_glob_is_expanded_in_home() -> TestResult {
+    fail_test("^echo ~/*.nu_no_such_extension", "no matches found")
+}
+
+#[cfg(not(windows))]
+#[test]
+fn expanded_paths_are_quoted_for_external_shell() -> TestResult {
+    let dir = tempfile::tempdir()?;
+    std::fs::write(dir.path().join("a b.rs"), "")?;
+
+    let dir = dir.path().display();
+
+    run_test(
+        &format!(
+            "let config = {{external_shell: $true}}; ^printf '%s|' {}/*.rs",
+            dir
+        ),
+        &format!("{}/a b.rs|", dir),
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn variables_are_escaped_for_external_shell() -> TestResult {
+    run_test(
+        r#"let config = {external_shell: $true}; let x = "a; echo injected $(echo b)"; ^echo $x"#,
+        "a; echo injected $(echo b)",
+    )
+}
+
+#[cfg(not(windows))]
+#[test]
+fn subexpressions_are_escaped_for_external_shell() -> TestResult {
+    run_test(
+        r#"let config = {external_shell: $true}; ^echo ("a" + " | echo b")"#,
+        "a | echo b",
+    )
+}
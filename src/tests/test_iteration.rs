@@ -1,4 +1,4 @@
-use crate::tests::{run_test, TestResult};
+use crate::tests::{fail_test, run_test, TestResult};
 
 #[test]
 fn better_block_types() -> TestResult {
@@ -49,3 +49,145 @@ fn par_each() -> TestResult {
         "false",
     )
 }
+
+#[test]
+fn while_loop() -> TestResult {
+    run_test(r#"mut x = 0; while $x < 5 { $x += 1 }; $x"#, "5")
+}
+
+#[test]
+fn loop_with_break() -> TestResult {
+    run_test(
+        r#"mut x = 0; loop { if $x == 7 { break }; $x += 1 }; $x"#,
+        "7",
+    )
+}
+
+#[test]
+fn while_with_continue() -> TestResult {
+    run_test(
+        r#"mut x = 0; mut n = 0; while $x < 10 { $x += 1; if $x > 3 { continue }; $n += 1 }; $n"#,
+        "3",
+    )
+}
+
+#[test]
+fn for_with_break() -> TestResult {
+    run_test(
+        r#"for i in 1..10 { if $i == 4 { break } else { $i } } | length"#,
+        "3",
+    )
+}
+
+#[test]
+fn for_with_continue() -> TestResult {
+    run_test(
+        r#"for i in 1..5 { if $i == 2 { continue } else { $i } } | math sum"#,
+        "13",
+    )
+}
+
+#[test]
+fn for_is_streamed() -> TestResult {
+    run_test(r#"for i in 1..1000000000 { $i } | first 2 | math sum"#, "3")
+}
+
+#[test]
+fn for_with_break_updating_mutable_variable() -> TestResult {
+    run_test(
+        r#"mut n = 0; for i in 1..10 { if $i == 4 { break }; $n += 1; $i } | length"#,
+        "3",
+    )
+}
+
+#[test]
+fn for_with_continue_updating_mutable_variable() -> TestResult {
+    run_test(
+        r#"mut n = 0; for i in 1..5 { if $i == 2 { continue }; $n += 1; $i } | math sum"#,
+        "13",
+    )
+}
+
+#[test]
+fn mutable_variable_is_updated_before_break() -> TestResult {
+    run_test(
+        r#"mut s = 0; for i in 1..10 { if $i == 4 { break }; $s += $i }; $s"#,
+        "6",
+    )
+}
+
+#[test]
+fn for_updates_mutable_variable() -> TestResult {
+    run_test(r#"mut s = 0; for i in [1 2 3] { $s += $i }; $s"#, "6")
+}
+
+#[test]
+fn if_in_for_updates_mutable_variable() -> TestResult {
+    run_test(
+        r#"mut x = 0; for i in 1..3 { if $i > 1 { $x += 1 } }; $x"#,
+        "2",
+    )
+}
+
+#[test]
+fn if_in_while_updates_mutable_variable() -> TestResult {
+    run_test(
+        r#"mut c = 0; while $true { if $c > 3 { break }; $c += 1 }; $c"#,
+        "4",
+    )
+}
+
+#[test]
+fn assignment_before_break_is_kept() -> TestResult {
+    run_test(
+        r#"mut i = 0; while $i < 5 { $i += 1; if $i == 2 { $i += 10; break } }; $i"#,
+        "12",
+    )
+}
+
+#[test]
+fn break_only_leaves_innermost_loop() -> TestResult {
+    run_test(
+        r#"mut i = 0; while $i < 3 { $i += 1; for j in [1 2] { if $j == 2 { break } } }; $i"#,
+        "3",
+    )
+}
+
+#[test]
+fn break_outside_loop_fails() -> TestResult {
+    fail_test(r#"if true { break }"#, "outside of a loop")
+}
+
+#[test]
+fn continue_in_custom_command_fails() -> TestResult {
+    fail_test(
+        r#"while true { def foo [] { continue }; break }"#,
+        "outside of a loop",
+    )
+}
+
+#[test]
+fn break_in_each_inside_loop_fails() -> TestResult {
+    fail_test(
+        r#"for x in [1] { [1] | each { break } }"#,
+        "outside of a loop",
+    )
+}
+
+#[test]
+fn break_in_stored_block_inside_loop_fails() -> TestResult {
+    fail_test(r#"loop { let f = { break }; break }"#, "outside of a loop")
+}
+
+#[test]
+fn break_in_do_inside_loop() -> TestResult {
+    run_test(
+        r#"for i in 1..10 { do { if $i == 3 { break } }; $i } | length"#,
+        "2",
+    )
+}
+
+#[test]
+fn for_errors_become_values() -> TestResult {
+    run_test(r#"for x in [1 0 2] { 10 / $x } | length"#, "3")
+}
@@ -25,6 +25,66 @@ fn modulo2() -> TestResult {
     run_test("5.25 mod 2", "1.25")
 }
 
+#[test]
+fn modulo_negative_takes_sign_of_divisor() -> TestResult {
+    run_test("-7 mod 3", "2")
+}
+
+#[test]
+fn floor_division_of_ints() -> TestResult {
+    run_test("7 // 2", "3")
+}
+
+#[test]
+fn floor_division_of_negative_ints() -> TestResult {
+    run_test("-7 // 2", "-4")
+}
+
+#[test]
+fn floor_division_of_floats() -> TestResult {
+    run_test("-7.5 // 2", "-4")
+}
+
+#[test]
+fn modulo_negative_divisor_takes_sign_of_divisor() -> TestResult {
+    run_test("7 mod -3", "-2")
+}
+
+#[test]
+fn floor_division_agrees_with_modulo() -> TestResult {
+    run_test("(-7 // 3) * 3 + (-7 mod 3) == -7", "true")
+}
+
+#[test]
+fn floor_division_agrees_with_modulo_for_negative_divisor() -> TestResult {
+    run_test("(7 // -3) * -3 + (7 mod -3) == 7", "true")
+}
+
+#[test]
+fn modulo_of_negative_durations() -> TestResult {
+    run_test("(-7sec mod 3sec) == 2sec", "true")
+}
+
+#[test]
+fn floor_division_of_filesizes() -> TestResult {
+    run_test("10kb // 3kb", "3")
+}
+
+#[test]
+fn floor_division_of_durations() -> TestResult {
+    run_test("7sec // 2sec", "3")
+}
+
+#[test]
+fn floor_division_of_duration_by_int() -> TestResult {
+    run_test("(7ns // 2) == 3ns", "true")
+}
+
+#[test]
+fn floor_division_by_zero_fails() -> TestResult {
+    fail_test("7 // 0", "division by zero")
+}
+
 #[test]
 fn and() -> TestResult {
     run_test("$true && $false", "false")
@@ -50,6 +110,41 @@ fn not_contains() -> TestResult {
     run_test("'testme' !~ 'test'", "false")
 }
 
+#[test]
+fn regex_match() -> TestResult {
+    run_test(r#"'404 error' =~ '^\d+ err'"#, "true")
+}
+
+#[test]
+fn regex_match_anchored_fails() -> TestResult {
+    run_test("'testme' =~ '^me'", "false")
+}
+
+#[test]
+fn not_regex_match() -> TestResult {
+    run_test("'testme' !~ '^me'", "true")
+}
+
+#[test]
+fn invalid_regex_fails() -> TestResult {
+    fail_test("'testme' =~ '(test'", "invalid regex")
+}
+
+#[test]
+fn starts_with() -> TestResult {
+    run_test("'foobar' starts-with 'foo'", "true")
+}
+
+#[test]
+fn ends_with() -> TestResult {
+    run_test("'foobar' ends-with 'foo'", "false")
+}
+
+#[test]
+fn starts_with_on_int_fails() -> TestResult {
+    fail_test("3 starts-with 'foo'", "doesn't support")
+}
+
 #[test]
 fn floating_add() -> TestResult {
     run_test("10.1 + 0.8", "10.9")
@@ -59,3 +154,58 @@ fn floating_add() -> TestResult {
 fn precedence_of_or_groups() -> TestResult {
     run_test(r#"4 mod 3 == 0 || 5 mod 5 == 0"#, "true")
 }
+
+#[test]
+fn bit_and() -> TestResult {
+    run_test("12 bit-and 10", "8")
+}
+
+#[test]
+fn bit_or() -> TestResult {
+    run_test("12 bit-or 10", "14")
+}
+
+#[test]
+fn bit_xor() -> TestResult {
+    run_test("12 bit-xor 10", "6")
+}
+
+#[test]
+fn bit_shl() -> TestResult {
+    run_test("1 bit-shl 4", "16")
+}
+
+#[test]
+fn bit_shr() -> TestResult {
+    run_test("-16 bit-shr 2", "-4")
+}
+
+#[test]
+fn bit_shl_overflow() -> TestResult {
+    fail_test("1 bit-shl 64", "overflow")
+}
+
+#[test]
+fn bit_shl_losing_bits_overflows() -> TestResult {
+    fail_test("0x4000000000000000 bit-shl 2", "overflow")
+}
+
+#[test]
+fn bit_shr_negative_amount_overflows() -> TestResult {
+    fail_test("16 bit-shr -1", "overflow")
+}
+
+#[test]
+fn precedence_of_bit_operators() -> TestResult {
+    run_test("1 bit-or 6 bit-and 3 bit-shl 1", "7")
+}
+
+#[test]
+fn bit_operator_on_float_fails() -> TestResult {
+    fail_test("1.5 bit-and 1", "doesn't support")
+}
+
+#[test]
+fn bits_not() -> TestResult {
+    run_test("5 | bits not", "-6")
+}
@@ -130,6 +130,22 @@ fn where_on_ranges() -> TestResult {
     run_test(r#"1..10 | where $it > 8 | math sum"#, "19")
 }
 
+#[test]
+fn where_starts_with() -> TestResult {
+    run_test(
+        r#"[[name]; [foo] [bar] [food]] | where name starts-with "foo" | length"#,
+        "2",
+    )
+}
+
+#[test]
+fn where_regex_match() -> TestResult {
+    run_test(
+        r#"[[msg]; ['12 err'] ['ok'] ['7 err: bad']] | where msg =~ '^\d+ err' | get msg.1"#,
+        "7 err: bad",
+    )
+}
+
 #[test]
 fn index_on_list() -> TestResult {
     run_test(r#"[1, 2, 3].1"#, "2")
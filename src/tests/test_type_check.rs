@@ -14,3 +14,89 @@ fn type_in_list_of_this_type() -> TestResult {
 fn type_in_list_of_non_this_type() -> TestResult {
     fail_test(r#"'hello' in [41 42 43]"#, "mismatched for operation")
 }
+
+#[test]
+fn input_type_matches_signature() -> TestResult {
+    run_test(r#"['a' 'b'] | str upcase | get 1"#, "B")
+}
+
+#[test]
+fn input_type_mismatch() -> TestResult {
+    fail_test(r#"[[a]; [x]] | str upcase"#, "doesn't support")
+}
+
+#[test]
+fn input_type_mismatch_from_output_type() -> TestResult {
+    fail_test(r#"[1 2] | bits not | str upcase"#, "doesn't support")
+}
+
+#[test]
+fn input_type_cell_path_passes_through() -> TestResult {
+    run_test(r#"[[a]; [x]] | str upcase a | get a.0"#, "X")
+}
+
+#[test]
+fn table_output_mismatch() -> TestResult {
+    fail_test(r#"ls | str upcase"#, "doesn't support")
+}
+
+#[test]
+fn length_of_record() -> TestResult {
+    run_test(r#"{a: 1} | length"#, "1")
+}
+
+#[test]
+fn length_of_string() -> TestResult {
+    run_test(r#""abc" | length"#, "1")
+}
+
+#[test]
+fn filtered_table_output_mismatch() -> TestResult {
+    fail_test(r#"ls | where size > 10 | str upcase"#, "doesn't support")
+}
+
+#[test]
+fn reduced_table_output_mismatch() -> TestResult {
+    fail_test(r#"ls | math sum | str upcase"#, "doesn't support")
+}
+
+#[test]
+fn lines_of_int_mismatch() -> TestResult {
+    fail_test(r#"5 | lines"#, "doesn't support")
+}
+
+#[test]
+fn date_output_mismatch() -> TestResult {
+    fail_test(r#"date now | str upcase"#, "doesn't support")
+}
+
+#[test]
+fn get_column_then_string_command() -> TestResult {
+    run_test(r#"[[a]; [x]] | get a | str upcase | get 0"#, "X")
+}
+
+#[test]
+fn each_then_reducer() -> TestResult {
+    run_test(r#"[1 2 3] | each { $it + 1 } | math sum"#, "9")
+}
+
+#[test]
+fn path_output_into_string_command() -> TestResult {
+    run_test(
+        r#"['a/b' 'c/d'] | path basename | str upcase | get 1"#,
+        "D",
+    )
+}
+
+#[test]
+fn conversion_then_math() -> TestResult {
+    run_test(r#""-3" | into int | math abs"#, "3")
+}
+
+#[test]
+fn list_of_unknown_is_not_taken_for_table() -> TestResult {
+    run_test(
+        r#"[1 2] | each { $it } | where $it > 1 | into string | str upcase | get 0"#,
+        "2",
+    )
+}